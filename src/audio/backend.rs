@@ -0,0 +1,169 @@
+//! Output backends - device lookup by name, and a sink for runs without one
+//!
+//! A named device is matched exactly, then by case-insensitive substring, so
+//! `--audio-device usb` finds "USB Audio CODEC". When no device can be opened
+//! (CI, headless servers, a device that was unplugged) the mix goes to a sink
+//! thread instead, which drains the rings in real time and either discards
+//! the audio or writes it to a WAV file.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use cpal::traits::{DeviceTrait, HostTrait};
+
+use super::channel::{SpatialReceiver, TriggerReceiver};
+use super::output::AudioState;
+use super::render::DEFAULT_RENDER_SAMPLE_RATE;
+use super::wav::WavWriter;
+
+/// Device name that selects the null sink instead of any device
+pub const NULL_AUDIO_DEVICE: &str = "null";
+
+/// How often the sink thread wakes
+const SINK_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How often a file sink rewrites its header
+const SINK_SYNC_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Names of the host's output devices
+pub fn output_device_names() -> Vec<String> {
+    cpal::default_host()
+        .output_devices()
+        .map(|devices| devices.filter_map(|device| device.name().ok()).collect())
+        .unwrap_or_default()
+}
+
+/// The output device called `name`, or the default device for None
+pub(super) fn find_output_device(name: Option<&str>) -> Result<cpal::Device, String> {
+    let host = cpal::default_host();
+    let Some(name) = name else {
+        return host
+            .default_output_device()
+            .ok_or_else(|| "no audio output device available".to_string());
+    };
+
+    let mut devices: Vec<(String, cpal::Device)> = host
+        .output_devices()
+        .map_err(|err| format!("cannot list audio devices: {}", err))?
+        .filter_map(|device| Some((device.name().ok()?, device)))
+        .collect();
+
+    let wanted = name.to_lowercase();
+    let position = devices
+        .iter()
+        .position(|(device_name, _)| device_name == name)
+        .or_else(|| {
+            devices
+                .iter()
+                .position(|(device_name, _)| device_name.to_lowercase().contains(&wanted))
+        });
+    match position {
+        Some(index) => Ok(devices.swap_remove(index).1),
+        None => Err(format!("no audio output device named '{}'", name)),
+    }
+}
+
+/// Real-time stand-in for an output stream
+pub(super) struct SinkThread {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+    /// The WAV being written, if any
+    pub(super) path: Option<PathBuf>,
+}
+
+impl SinkThread {
+    /// Start draining the rings; with a path the mix is rendered into it
+    pub(super) fn spawn(
+        triggers: TriggerReceiver,
+        spatial: SpatialReceiver,
+        master_volume: f32,
+        path: Option<PathBuf>,
+    ) -> std::io::Result<Self> {
+        let writer = match &path {
+            Some(path) => Some(WavWriter::create(path, DEFAULT_RENDER_SAMPLE_RATE)?),
+            None => None,
+        };
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = thread::Builder::new().name("lightwatch-audio-sink".into()).spawn({
+            let stop = stop.clone();
+            move || run_sink(triggers, spatial, master_volume, writer, &stop)
+        })?;
+
+        Ok(Self {
+            stop,
+            handle: Some(handle),
+            path,
+        })
+    }
+}
+
+impl Drop for SinkThread {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn run_sink(
+    mut triggers: TriggerReceiver,
+    mut spatial: SpatialReceiver,
+    master_volume: f32,
+    mut writer: Option<WavWriter>,
+    stop: &AtomicBool,
+) {
+    let sample_rate = DEFAULT_RENDER_SAMPLE_RATE;
+    let mut state = AudioState::new(sample_rate as f32, master_volume);
+    let started = Instant::now();
+    let mut last_sync = started;
+    let mut rendered: u64 = 0;
+
+    while !stop.load(Ordering::Relaxed) {
+        thread::sleep(SINK_INTERVAL);
+        let now = Instant::now();
+
+        let Some(file) = writer.as_mut() else {
+            // Null sink: nothing to play, only keep the rings moving
+            triggers.drain(now, |_| {});
+            spatial.latest();
+            continue;
+        };
+
+        triggers.drain(now, |trigger| state.process_trigger(trigger));
+        if let Some(spatial_data) = spatial.latest() {
+            state.update_spatial(&spatial_data);
+        }
+
+        let due = (now - started).as_secs_f64() * sample_rate as f64;
+        while (rendered as f64) < due {
+            let (left, right) = state.generate_frame(None);
+            if let Err(err) = file.write_frame(left, right) {
+                error!(target: "lightwatch::audio", "Audio file sink stopped: {}", err);
+                writer = None;
+                break;
+            }
+            rendered += 1;
+        }
+
+        if let Some(file) = writer.as_mut() {
+            if now - last_sync >= SINK_SYNC_INTERVAL {
+                last_sync = now;
+                if let Err(err) = file.sync() {
+                    warn!(target: "lightwatch::audio", "Audio file sink header: {}", err);
+                }
+            }
+        }
+    }
+
+    if let Some(file) = writer {
+        if let Err(err) = file.finish() {
+            warn!(target: "lightwatch::audio", "Audio file sink header: {}", err);
+        }
+    }
+}
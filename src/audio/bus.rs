@@ -0,0 +1,125 @@
+//! Traveler voice bus - one traveler's sound placed in the stereo field
+//!
+//! Everything a traveler plays (leitmotif, its notes, grief) is mixed onto its
+//! own bus. The bus resamples the source by the Doppler pitch, darkens it with
+//! a low-pass for distance, and applies the distance gain and a constant-power
+//! pan. Targets arrive from the ECS a frame at a time, so every parameter is
+//! smoothed per sample to avoid zipper noise and pitch steps.
+
+use std::f32::consts::FRAC_PI_4;
+
+use super::output::SpatialSourceData;
+use super::{BiquadFilter, FilterType};
+
+/// Gain and pan smoothing time (seconds)
+const LEVEL_SMOOTHING: f32 = 0.03;
+
+/// Doppler pitch smoothing time (seconds)
+const PITCH_SMOOTHING: f32 = 0.15;
+
+/// Low-pass cutoff smoothing time (seconds)
+const CUTOFF_SMOOTHING: f32 = 0.1;
+
+/// Samples between low-pass coefficient updates
+const CUTOFF_UPDATE_INTERVAL: u32 = 32;
+
+/// One-pole smoothing coefficient for `seconds` at `sample_rate`
+fn smoothing(seconds: f32, sample_rate: f32) -> f32 {
+    1.0 - (-1.0 / (seconds * sample_rate)).exp()
+}
+
+/// A parameter that glides to its target
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Smoothed {
+    current: f32,
+    pub(super) target: f32,
+    coefficient: f32,
+}
+
+impl Smoothed {
+    pub(super) fn new(value: f32, seconds: f32, sample_rate: f32) -> Self {
+        Self {
+            current: value,
+            target: value,
+            coefficient: smoothing(seconds, sample_rate),
+        }
+    }
+
+    pub(super) fn next(&mut self) -> f32 {
+        self.current += (self.target - self.current) * self.coefficient;
+        self.current
+    }
+}
+
+/// A traveler's bus
+pub struct TravelerBus {
+    gain: Smoothed,
+    pan: Smoothed,
+    pitch: Smoothed,
+    cutoff: Smoothed,
+    filter: BiquadFilter,
+    /// Cutoff the filter coefficients were last computed for
+    filter_cutoff: f32,
+    samples_to_cutoff_update: u32,
+    /// Resampler: the two source samples around the read position
+    previous: f32,
+    next: f32,
+    /// Read position between `previous` and `next`
+    fraction: f32,
+}
+
+impl TravelerBus {
+    pub fn new(sample_rate: f32) -> Self {
+        let open = SpatialSourceData::default();
+        Self {
+            gain: Smoothed::new(open.gain, LEVEL_SMOOTHING, sample_rate),
+            pan: Smoothed::new(open.pan, LEVEL_SMOOTHING, sample_rate),
+            pitch: Smoothed::new(open.pitch, PITCH_SMOOTHING, sample_rate),
+            cutoff: Smoothed::new(open.cutoff, CUTOFF_SMOOTHING, sample_rate),
+            filter: BiquadFilter::new(FilterType::LowPass, open.cutoff, 0.707, sample_rate),
+            filter_cutoff: open.cutoff,
+            samples_to_cutoff_update: 0,
+            previous: 0.0,
+            next: 0.0,
+            fraction: 0.0,
+        }
+    }
+
+    /// Glide toward the traveler's latest spatial data
+    pub fn set_target(&mut self, data: &SpatialSourceData) {
+        self.gain.target = data.gain.clamp(0.0, 1.0);
+        self.pan.target = data.pan.clamp(-1.0, 1.0);
+        self.pitch.target = data.pitch.clamp(0.5, 2.0);
+        self.cutoff.target = data.cutoff;
+    }
+
+    /// Next stereo frame; `source` is pulled once per source sample, which is
+    /// more or less often than once per frame as the pitch moves off 1.0
+    pub fn process(&mut self, mut source: impl FnMut() -> f32) -> (f32, f32) {
+        // Doppler: read the source at `pitch` samples per frame
+        self.fraction += self.pitch.next();
+        while self.fraction >= 1.0 {
+            self.previous = self.next;
+            self.next = source();
+            self.fraction -= 1.0;
+        }
+        let resampled = self.previous + (self.next - self.previous) * self.fraction;
+
+        // Distance low-pass
+        let cutoff = self.cutoff.next();
+        if self.samples_to_cutoff_update == 0 {
+            self.samples_to_cutoff_update = CUTOFF_UPDATE_INTERVAL;
+            if (cutoff - self.filter_cutoff).abs() > self.filter_cutoff * 0.01 {
+                self.filter.set_cutoff(cutoff);
+                self.filter_cutoff = cutoff;
+            }
+        }
+        self.samples_to_cutoff_update -= 1;
+        let filtered = self.filter.process(resampled);
+
+        // Distance gain and constant-power pan
+        let sample = filtered * self.gain.next();
+        let angle = (self.pan.next() + 1.0) * FRAC_PI_4;
+        (sample * angle.cos(), sample * angle.sin())
+    }
+}
@@ -0,0 +1,256 @@
+//! Audio thread communication - lock-free rings between the ECS and the callback
+//!
+//! Triggers and spatial snapshots reach the audio thread over single-producer,
+//! single-consumer ring buffers. ECS systems run in parallel, so the producer
+//! side sits behind a mutex; the consumer side is owned by the stream callback
+//! outright, which therefore never locks, blocks or allocates. Each stream
+//! `connect`s its own rings, so a rebuilt stream starts clean.
+//!
+//! The callback counts underruns and late triggers in `AudioStats`, and
+//! `report_audio_glitches` logs any increase as an `audio_glitch` wide event.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use ringbuf::{HeapConsumer, HeapProducer, HeapRb};
+
+use super::output::{AudioTrigger, SpatialMixData};
+use crate::core::{ExperienceClock, WideEventLog};
+use crate::wide_event;
+
+/// Triggers that can wait for the audio thread
+pub const TRIGGER_CAPACITY: usize = 1024;
+
+/// Spatial snapshots that can wait for the audio thread (only the latest is used)
+const SPATIAL_CAPACITY: usize = 8;
+
+/// A trigger picked up later than this after it was sent counts as late
+pub const LATE_TRIGGER: Duration = Duration::from_millis(50);
+
+/// How often glitch counters are checked (seconds)
+const GLITCH_REPORT_INTERVAL: f32 = 1.0;
+
+/// A trigger and when it was sent
+struct TimedTrigger {
+    trigger: AudioTrigger,
+    sent: Instant,
+}
+
+/// Counters shared between the audio thread and the ECS
+#[derive(Default)]
+struct AudioCounters {
+    underruns: AtomicU64,
+    late_triggers: AtomicU64,
+    dropped_triggers: AtomicU64,
+}
+
+/// Audio glitch counters
+#[derive(Clone, Default)]
+pub struct AudioStats {
+    counters: Arc<AudioCounters>,
+}
+
+/// Audio glitch counts at one moment
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AudioStatsSnapshot {
+    /// Gaps in the output stream
+    pub underruns: u64,
+    /// Triggers picked up more than `LATE_TRIGGER` after they were sent
+    pub late_triggers: u64,
+    /// Triggers lost to a full queue
+    pub dropped_triggers: u64,
+}
+
+impl AudioStats {
+    pub fn record_underrun(&self) {
+        self.counters.underruns.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_late_trigger(&self) {
+        self.counters.late_triggers.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_dropped_trigger(&self) {
+        self.counters.dropped_triggers.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> AudioStatsSnapshot {
+        AudioStatsSnapshot {
+            underruns: self.counters.underruns.load(Ordering::Relaxed),
+            late_triggers: self.counters.late_triggers.load(Ordering::Relaxed),
+            dropped_triggers: self.counters.dropped_triggers.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Trigger queue from the ECS to the audio thread
+#[derive(Resource, Default)]
+pub struct AudioTriggerQueue {
+    /// None until an audio thread connects; triggers are discarded until then
+    producer: Mutex<Option<HeapProducer<TimedTrigger>>>,
+    stats: AudioStats,
+}
+
+impl AudioTriggerQueue {
+    pub fn send(&self, trigger: AudioTrigger) {
+        let Ok(mut producer) = self.producer.lock() else {
+            return;
+        };
+        let Some(producer) = producer.as_mut() else {
+            return;
+        };
+
+        let sent = Instant::now();
+        if producer.push(TimedTrigger { trigger, sent }).is_err() {
+            self.stats.record_dropped_trigger();
+        }
+    }
+
+    /// Open a fresh ring for a new audio thread (replacing any earlier one)
+    pub fn connect(&self) -> TriggerReceiver {
+        let (producer, consumer) = HeapRb::new(TRIGGER_CAPACITY).split();
+        if let Ok(mut slot) = self.producer.lock() {
+            *slot = Some(producer);
+        }
+        TriggerReceiver {
+            consumer,
+            stats: self.stats.clone(),
+        }
+    }
+
+    /// Stop queueing triggers (the audio thread has gone)
+    pub fn disconnect(&self) {
+        if let Ok(mut slot) = self.producer.lock() {
+            *slot = None;
+        }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.producer.lock().is_ok_and(|slot| slot.is_some())
+    }
+
+    pub fn stats(&self) -> &AudioStats {
+        &self.stats
+    }
+}
+
+/// Audio thread end of the trigger queue
+pub struct TriggerReceiver {
+    consumer: HeapConsumer<TimedTrigger>,
+    stats: AudioStats,
+}
+
+impl TriggerReceiver {
+    /// Hand every waiting trigger to `apply`, counting those older than
+    /// `LATE_TRIGGER` at `now`
+    pub fn drain(&mut self, now: Instant, mut apply: impl FnMut(AudioTrigger)) {
+        while let Some(timed) = self.consumer.pop() {
+            if now.saturating_duration_since(timed.sent) > LATE_TRIGGER {
+                self.stats.record_late_trigger();
+            }
+            apply(timed.trigger);
+        }
+    }
+
+    /// Hand every waiting trigger to `apply` without lateness checks (offline
+    /// rendering has no real-time deadline)
+    pub fn drain_untimed(&mut self, mut apply: impl FnMut(AudioTrigger)) {
+        while let Some(timed) = self.consumer.pop() {
+            apply(timed.trigger);
+        }
+    }
+
+    pub fn stats(&self) -> &AudioStats {
+        &self.stats
+    }
+}
+
+/// Spatial snapshots from the ECS to the audio thread
+#[derive(Resource, Default)]
+pub struct SharedSpatialData {
+    producer: Mutex<Option<HeapProducer<SpatialMixData>>>,
+}
+
+impl SharedSpatialData {
+    /// Queue a snapshot; skipped while the audio thread is behind or absent
+    pub fn publish(&self, data: SpatialMixData) {
+        if let Ok(mut producer) = self.producer.lock() {
+            if let Some(producer) = producer.as_mut() {
+                let _ = producer.push(data);
+            }
+        }
+    }
+
+    /// Open a fresh ring for a new audio thread (replacing any earlier one)
+    pub fn connect(&self) -> SpatialReceiver {
+        let (producer, consumer) = HeapRb::new(SPATIAL_CAPACITY).split();
+        if let Ok(mut slot) = self.producer.lock() {
+            *slot = Some(producer);
+        }
+        SpatialReceiver { consumer }
+    }
+
+    pub fn disconnect(&self) {
+        if let Ok(mut slot) = self.producer.lock() {
+            *slot = None;
+        }
+    }
+}
+
+/// Audio thread end of the spatial snapshots
+pub struct SpatialReceiver {
+    consumer: HeapConsumer<SpatialMixData>,
+}
+
+impl SpatialReceiver {
+    /// Newest waiting snapshot, if any
+    pub fn latest(&mut self) -> Option<SpatialMixData> {
+        let mut latest = None;
+        while let Some(data) = self.consumer.pop() {
+            latest = Some(data);
+        }
+        latest
+    }
+}
+
+/// Log any new underruns, late or dropped triggers
+pub fn report_audio_glitches(
+    time: Res<Time<Real>>,
+    clock: Res<ExperienceClock>,
+    queue: Res<AudioTriggerQueue>,
+    log: Res<WideEventLog>,
+    mut since_check: Local<f32>,
+    mut reported: Local<AudioStatsSnapshot>,
+) {
+    *since_check += time.delta_seconds();
+    if *since_check < GLITCH_REPORT_INTERVAL {
+        return;
+    }
+    *since_check = 0.0;
+
+    let current = queue.stats().snapshot();
+    if current == *reported {
+        return;
+    }
+
+    let underruns = current.underruns - reported.underruns;
+    let late = current.late_triggers - reported.late_triggers;
+    let dropped = current.dropped_triggers - reported.dropped_triggers;
+    *reported = current;
+
+    warn!(
+        target: "lightwatch::audio",
+        "Audio glitches: {} underruns, {} late triggers, {} dropped triggers",
+        underruns,
+        late,
+        dropped
+    );
+    wide_event!("audio_glitch")
+        .with_i64("underruns", underruns as i64)
+        .with_i64("late_triggers", late as i64)
+        .with_i64("dropped_triggers", dropped as i64)
+        .with_i64("underruns_total", current.underruns as i64)
+        .emit(&log, clock.elapsed());
+}
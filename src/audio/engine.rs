@@ -1,71 +1,163 @@
-//! Main audio synthesis engine
+//! Polyphonic voice pool - leitmotif and moment notes on the audio thread
+//!
+//! Voices are allocated once; a note-on takes an idle voice, or steals the
+//! quietest releasing voice, or failing that the oldest held one. Each voice
+//! takes the timbre of the traveler it plays for, and is released by a
+//! note-off or when its gate time runs out.
 
-use bevy::prelude::*;
+use super::voice::{Timbre, Voice};
+use crate::core::TravelerId;
 
-use super::Voice;
+/// Voices in the pool
+pub const MAX_VOICES: usize = 16;
 
-/// Main audio synthesis engine
-#[derive(Resource)]
+/// A pool voice and what it is playing
+struct PooledVoice {
+    voice: Voice,
+    traveler: Option<TravelerId>,
+    frequency: f32,
+    velocity: f32,
+    /// Seconds until an automatic release
+    gate: Option<f32>,
+    /// Note-on order, for stealing the oldest
+    started: u64,
+}
+
+/// Polyphonic voice pool, owned by the audio thread
 pub struct AudioEngine {
     pub sample_rate: f32,
     pub master_volume: f32,
-    voices: Vec<Voice>,
-    max_voices: usize,
+    voices: Vec<PooledVoice>,
+    notes_started: u64,
 }
 
 impl AudioEngine {
     pub fn new(sample_rate: f32) -> Self {
+        let voices = (0..MAX_VOICES)
+            .map(|_| PooledVoice {
+                voice: Voice::new(sample_rate),
+                traveler: None,
+                frequency: 0.0,
+                velocity: 0.0,
+                gate: None,
+                started: 0,
+            })
+            .collect();
+
         Self {
             sample_rate,
-            master_volume: 0.5,
-            voices: Vec::new(),
-            max_voices: 16,
+            master_volume: 1.0,
+            voices,
+            notes_started: 0,
+        }
+    }
+
+    /// Start a note in `traveler`'s timbre (the default timbre for None); with a
+    /// gate it releases itself after that many seconds
+    pub fn note_on(
+        &mut self,
+        traveler: Option<TravelerId>,
+        frequency: f32,
+        velocity: f32,
+        gate: Option<f32>,
+    ) {
+        let index = self.free_voice();
+        let timbre = traveler.map(Timbre::for_traveler).unwrap_or_default();
+        self.notes_started += 1;
+
+        let pooled = &mut self.voices[index];
+        pooled.voice.set_timbre(&timbre);
+        pooled.voice.trigger(frequency);
+        pooled.traveler = traveler;
+        pooled.frequency = frequency;
+        pooled.velocity = velocity.clamp(0.0, 1.0) * timbre.gain;
+        pooled.gate = gate;
+        pooled.started = self.notes_started;
+    }
+
+    /// Release `traveler`'s held notes at `frequency`
+    pub fn note_off(&mut self, traveler: Option<TravelerId>, frequency: f32) {
+        for pooled in &mut self.voices {
+            if pooled.traveler == traveler
+                && !pooled.voice.is_released()
+                && (pooled.frequency - frequency).abs() < 0.1
+            {
+                pooled.voice.release();
+                pooled.gate = None;
+            }
         }
     }
 
-    /// Play a note
-    #[allow(dead_code)]
-    pub fn play_note(&mut self, frequency: f32) {
-        // Find inactive voice or steal oldest
-        let voice_idx = self
+    /// Silence every voice at once
+    pub fn clear(&mut self) {
+        for pooled in &mut self.voices {
+            pooled.voice = Voice::new(self.sample_rate);
+            pooled.traveler = None;
+            pooled.gate = None;
+        }
+    }
+
+    /// Idle voice, else the quietest releasing one, else the oldest
+    fn free_voice(&self) -> usize {
+        if let Some(index) = self.voices.iter().position(|pooled| !pooled.voice.is_active()) {
+            return index;
+        }
+
+        let quietest_released = self
             .voices
             .iter()
-            .position(|v| !v.is_active())
-            .unwrap_or_else(|| {
-                if self.voices.len() < self.max_voices {
-                    self.voices.push(Voice::new(self.sample_rate));
-                    self.voices.len() - 1
-                } else {
-                    0 // Steal first voice
-                }
-            });
-
-        if voice_idx < self.voices.len() {
-            self.voices[voice_idx].trigger(frequency);
+            .enumerate()
+            .filter(|(_, pooled)| pooled.voice.is_released())
+            .min_by(|(_, a), (_, b)| a.voice.level().total_cmp(&b.voice.level()));
+        if let Some((index, _)) = quietest_released {
+            return index;
+        }
+
+        self.voices
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, pooled)| pooled.started)
+            .map_or(0, |(index, _)| index)
+    }
+
+    /// Next sample of `traveler`'s voices (None: notes without a traveler)
+    pub fn sample_traveler(&mut self, traveler: Option<TravelerId>, delta_time: f32) -> f32 {
+        let mut sum = 0.0;
+        for pooled in &mut self.voices {
+            if pooled.traveler == traveler && pooled.voice.is_active() {
+                sum += Self::process(pooled, delta_time);
+            }
         }
+        sum * self.master_volume
     }
 
-    /// Release all notes at frequency
-    #[allow(dead_code)]
-    pub fn release_note(&mut self, frequency: f32) {
-        for voice in &mut self.voices {
-            if (voice.oscillator.frequency - frequency).abs() < 0.1 {
-                voice.release();
+    /// Next sample of every voice
+    pub fn sample(&mut self, delta_time: f32) -> f32 {
+        let mut sum = 0.0;
+        for pooled in &mut self.voices {
+            if pooled.voice.is_active() {
+                sum += Self::process(pooled, delta_time);
             }
         }
+        sum * self.master_volume
+    }
+
+    fn process(pooled: &mut PooledVoice, delta_time: f32) -> f32 {
+        if let Some(gate) = &mut pooled.gate {
+            *gate -= delta_time;
+            if *gate <= 0.0 {
+                pooled.voice.release();
+                pooled.gate = None;
+            }
+        }
+        pooled.voice.process(delta_time) * pooled.velocity
     }
 
     /// Generate audio buffer
-    #[allow(dead_code)]
     pub fn fill_buffer(&mut self, buffer: &mut [f32]) {
         let delta_time = 1.0 / self.sample_rate;
-
         for sample in buffer.iter_mut() {
-            let mut sum = 0.0;
-            for voice in &mut self.voices {
-                sum += voice.process(delta_time);
-            }
-            *sample = sum * self.master_volume;
+            *sample = self.sample(delta_time);
         }
     }
 }
@@ -20,6 +20,8 @@ pub struct Envelope {
     stage: EnvelopeStage,
     level: f32,
     time_in_stage: f32,
+    /// Level the current attack or release started from
+    stage_start_level: f32,
 }
 
 impl Envelope {
@@ -32,20 +34,24 @@ impl Envelope {
             stage: EnvelopeStage::Idle,
             level: 0.0,
             time_in_stage: 0.0,
+            stage_start_level: 0.0,
         }
     }
 
-    /// Trigger the envelope
+    /// Trigger the envelope (a retrigger attacks from the current level, so a
+    /// reused voice does not click)
     pub fn trigger(&mut self) {
         self.stage = EnvelopeStage::Attack;
         self.time_in_stage = 0.0;
+        self.stage_start_level = self.level;
     }
 
-    /// Release the envelope
+    /// Release the envelope from wherever it is
     pub fn release(&mut self) {
-        if self.stage != EnvelopeStage::Idle {
+        if self.stage != EnvelopeStage::Idle && self.stage != EnvelopeStage::Release {
             self.stage = EnvelopeStage::Release;
             self.time_in_stage = 0.0;
+            self.stage_start_level = self.level;
         }
     }
 
@@ -59,7 +65,8 @@ impl Envelope {
             }
             EnvelopeStage::Attack => {
                 if self.attack > 0.0 {
-                    self.level = (self.time_in_stage / self.attack).min(1.0);
+                    let t = (self.time_in_stage / self.attack).min(1.0);
+                    self.level = self.stage_start_level + (1.0 - self.stage_start_level) * t;
                 } else {
                     self.level = 1.0;
                 }
@@ -85,7 +92,7 @@ impl Envelope {
             EnvelopeStage::Release => {
                 if self.release > 0.0 {
                     let t = (self.time_in_stage / self.release).min(1.0);
-                    self.level = self.sustain * (1.0 - t);
+                    self.level = self.stage_start_level * (1.0 - t);
                 } else {
                     self.level = 0.0;
                 }
@@ -102,4 +109,13 @@ impl Envelope {
     pub fn is_active(&self) -> bool {
         self.stage != EnvelopeStage::Idle
     }
+
+    /// In the release stage or idle
+    pub fn is_released(&self) -> bool {
+        matches!(self.stage, EnvelopeStage::Release | EnvelopeStage::Idle)
+    }
+
+    pub fn level(&self) -> f32 {
+        self.level
+    }
 }
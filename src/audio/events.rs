@@ -5,9 +5,14 @@
 
 use bevy::prelude::*;
 
-use super::output::{AudioTrigger, AudioTriggerQueue};
+use super::channel::AudioTriggerQueue;
+use super::output::AudioTrigger;
 use super::silence::SilenceManager;
 use crate::core::{AudioAction, AudioLayerEvent, BangEvent, BangStage, PhaseChangedEvent, TravelerId, TravelerFadedEvent};
+use crate::core::{reset_resource, ExperienceReset, MomentAction, MomentActionAppExt, PlayNoteEvent};
+
+/// Layer names understood by `handle_audio_layer_events`
+pub const AUDIO_LAYERS: &[&str] = &["radiation", "all"];
 
 /// Event sound configuration (for reference/future tuning)
 #[derive(Resource)]
@@ -57,7 +62,7 @@ pub fn handle_traveler_faded(
 ) {
     for event in events.read() {
         if event.id == TravelerId::Child {
-            trigger_queue.send(AudioTrigger::GriefDissonance);
+            trigger_queue.send(AudioTrigger::GriefDissonance(event.id));
             info!(target: "lightwatch::audio", "Grief dissonance triggered for Child");
         }
     }
@@ -95,7 +100,7 @@ pub fn handle_audio_layer_events(
             }
             AudioAction::FadeOut => {
                 // Fade out over 2 seconds
-                if event.layer == "all" || event.layer == "radiation" {
+                if AUDIO_LAYERS.contains(&event.layer.as_str()) {
                     trigger_queue.send(AudioTrigger::FadeAmbiance { duration: 2.0 });
                     debug!(target: "lightwatch::audio", "Layer '{}' fading out over 2s", event.layer);
                 }
@@ -104,32 +109,88 @@ pub fn handle_audio_layer_events(
     }
 }
 
+/// Handle play note events - one synth note per event
+pub fn handle_play_note_events(
+    mut events: EventReader<PlayNoteEvent>,
+    trigger_queue: Res<AudioTriggerQueue>,
+) {
+    for event in events.read() {
+        trigger_queue.send(AudioTrigger::PlayNote {
+            frequency: event.frequency,
+            duration: event.duration,
+            volume: event.volume,
+            traveler: event.traveler,
+        });
+        debug!(target: "lightwatch::audio", "Note {:.1} Hz for {:.2}s", event.frequency, event.duration);
+    }
+}
+
+/// Moment actions owned by the audio (`StartLayer`, `StopLayer`, `PlayNote`)
+pub fn handle_audio_moment(world: &mut World, action: &MomentAction, elapsed: f32) {
+    match action {
+        MomentAction::StartLayer(layer) => {
+            world.send_event(AudioLayerEvent {
+                layer: layer.clone(),
+                action: AudioAction::Start,
+                elapsed,
+            });
+        }
+        MomentAction::StopLayer(layer) => {
+            // Use FadeOut instead of Stop for smooth crossfades
+            world.send_event(AudioLayerEvent {
+                layer: layer.clone(),
+                action: AudioAction::FadeOut,
+                elapsed,
+            });
+        }
+        MomentAction::PlayNote { freq, dur, vol } => {
+            world.send_event(PlayNoteEvent {
+                frequency: *freq,
+                duration: *dur,
+                volume: *vol,
+                traveler: None,
+            });
+        }
+        _ => {}
+    }
+}
+
 /// Update silence manager
 pub fn update_silence(time: Res<Time>, mut silence: ResMut<SilenceManager>) {
     let dt = time.delta_seconds();
     silence.update(dt);
 }
 
+/// Seconds before the end of the cut at which the ambiance fades out
+pub const AMBIANCE_FADE_LEAD: f32 = 4.0;
+
 /// Track if ambiance fade has been triggered
 #[derive(Resource, Default)]
 pub struct AmbianceFadeState {
     pub triggered: bool,
 }
 
-/// Fade ambiance at experience end (139s+)
+/// Fade ambiance at experience end (139s+ in the standard cut)
 pub fn fade_ambiance_at_end(
     clock: Res<crate::core::ExperienceClock>,
     trigger_queue: Res<AudioTriggerQueue>,
     mut fade_state: ResMut<AmbianceFadeState>,
 ) {
-    // Fade starts at 139s (Ended phase), takes 4 seconds
-    if !fade_state.triggered && clock.elapsed() >= 139.0 {
-        trigger_queue.send(AudioTrigger::FadeAmbiance { duration: 4.0 });
+    // Fade starts 4 seconds before the end and lasts until it
+    if !fade_state.triggered && clock.elapsed() >= clock.duration() - AMBIANCE_FADE_LEAD {
+        trigger_queue.send(AudioTrigger::FadeAmbiance {
+            duration: AMBIANCE_FADE_LEAD,
+        });
         fade_state.triggered = true;
         info!(target: "lightwatch::audio", "Ambiance fade started");
     }
 }
 
+/// Silence one-shot sounds and bring the ambiance back (on `ExperienceReset`)
+pub fn restart_audio(trigger_queue: Res<AudioTriggerQueue>) {
+    trigger_queue.send(AudioTrigger::Resync { ambiance: true });
+}
+
 /// Event sound plugin
 pub struct EventSoundPlugin;
 
@@ -138,6 +199,15 @@ impl Plugin for EventSoundPlugin {
         app.init_resource::<EventSoundConfig>()
             .init_resource::<SilenceManager>()
             .init_resource::<AmbianceFadeState>()
+            .add_systems(
+                ExperienceReset,
+                (
+                    reset_resource::<SilenceManager>,
+                    reset_resource::<AmbianceFadeState>,
+                    restart_audio,
+                ),
+            )
+            .add_moment_actions(&["StartLayer", "StopLayer", "PlayNote"], handle_audio_moment)
             .add_systems(
                 Update,
                 (
@@ -145,6 +215,7 @@ impl Plugin for EventSoundPlugin {
                     handle_traveler_faded,
                     handle_phase_transitions,
                     handle_audio_layer_events,
+                    handle_play_note_events,
                     update_silence,
                     fade_ambiance_at_end,
                 ),
@@ -1,22 +1,57 @@
 //! Leitmotif playback system
+//!
+//! `PlayLeitmotif` moments start a phrase for the named traveler, or for every
+//! traveler still present. The player steps through each phrase on the clock
+//! and sends note-on and note-off triggers to the voice pool on the audio
+//! thread; a traveler's phrase stops when it fades out.
 
 use bevy::prelude::*;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use super::leitmotif::Leitmotif;
 use super::melody::{Melody, MelodyGenerator};
-use super::AudioEngine;
-use crate::core::TravelerId;
+use super::channel::AudioTriggerQueue;
+use super::output::AudioTrigger;
+use crate::core::{
+    reset_resource, ExperienceClock, ExperienceReset, MomentAction, MomentActionAppExt,
+    PlayLeitmotifEvent, TravelerFadingEvent, TravelerId,
+};
+use crate::travelers::{Traveler, TravelerState};
+
+/// Fraction of each note's length it is held before the note-off
+const LEGATO: f32 = 0.85;
+
+/// Leitmotif note velocity
+const NOTE_VELOCITY: f32 = 0.6;
+
+/// Note change for the voice pool
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LeitmotifNote {
+    On { traveler: TravelerId, frequency: f32 },
+    Off { traveler: TravelerId, frequency: f32 },
+}
+
+/// One traveler's phrase in progress
+struct MotifPlayback {
+    melody: Melody,
+    /// Next note to play
+    index: usize,
+    /// Time until the next note
+    time_until_next: f32,
+    /// Seconds per beat
+    beat: f32,
+    /// Sounding note and the time until its note-off
+    held: Option<(f32, f32)>,
+}
 
 /// Leitmotif playback state
 #[derive(Resource)]
 pub struct LeitmotifPlayer {
-    /// Current melodies per traveler
-    melodies: HashMap<TravelerId, Melody>,
-    /// Current note index per traveler
-    note_indices: HashMap<TravelerId, usize>,
-    /// Time until next note per traveler
-    time_until_next: HashMap<TravelerId, f32>,
+    /// Phrases in progress per traveler, in traveler order so notes that
+    /// fall on the same step always reach the voice pool in the same order
+    playing: BTreeMap<TravelerId, MotifPlayback>,
+    /// Note-offs still owed to stopped phrases
+    pending_offs: Vec<LeitmotifNote>,
     /// Generator
     generator: MelodyGenerator,
 }
@@ -24,17 +59,15 @@ pub struct LeitmotifPlayer {
 impl Default for LeitmotifPlayer {
     fn default() -> Self {
         Self {
-            melodies: HashMap::new(),
-            note_indices: HashMap::new(),
-            time_until_next: HashMap::new(),
+            playing: BTreeMap::new(),
+            pending_offs: Vec::new(),
             generator: MelodyGenerator::new(42),
         }
     }
 }
 
 impl LeitmotifPlayer {
-    /// Start playing leitmotif for traveler
-    #[allow(dead_code)]
+    /// Start playing leitmotif for traveler (restarts a phrase in progress)
     pub fn start(&mut self, traveler: TravelerId) {
         let leitmotif = match traveler {
             TravelerId::Archivist => Leitmotif::archivist(),
@@ -44,80 +77,180 @@ impl LeitmotifPlayer {
             TravelerId::Other => Leitmotif::other(),
         };
 
+        self.stop(traveler);
         let melody = self.generator.generate(&leitmotif);
-        self.melodies.insert(traveler, melody);
-        self.note_indices.insert(traveler, 0);
-        self.time_until_next.insert(traveler, 0.0);
+        self.playing.insert(
+            traveler,
+            MotifPlayback {
+                melody,
+                index: 0,
+                time_until_next: 0.0,
+                beat: 60.0 / leitmotif.tempo,
+                held: None,
+            },
+        );
 
         info!(target: "lightwatch::audio", "Leitmotif started for {:?}", traveler);
     }
 
-    /// Stop playing leitmotif for traveler
-    #[allow(dead_code)]
+    /// Stop playing leitmotif for traveler, releasing its sounding note
     pub fn stop(&mut self, traveler: TravelerId) {
-        self.melodies.remove(&traveler);
-        self.note_indices.remove(&traveler);
-        self.time_until_next.remove(&traveler);
+        if let Some(playback) = self.playing.remove(&traveler) {
+            if let Some((frequency, _)) = playback.held {
+                self.pending_offs.push(LeitmotifNote::Off {
+                    traveler,
+                    frequency,
+                });
+            }
+        }
     }
 
-    /// Update and get notes to play
-    pub fn update(&mut self, delta: f32) -> Vec<(TravelerId, f32)> {
-        let mut notes_to_play = Vec::new();
-
-        for (traveler, melody) in &self.melodies {
-            let time = self.time_until_next.get_mut(traveler).unwrap();
-            *time -= delta;
-
-            if *time <= 0.0 {
-                let idx = self.note_indices.get_mut(traveler).unwrap();
-
-                if *idx < melody.notes.len() {
-                    if !melody.is_rest[*idx] {
-                        notes_to_play.push((*traveler, melody.notes[*idx]));
-                    }
-
-                    // Get tempo for this traveler
-                    let tempo = match traveler {
-                        TravelerId::Archivist => 60.0,
-                        TravelerId::Wanderer => 75.0,
-                        TravelerId::Keeper => 55.0,
-                        TravelerId::Child => 90.0,
-                        TravelerId::Other => 45.0,
-                    };
-
-                    let beat_duration = 60.0 / tempo;
-                    *time = melody.durations[*idx] * beat_duration;
-                    *idx += 1;
+    pub fn is_playing(&self, traveler: TravelerId) -> bool {
+        self.playing.contains_key(&traveler)
+    }
+
+    /// Advance by `delta` seconds and get the note changes, in order
+    pub fn update(&mut self, delta: f32) -> Vec<LeitmotifNote> {
+        let mut notes = std::mem::take(&mut self.pending_offs);
+
+        for (&traveler, playback) in &mut self.playing {
+            if let Some((frequency, gate)) = &mut playback.held {
+                *gate -= delta;
+                if *gate <= 0.0 {
+                    notes.push(LeitmotifNote::Off {
+                        traveler,
+                        frequency: *frequency,
+                    });
+                    playback.held = None;
                 }
             }
+
+            playback.time_until_next -= delta;
+            if playback.time_until_next > 0.0 || playback.index >= playback.melody.notes.len() {
+                continue;
+            }
+
+            let index = playback.index;
+            let length = playback.melody.durations[index] * playback.beat;
+            if let Some((frequency, _)) = playback.held.take() {
+                notes.push(LeitmotifNote::Off {
+                    traveler,
+                    frequency,
+                });
+            }
+            if !playback.melody.is_rest[index] {
+                let frequency = playback.melody.notes[index];
+                notes.push(LeitmotifNote::On {
+                    traveler,
+                    frequency,
+                });
+                playback.held = Some((frequency, length * LEGATO));
+            }
+
+            playback.time_until_next += length;
+            playback.index += 1;
         }
 
-        notes_to_play
+        // Finished phrases go once their last note is released
+        self.playing.retain(|_, playback| {
+            playback.index < playback.melody.notes.len() || playback.held.is_some()
+        });
+
+        notes
     }
+}
+
+/// `PlayLeitmotif` moments
+pub fn handle_leitmotif_moment(world: &mut World, action: &MomentAction, elapsed: f32) {
+    if let MomentAction::PlayLeitmotif(motif) = action {
+        let traveler = TravelerId::all()
+            .iter()
+            .copied()
+            .find(|id| id.name() == motif.as_str());
+        world.send_event(PlayLeitmotifEvent {
+            motif: motif.clone(),
+            traveler,
+            elapsed,
+        });
+    }
+}
 
-    /// Check if traveler's melody is complete
-    #[allow(dead_code)]
-    pub fn is_complete(&self, traveler: TravelerId) -> bool {
-        if let (Some(melody), Some(idx)) =
-            (self.melodies.get(&traveler), self.note_indices.get(&traveler))
-        {
-            *idx >= melody.notes.len()
-        } else {
-            true
+/// Start the requested phrases: the named traveler's, or, for a motif that
+/// names no traveler, those of every traveler that has not begun to fade
+pub fn handle_leitmotif_events(
+    mut events: EventReader<PlayLeitmotifEvent>,
+    travelers: Query<(&Traveler, &TravelerState)>,
+    mut player: ResMut<LeitmotifPlayer>,
+) {
+    for event in events.read() {
+        match event.traveler {
+            Some(id) => player.start(id),
+            None => {
+                let mut present: Vec<TravelerId> = travelers
+                    .iter()
+                    .filter(|(_, state)| {
+                        !matches!(state, TravelerState::Fading | TravelerState::Gone)
+                    })
+                    .map(|(traveler, _)| traveler.id)
+                    .collect();
+                present.sort_by_key(|id| *id as usize);
+                debug!(
+                    target: "lightwatch::audio",
+                    "Leitmotif '{}' for {} travelers",
+                    event.motif,
+                    present.len()
+                );
+                for id in present {
+                    player.start(id);
+                }
+            }
         }
     }
 }
 
+/// A fading traveler's phrase stops
+pub fn stop_fading_leitmotifs(
+    mut events: EventReader<TravelerFadingEvent>,
+    mut player: ResMut<LeitmotifPlayer>,
+) {
+    for event in events.read() {
+        player.stop(event.id);
+    }
+}
+
 /// Update leitmotif playback
 pub fn update_leitmotifs(
     time: Res<Time>,
+    clock: Res<ExperienceClock>,
     mut player: ResMut<LeitmotifPlayer>,
-    mut engine: ResMut<AudioEngine>,
+    trigger_queue: Res<AudioTriggerQueue>,
 ) {
-    let notes = player.update(time.delta_seconds());
+    // Phrases hold their place while the clock is paused
+    let delta = if clock.is_running() {
+        time.delta_seconds()
+    } else {
+        0.0
+    };
 
-    for (_traveler, frequency) in notes {
-        engine.play_note(frequency);
+    for note in player.update(delta) {
+        let trigger = match note {
+            LeitmotifNote::On {
+                traveler,
+                frequency,
+            } => AudioTrigger::NoteOn {
+                traveler,
+                frequency,
+                velocity: NOTE_VELOCITY,
+            },
+            LeitmotifNote::Off {
+                traveler,
+                frequency,
+            } => AudioTrigger::NoteOff {
+                traveler,
+                frequency,
+            },
+        };
+        trigger_queue.send(trigger);
     }
 }
 
@@ -127,6 +260,59 @@ pub struct LeitmotifPlugin;
 impl Plugin for LeitmotifPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<LeitmotifPlayer>()
-            .add_systems(Update, update_leitmotifs);
+            .add_moment_actions(&["PlayLeitmotif"], handle_leitmotif_moment)
+            .add_systems(
+                Update,
+                (
+                    handle_leitmotif_events,
+                    stop_fading_leitmotifs,
+                    update_leitmotifs,
+                )
+                    .chain(),
+            )
+            .add_systems(ExperienceReset, reset_resource::<LeitmotifPlayer>);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every note change from starting all travelers and playing their phrases out
+    fn play_all(delta: f32) -> Vec<LeitmotifNote> {
+        let mut player = LeitmotifPlayer::default();
+        for id in TravelerId::all() {
+            player.start(*id);
+        }
+
+        let mut notes = Vec::new();
+        for _ in 0..(60.0 / delta) as usize {
+            notes.extend(player.update(delta));
+        }
+        assert!(TravelerId::all().iter().all(|id| !player.is_playing(*id)));
+        notes
+    }
+
+    #[test]
+    fn simultaneous_notes_come_in_traveler_order() {
+        let delta = 1.0 / 60.0;
+        let first = play_all(delta);
+        assert_eq!(first, play_all(delta));
+
+        // Every phrase starts on the first step
+        let mut player = LeitmotifPlayer::default();
+        for id in TravelerId::all().iter().rev() {
+            player.start(*id);
+        }
+        let started: Vec<TravelerId> = player
+            .update(delta)
+            .into_iter()
+            .filter_map(|note| match note {
+                LeitmotifNote::On { traveler, .. } => Some(traveler),
+                LeitmotifNote::Off { .. } => None,
+            })
+            .collect();
+        assert!(!started.is_empty());
+        assert!(started.windows(2).all(|pair| pair[0] < pair[1]));
     }
 }
@@ -1,7 +1,10 @@
 //! Audio systems: Synthesis, Leitmotifs, Spatial audio, Events
 
 mod ambiance;
+mod backend;
 mod bang_sound;
+mod bus;
+mod channel;
 mod engine;
 mod envelope;
 mod events;
@@ -12,42 +15,46 @@ mod leitmotif_player;
 mod melody;
 mod oscillator;
 mod output;
+mod render;
 mod reverb;
 mod scale;
 mod silence;
 mod spatial;
 mod transitions;
 mod voice;
+mod wav;
 
 pub use ambiance::CosmicAmbiance;
-pub use engine::AudioEngine;
+pub use backend::{output_device_names, NULL_AUDIO_DEVICE};
+pub use engine::{AudioEngine, MAX_VOICES};
 pub use envelope::Envelope;
-pub use events::EventSoundPlugin;
+pub use events::{AmbianceFadeState, EventSoundPlugin, AMBIANCE_FADE_LEAD, AUDIO_LAYERS};
 pub use filter::{BiquadFilter, FilterType};
-pub use leitmotif_player::LeitmotifPlugin;
+pub use leitmotif_player::{LeitmotifNote, LeitmotifPlayer, LeitmotifPlugin};
 pub use oscillator::{Oscillator, Waveform};
-pub use output::AudioOutputPlugin;
+pub use channel::{
+    AudioStats, AudioStatsSnapshot, AudioTriggerQueue, SharedSpatialData, LATE_TRIGGER,
+    TRIGGER_CAPACITY,
+};
+pub use output::{
+    AudioOutput, AudioOutputConfig, AudioOutputPlugin, AudioTrigger, SpatialMixData,
+    SpatialSourceData,
+};
+pub use render::{
+    run_audio_render, stem_path, AudioRenderConfig, AudioRenderReport, DEFAULT_RENDER_SAMPLE_RATE,
+};
 pub use spatial::{SpatialAudioPlugin, SpatialAudioSource};
-pub use voice::Voice;
+pub use voice::{Timbre, Voice};
+pub use wav::WavWriter;
 
 use bevy::prelude::*;
 
-/// Audio synthesis plugin
-pub struct AudioSynthesisPlugin;
-
-impl Plugin for AudioSynthesisPlugin {
-    fn build(&self, app: &mut App) {
-        app.init_resource::<AudioEngine>();
-    }
-}
-
 /// Audio plugin for synthesis and spatial sound
 pub struct AudioPlugin;
 
 impl Plugin for AudioPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(AudioSynthesisPlugin)
-            .add_plugins(AudioOutputPlugin)
+        app.add_plugins(AudioOutputPlugin)
             .add_plugins(LeitmotifPlugin)
             .add_plugins(SpatialAudioPlugin)
             .add_plugins(EventSoundPlugin)
@@ -1,145 +1,264 @@
 //! Audio output system - connects synthesis to speakers via cpal
 
-use std::sync::{Arc, Mutex};
+use std::f32::consts::FRAC_1_SQRT_2;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use bevy::prelude::*;
-use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::traits::{DeviceTrait, StreamTrait};
 
+use super::backend::{find_output_device, SinkThread, NULL_AUDIO_DEVICE};
 use super::bang_sound::BangRumble;
+use super::bus::{Smoothed, TravelerBus};
+use super::channel::{
+    report_audio_glitches, AudioTriggerQueue, SharedSpatialData, SpatialReceiver, TriggerReceiver,
+};
+use super::engine::AudioEngine;
 use super::grief_sound::GriefDissonance;
 use super::reverb::Reverb;
 use super::spatial::SpatialAudioSource;
 use super::transitions::TransitionSound;
 use super::{BiquadFilter, FilterType, Oscillator, Waveform};
-use crate::core::Phase;
+use crate::core::{
+    ExperienceClock, LoadingTracker, Phase, TravelerId, UserSettings, WideEventLog,
+};
+use crate::wide_event;
+
+/// Loading task for opening the output device
+const AUDIO_DEVICE_TASK: &str = "audio device";
+
+/// Seconds between attempts to reopen a lost device
+const DEVICE_RETRY_INTERVAL: f32 = 2.0;
+
+/// Master volume ramp time (seconds)
+const VOLUME_SMOOTHING: f32 = 0.05;
 
 /// Trigger commands sent from Bevy to audio thread
 #[derive(Clone)]
 pub enum AudioTrigger {
     BangRumble,
-    GriefDissonance,
+    /// Grief cluster, heard from where this traveler is
+    GriefDissonance(TravelerId),
     PhaseTransition(Phase),
     /// Start fading ambiance to silence
     FadeAmbiance { duration: f32 },
+    /// Silence one-shot sounds and set ambiance on or off (after a seek)
+    Resync { ambiance: bool },
+    /// Set the master volume (0.0 to 1.0)
+    SetVolume(f32),
+    /// Play a single synth note (`PlayNote` moments)
+    PlayNote {
+        frequency: f32,
+        duration: f32,
+        volume: f32,
+        traveler: Option<TravelerId>,
+    },
+    /// Hold a note in a traveler's timbre until the matching `NoteOff`
+    NoteOn {
+        traveler: TravelerId,
+        frequency: f32,
+        velocity: f32,
+    },
+    /// Release a traveler's note started by `NoteOn`
+    NoteOff { traveler: TravelerId, frequency: f32 },
 }
 
 /// Spatial data for a single audio source
-#[derive(Clone, Default)]
-#[allow(dead_code)]
+#[derive(Clone, Copy, Debug)]
 pub struct SpatialSourceData {
     pub gain: f32,
     pub pan: f32, // -1 left, +1 right
     pub pitch: f32,
+    /// Distance low-pass cutoff (Hz)
+    pub cutoff: f32,
 }
 
-/// Shared spatial mix data between Bevy and audio thread
-#[derive(Default)]
+impl Default for SpatialSourceData {
+    /// Centred, full level and unfiltered (a traveler not yet placed)
+    fn default() -> Self {
+        Self {
+            gain: 1.0,
+            pan: 0.0,
+            pitch: 1.0,
+            cutoff: 18000.0,
+        }
+    }
+}
+
+/// Spatial mix data sent from Bevy to the audio thread
+#[derive(Clone, Copy, Debug, Default)]
 pub struct SpatialMixData {
     /// Per-traveler spatial data
     pub travelers: [SpatialSourceData; 5], // One per TravelerId
-    /// Master spatial influence (weighted average of active travelers)
-    pub master_pan: f32,
-    pub master_gain: f32,
 }
 
-/// Resource for sharing spatial data with audio thread
-#[derive(Resource)]
-pub struct SharedSpatialData {
-    pub data: Arc<Mutex<SpatialMixData>>,
+/// Each bus of one stereo frame, dry and before the master soft clip
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct StemFrame {
+    pub(super) ambiance: (f32, f32),
+    /// Bang, transitions and notes without a traveler
+    pub(super) events: (f32, f32),
+    /// Traveler buses, by `TravelerId as usize`
+    pub(super) travelers: [(f32, f32); 5],
 }
 
-/// Audio state owned by the audio thread
-struct AudioState {
+/// Audio state owned by the audio thread (or the offline renderer)
+pub(super) struct AudioState {
     bang_rumble: BangRumble,
     grief: GriefDissonance,
+    /// Traveler whose bus carries the grief cluster
+    grief_traveler: TravelerId,
     transitions: TransitionSound,
+    /// Leitmotif and moment notes
+    voices: AudioEngine,
+    /// One bus per traveler, by `TravelerId as usize`
+    buses: [TravelerBus; 5],
     ambiance: AmbianceGenerator,
     reverb: Reverb,
-    master_volume: f32,
+    /// Ramps to `SetVolume` changes
+    master_volume: Smoothed,
     sample_rate: f32,
-    /// Cached spatial data
-    cached_pan: f32,
-    cached_gain: f32,
 }
 
 impl AudioState {
-    fn new(sample_rate: f32) -> Self {
+    pub(super) fn new(sample_rate: f32, master_volume: f32) -> Self {
         let mut reverb = Reverb::new(sample_rate);
         reverb.mix = 0.25; // 25% wet for cosmic space feel
 
         Self {
             bang_rumble: BangRumble::new(sample_rate),
             grief: GriefDissonance::new(),
+            grief_traveler: TravelerId::Child,
             transitions: TransitionSound::new(),
+            voices: AudioEngine::new(sample_rate),
+            buses: std::array::from_fn(|_| TravelerBus::new(sample_rate)),
             ambiance: AmbianceGenerator::new(sample_rate),
             reverb,
-            master_volume: 0.7,
+            master_volume: Smoothed::new(master_volume, VOLUME_SMOOTHING, sample_rate),
             sample_rate,
-            cached_pan: 0.0,
-            cached_gain: 1.0,
         }
     }
 
-    fn update_spatial(&mut self, spatial_data: &SpatialMixData) {
-        self.cached_pan = spatial_data.master_pan;
-        self.cached_gain = spatial_data.master_gain.max(0.3); // Minimum gain
+    pub(super) fn update_spatial(&mut self, spatial_data: &SpatialMixData) {
+        for (bus, data) in self.buses.iter_mut().zip(&spatial_data.travelers) {
+            bus.set_target(data);
+        }
     }
 
-    fn process_triggers(&mut self, triggers: &mut Vec<AudioTrigger>) {
-        for trigger in triggers.drain(..) {
-            match trigger {
-                AudioTrigger::BangRumble => {
-                    self.bang_rumble.trigger();
-                }
-                AudioTrigger::GriefDissonance => {
-                    self.grief.trigger();
-                }
-                AudioTrigger::PhaseTransition(phase) => {
-                    self.transitions.trigger_for_phase(phase);
-                }
-                AudioTrigger::FadeAmbiance { duration } => {
-                    self.ambiance.start_fade(duration);
-                }
+    pub(super) fn process_trigger(&mut self, trigger: AudioTrigger) {
+        match trigger {
+            AudioTrigger::BangRumble => {
+                self.bang_rumble.trigger();
+            }
+            AudioTrigger::GriefDissonance(traveler) => {
+                self.grief_traveler = traveler;
+                self.grief.trigger();
+            }
+            AudioTrigger::PhaseTransition(phase) => {
+                self.transitions.trigger_for_phase(phase);
+            }
+            AudioTrigger::FadeAmbiance { duration } => {
+                self.ambiance.start_fade(duration);
+            }
+            AudioTrigger::Resync { ambiance } => {
+                self.bang_rumble = BangRumble::new(self.sample_rate);
+                self.grief = GriefDissonance::new();
+                self.transitions = TransitionSound::new();
+                self.voices.clear();
+                self.ambiance.resync(ambiance);
+            }
+            AudioTrigger::SetVolume(volume) => {
+                self.master_volume.target = volume.clamp(0.0, 1.0);
+            }
+            AudioTrigger::PlayNote {
+                frequency,
+                duration,
+                volume,
+                traveler,
+            } => {
+                self.voices.note_on(traveler, frequency, volume, Some(duration));
+            }
+            AudioTrigger::NoteOn {
+                traveler,
+                frequency,
+                velocity,
+            } => {
+                self.voices.note_on(Some(traveler), frequency, velocity, None);
+            }
+            AudioTrigger::NoteOff {
+                traveler,
+                frequency,
+            } => {
+                self.voices.note_off(Some(traveler), frequency);
             }
         }
     }
 
-    fn generate_sample(&mut self) -> f32 {
-        let dt = 1.0 / self.sample_rate;
-
-        let mut sample = 0.0;
+    /// Generate a stereo frame: traveler buses placed by their spatial data,
+    /// everything without a position (ambiance, bang, transitions) centred
+    fn generate_stereo_sample(&mut self) -> (f32, f32) {
+        self.generate_frame(None)
+    }
 
-        // Event sounds
-        sample += self.bang_rumble.sample(self.sample_rate, dt);
-        sample += self.grief.sample(self.sample_rate, dt);
-        sample += self.transitions.sample(self.sample_rate, dt);
+    /// Generate a stereo frame, also filling `stems` with each bus at the
+    /// master volume
+    pub(super) fn generate_frame(&mut self, mut stems: Option<&mut StemFrame>) -> (f32, f32) {
+        let dt = 1.0 / self.sample_rate;
+        let sample_rate = self.sample_rate;
+
+        // Centred sounds, at the level a centred constant-power pan gives
+        let mut events = 0.0;
+        events += self.bang_rumble.sample(sample_rate, dt);
+        events += self.transitions.sample(sample_rate, dt);
+        events += self.voices.sample_traveler(None, dt);
+        let ambiance = self.ambiance.sample(sample_rate);
+        let mut left = (events + ambiance) * FRAC_1_SQRT_2;
+        let mut right = left;
+        if let Some(stems) = stems.as_deref_mut() {
+            stems.events = (events * FRAC_1_SQRT_2, events * FRAC_1_SQRT_2);
+            stems.ambiance = (ambiance * FRAC_1_SQRT_2, ambiance * FRAC_1_SQRT_2);
+        }
 
-        // Ambiance
-        sample += self.ambiance.sample(self.sample_rate);
+        // Traveler buses
+        for (index, bus) in self.buses.iter_mut().enumerate() {
+            let traveler = TravelerId::all()[index];
+            let voices = &mut self.voices;
+            let grief = &mut self.grief;
+            let grieving = self.grief_traveler == traveler;
+            let (bus_left, bus_right) = bus.process(|| {
+                let mut sample = voices.sample_traveler(Some(traveler), dt);
+                if grieving {
+                    sample += grief.sample(sample_rate, dt);
+                }
+                sample
+            });
+            left += bus_left;
+            right += bus_right;
+            if let Some(stems) = stems.as_deref_mut() {
+                stems.travelers[index] = (bus_left, bus_right);
+            }
+        }
 
-        // Apply reverb for cosmic space feel
-        sample = self.reverb.process(sample);
+        // Shared reverb for cosmic space feel, fed from the whole mix
+        let wet = self.reverb.wet((left + right) * FRAC_1_SQRT_2);
+        let dry = 1.0 - self.reverb.mix;
+        left = left * dry + wet * self.reverb.mix;
+        right = right * dry + wet * self.reverb.mix;
 
         // Master volume and soft clip
-        sample *= self.master_volume;
-        soft_clip(sample)
-    }
-
-    /// Generate stereo sample with spatial panning
-    fn generate_stereo_sample(&mut self) -> (f32, f32) {
-        let mono = self.generate_sample();
-
-        // Apply spatial panning
-        // pan: -1.0 = full left, +1.0 = full right
-        // Using constant-power panning for natural sound
-        let pan = self.cached_pan.clamp(-1.0, 1.0);
-        let angle = (pan + 1.0) * std::f32::consts::FRAC_PI_4; // 0 to PI/2
-        let left = mono * angle.cos();
-        let right = mono * angle.sin();
-
-        // Apply spatial gain
-        let gain = self.cached_gain;
-        (left * gain, right * gain)
+        let volume = self.master_volume.next();
+        if let Some(stems) = stems {
+            for (left, right) in [&mut stems.ambiance, &mut stems.events]
+                .into_iter()
+                .chain(&mut stems.travelers)
+            {
+                *left *= volume;
+                *right *= volume;
+            }
+        }
+        (soft_clip(left * volume), soft_clip(right * volume))
     }
 }
 
@@ -182,6 +301,13 @@ impl AmbianceGenerator {
         }
     }
 
+    fn resync(&mut self, playing: bool) {
+        self.volume = if playing { 0.15 } else { 0.0 };
+        self.target_volume = self.volume;
+        self.fade_speed = 0.0;
+        self.active = playing;
+    }
+
     fn sample(&mut self, sample_rate: f32) -> f32 {
         if !self.active {
             return 0.0;
@@ -221,16 +347,27 @@ fn soft_clip(x: f32) -> f32 {
     }
 }
 
-/// Shared trigger queue between Bevy and audio thread
-#[derive(Resource, Default)]
-pub struct AudioTriggerQueue {
-    triggers: Arc<Mutex<Vec<AudioTrigger>>>,
+/// Where the mix is going
+enum AudioBackend {
+    /// An output device stream
+    Device {
+        #[allow(dead_code)]
+        stream: cpal::Stream,
+        name: String,
+    },
+    /// No device: drained in real time, discarded or written to a file
+    Sink(SinkThread),
+    /// Nothing is reading the rings; triggers are discarded
+    Disconnected,
 }
 
-impl AudioTriggerQueue {
-    pub fn send(&self, trigger: AudioTrigger) {
-        if let Ok(mut queue) = self.triggers.lock() {
-            queue.push(trigger);
+impl AudioBackend {
+    fn name(&self) -> &str {
+        match self {
+            AudioBackend::Device { name, .. } => name,
+            AudioBackend::Sink(SinkThread { path: Some(_), .. }) => "file",
+            AudioBackend::Sink(_) => NULL_AUDIO_DEVICE,
+            AudioBackend::Disconnected => "none",
         }
     }
 }
@@ -238,30 +375,237 @@ impl AudioTriggerQueue {
 /// NonSend resource holding the audio stream (keeps it alive)
 /// cpal::Stream is not Send+Sync on macOS, so we use NonSend
 pub struct AudioOutput {
-    #[allow(dead_code)]
-    stream: cpal::Stream,
+    backend: AudioBackend,
+    /// Set by the stream's error callback
+    failed: Arc<AtomicBool>,
+    /// After a device was lost: real time of the next attempt to reopen one
+    retry_at: Option<f32>,
+}
+
+impl AudioOutput {
+    /// Name of the device in use, `null`, `file` or `none`
+    pub fn backend_name(&self) -> &str {
+        self.backend.name()
+    }
+
+    /// Is the mix reaching an output device?
+    pub fn has_device(&self) -> bool {
+        matches!(self.backend, AudioBackend::Device { .. })
+    }
+}
+
+/// Audio output settings; insert before `LightwatchPlugin` to override
+#[derive(Resource, Debug, Clone)]
+pub struct AudioOutputConfig {
+    /// Don't open an output device at all
+    pub muted: bool,
+    /// Final gain applied on the audio thread
+    pub master_volume: f32,
+    /// Output device by name (the system default when None, no device for `null`)
+    pub device: Option<String>,
+    /// Without a device, at startup or after one is lost, write the mix here
+    /// instead of discarding it
+    pub sink_file: Option<PathBuf>,
+}
+
+impl Default for AudioOutputConfig {
+    fn default() -> Self {
+        Self {
+            muted: false,
+            master_volume: 0.7,
+            device: None,
+            sink_file: None,
+        }
+    }
 }
 
 /// Initialize audio output (exclusive system for NonSend resource)
 pub fn init_audio_output(world: &mut World) {
-    let trigger_queue = world.resource::<AudioTriggerQueue>();
-    let triggers = trigger_queue.triggers.clone();
+    if world.contains_resource::<crate::core::HeadlessMode>() {
+        info!(target: "lightwatch::audio", "Headless mode, skipping audio output");
+        return;
+    }
 
-    let spatial_data = world.resource::<SharedSpatialData>();
-    let spatial = spatial_data.data.clone();
+    let output_config = world.resource::<AudioOutputConfig>().clone();
+    if output_config.muted {
+        info!(target: "lightwatch::audio", "Muted, skipping audio output");
+        return;
+    }
 
-    let host = cpal::default_host();
+    // Without a device the run goes ahead silently; the ready screen says so
+    if let Some(mut loading) = world.get_resource_mut::<LoadingTracker>() {
+        loading.add_optional_task(AUDIO_DEVICE_TASK);
+    }
 
-    let Some(device) = host.default_output_device() else {
-        warn!(target: "lightwatch::audio", "No audio output device available");
-        return;
+    let sink_file = output_config.sink_file.clone();
+    let (output, device_error) = open_output(world, &output_config, sink_file);
+    match &output.backend {
+        AudioBackend::Device { name, .. } => {
+            info!(target: "lightwatch::audio", "Audio output initialized on {}", name);
+        }
+        AudioBackend::Sink(sink) => match &sink.path {
+            Some(path) => info!(
+                target: "lightwatch::audio",
+                "No audio device, writing the mix to {}",
+                path.display()
+            ),
+            None => info!(target: "lightwatch::audio", "No audio device, using the null sink"),
+        },
+        AudioBackend::Disconnected => {}
+    }
+
+    if let Some(mut loading) = world.get_resource_mut::<LoadingTracker>() {
+        match device_error {
+            Some(err) => loading.fail(AUDIO_DEVICE_TASK, err),
+            None => loading.finish(AUDIO_DEVICE_TASK),
+        }
+    }
+    world.insert_non_send_resource(output);
+}
+
+/// Open the configured device, falling back to a sink on `sink_file` (or the
+/// null sink); each attempt connects fresh rings. Also returns why no device
+/// was opened, unless the null device was asked for.
+fn open_output(
+    world: &World,
+    config: &AudioOutputConfig,
+    sink_file: Option<PathBuf>,
+) -> (AudioOutput, Option<String>) {
+    let failed = Arc::new(AtomicBool::new(false));
+    let queue = world.resource::<AudioTriggerQueue>();
+    let shared = world.resource::<SharedSpatialData>();
+    let output = |backend| AudioOutput {
+        backend,
+        failed: failed.clone(),
+        retry_at: None,
     };
 
-    let Ok(config) = device.default_output_config() else {
-        warn!(target: "lightwatch::audio", "No default audio config available");
+    let mut device_error = None;
+    if config.device.as_deref() != Some(NULL_AUDIO_DEVICE) {
+        let device = find_output_device(config.device.as_deref()).or_else(|err| {
+            // A named device that is missing gives way to the default one
+            if config.device.is_none() {
+                return Err(err);
+            }
+            warn!(target: "lightwatch::audio", "{}, trying the default device", err);
+            find_output_device(None)
+        });
+        let stream = device.and_then(|device| {
+            let name = device.name().unwrap_or_default();
+            let (triggers, spatial) = (queue.connect(), shared.connect());
+            start_output_stream(&device, triggers, spatial, config.master_volume, failed.clone())
+                .map(|stream| AudioBackend::Device { stream, name })
+        });
+        match stream {
+            Ok(backend) => return (output(backend), None),
+            Err(err) => {
+                warn!(target: "lightwatch::audio", "No audio output: {}", err);
+                device_error = Some(err);
+            }
+        }
+    }
+
+    let sink = SinkThread::spawn(
+        queue.connect(),
+        shared.connect(),
+        config.master_volume,
+        sink_file.clone(),
+    )
+    .or_else(|err| {
+        warn!(target: "lightwatch::audio", "Cannot write audio to a file: {}", err);
+        SinkThread::spawn(queue.connect(), shared.connect(), config.master_volume, None)
+    });
+    let backend = match sink {
+        Ok(sink) => AudioBackend::Sink(sink),
+        Err(err) => {
+            warn!(target: "lightwatch::audio", "Cannot start the audio sink: {}", err);
+            queue.disconnect();
+            shared.disconnect();
+            AudioBackend::Disconnected
+        }
+    };
+    (output(backend), device_error)
+}
+
+/// Rebuild a failed stream, and keep trying to get a device back after one
+/// was lost; the new stream picks up at the clock's current position
+pub fn recover_audio_output(world: &mut World) {
+    let now = world.resource::<Time<Real>>().elapsed_seconds();
+    let Some(output) = world.get_non_send_resource::<AudioOutput>() else {
         return;
     };
 
+    let failed = output.failed.load(Ordering::Relaxed);
+    let retry_due = output.retry_at.is_some_and(|at| now >= at);
+    if !failed && !retry_due {
+        return;
+    }
+    let lost = output.backend_name().to_string();
+    let elapsed = world.resource::<ExperienceClock>().elapsed();
+    let log = world.resource::<WideEventLog>().clone();
+    let config = world.resource::<AudioOutputConfig>().clone();
+
+    // Reopening would restart the sink's file, so keep it until a device is back
+    if !failed
+        && find_output_device(config.device.as_deref())
+            .or_else(|_| find_output_device(None))
+            .is_err()
+    {
+        if let Some(mut output) = world.get_non_send_resource_mut::<AudioOutput>() {
+            output.retry_at = Some(now + DEVICE_RETRY_INTERVAL);
+        }
+        return;
+    }
+
+    if failed {
+        warn!(target: "lightwatch::audio", "Audio stream on {} failed, rebuilding", lost);
+        wide_event!("audio_stream_lost")
+            .with_str("device", &lost)
+            .emit(&log, elapsed);
+    }
+
+    // Close the old stream before opening the device again
+    world.remove_non_send_resource::<AudioOutput>();
+    let (mut output, _) = open_output(world, &config, config.sink_file.clone());
+
+    if output.has_device() {
+        info!(
+            target: "lightwatch::audio",
+            "Audio output restored on {} at {:.2}s",
+            output.backend_name(),
+            elapsed
+        );
+        wide_event!("audio_stream_restored")
+            .with_str("device", output.backend_name())
+            .emit(&log, elapsed);
+    } else {
+        output.retry_at = Some(now + DEVICE_RETRY_INTERVAL);
+    }
+
+    // The new mixer starts blank: bring ambiance and volume to where the run is
+    let ambiance = crate::core::ambiance_playing(world, elapsed);
+    let volume = world
+        .get_resource::<UserSettings>()
+        .map_or(config.master_volume, |settings| settings.volume);
+    let queue = world.resource::<AudioTriggerQueue>();
+    queue.send(AudioTrigger::Resync { ambiance });
+    queue.send(AudioTrigger::SetVolume(volume));
+
+    world.insert_non_send_resource(output);
+}
+
+/// Start the synthesis stream on `device`; errors raise `failed`
+fn start_output_stream(
+    device: &cpal::Device,
+    triggers: TriggerReceiver,
+    spatial: SpatialReceiver,
+    master_volume: f32,
+    failed: Arc<AtomicBool>,
+) -> Result<cpal::Stream, String> {
+    let config = device
+        .default_output_config()
+        .map_err(|err| format!("no default audio config: {}", err))?;
+
     info!(
         target: "lightwatch::audio",
         "Audio device: {}, Sample rate: {}, Channels: {}",
@@ -273,35 +617,32 @@ pub fn init_audio_output(world: &mut World) {
     let sample_rate = config.sample_rate().0 as f32;
     let channels = config.channels() as usize;
 
-    // Audio state owned by the audio thread
-    let state = Arc::new(Mutex::new(AudioState::new(sample_rate)));
-
-    // Counter for periodic spatial updates
-    let mut spatial_update_counter = 0u32;
+    // Everything the callback touches is moved into it: no locks on the audio thread
+    let mut audio_state = AudioState::new(sample_rate, master_volume);
+    let mut triggers = triggers;
+    let mut spatial = spatial;
+    let mut expected_playback: Option<cpal::StreamInstant> = None;
 
     let stream = device
         .build_output_stream(
             &config.into(),
-            move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
-                let Ok(mut audio_state) = state.lock() else {
-                    for sample in data.iter_mut() {
-                        *sample = 0.0;
+            move |data: &mut [f32], info: &cpal::OutputCallbackInfo| {
+                let frames = data.len() / channels.max(1);
+                let buffer = Duration::from_secs_f32(frames as f32 / sample_rate);
+
+                // A buffer that starts playing well after the previous one
+                // ended means the device ran dry
+                let playback = info.timestamp().playback;
+                if let Some(gap) = expected_playback.and_then(|at| playback.duration_since(&at)) {
+                    if gap > buffer / 2 {
+                        triggers.stats().record_underrun();
                     }
-                    return;
-                };
-
-                // Process any pending triggers
-                if let Ok(mut queue) = triggers.try_lock() {
-                    audio_state.process_triggers(&mut queue);
                 }
+                expected_playback = playback.add(buffer);
 
-                // Periodically update spatial data (every ~1024 samples)
-                spatial_update_counter += 1;
-                if spatial_update_counter >= 1024 {
-                    spatial_update_counter = 0;
-                    if let Ok(spatial_data) = spatial.try_lock() {
-                        audio_state.update_spatial(&spatial_data);
-                    }
+                triggers.drain(Instant::now(), |trigger| audio_state.process_trigger(trigger));
+                if let Some(spatial_data) = spatial.latest() {
+                    audio_state.update_spatial(&spatial_data);
                 }
 
                 // Generate stereo audio
@@ -320,60 +661,41 @@ pub fn init_audio_output(world: &mut World) {
                     }
                 }
             },
-            |err| {
+            move |err| {
                 error!(target: "lightwatch::audio", "Audio stream error: {}", err);
+                failed.store(true, Ordering::Relaxed);
             },
             None,
         )
-        .expect("Failed to build audio stream");
+        .map_err(|err| format!("cannot build audio stream: {}", err))?;
 
-    stream.play().expect("Failed to start audio stream");
+    stream
+        .play()
+        .map_err(|err| format!("cannot start audio stream: {}", err))?;
 
-    world.insert_non_send_resource(AudioOutput { stream });
-
-    info!(target: "lightwatch::audio", "Audio output initialized with spatial stereo");
+    Ok(stream)
 }
 
-/// System to sync spatial audio data from ECS to audio thread
+/// System to sync spatial audio data from ECS to audio thread (every frame; the
+/// buses glide between snapshots)
 pub fn sync_spatial_audio_data(
     shared: Res<SharedSpatialData>,
     sources: Query<(&SpatialAudioSource, &crate::travelers::Traveler)>,
+    mut data: Local<SpatialMixData>,
 ) {
-    let Ok(mut data) = shared.data.try_lock() else {
-        return;
-    };
-
-    let mut total_gain = 0.0;
-    let mut weighted_pan = 0.0;
-    let mut active_count = 0.0;
-
     for (source, traveler) in sources.iter() {
-        // Store per-traveler spatial data
         let idx = traveler.id as usize;
         if idx < data.travelers.len() {
             data.travelers[idx] = SpatialSourceData {
                 gain: source.computed_gain,
                 pan: source.computed_pan,
                 pitch: source.computed_pitch,
+                cutoff: source.computed_cutoff,
             };
         }
-
-        // Accumulate for master spatial
-        if source.computed_gain > 0.01 {
-            total_gain += source.computed_gain;
-            weighted_pan += source.computed_pan * source.computed_gain;
-            active_count += 1.0;
-        }
     }
 
-    // Calculate master spatial (weighted average)
-    if active_count > 0.0 {
-        data.master_gain = (total_gain / active_count).clamp(0.3, 1.0);
-        data.master_pan = (weighted_pan / total_gain).clamp(-1.0, 1.0);
-    } else {
-        data.master_gain = 1.0;
-        data.master_pan = 0.0;
-    }
+    shared.publish(*data);
 }
 
 /// Audio output plugin
@@ -382,10 +704,16 @@ pub struct AudioOutputPlugin;
 impl Plugin for AudioOutputPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<AudioTriggerQueue>()
-            .insert_resource(SharedSpatialData {
-                data: Arc::new(Mutex::new(SpatialMixData::default())),
-            })
+            .init_resource::<AudioOutputConfig>()
+            .init_resource::<SharedSpatialData>()
             .add_systems(Startup, init_audio_output)
-            .add_systems(Update, sync_spatial_audio_data);
+            .add_systems(
+                Update,
+                (
+                    sync_spatial_audio_data,
+                    report_audio_glitches,
+                    recover_audio_output,
+                ),
+            );
     }
 }
@@ -0,0 +1,209 @@
+//! Offline audio render - the full mix written to WAV without an audio device
+//!
+//! Runs the experience headless and feeds the audio thread's mixer from the
+//! same trigger and spatial rings a live stream uses. After each frame the
+//! mix is rendered up to the clock's elapsed time, then that frame's triggers
+//! are applied, so every trigger lands where the clock stood when it was sent.
+//! Optional stems hold each bus dry, before the shared reverb and soft clip.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+
+use super::channel::{AudioTriggerQueue, SharedSpatialData, SpatialReceiver, TriggerReceiver};
+use super::output::{AudioOutputConfig, AudioState, StemFrame};
+use super::wav::WavWriter;
+use crate::core::{
+    build_headless_app, run_headless_app, ExperienceClock, HeadlessConfig, HeadlessReport,
+    TravelerId,
+};
+
+/// Default render sample rate (Hz)
+pub const DEFAULT_RENDER_SAMPLE_RATE: u32 = 48000;
+
+/// Offline render parameters
+#[derive(Debug, Clone)]
+pub struct AudioRenderConfig {
+    /// Simulation settings (seed, timestep, replay)
+    pub headless: HeadlessConfig,
+    /// Mix output file
+    pub path: PathBuf,
+    pub sample_rate: u32,
+    /// Also write one file per bus next to the mix
+    pub stems: bool,
+}
+
+impl Default for AudioRenderConfig {
+    fn default() -> Self {
+        Self {
+            headless: HeadlessConfig::default(),
+            path: PathBuf::from("lightwatch.wav"),
+            sample_rate: DEFAULT_RENDER_SAMPLE_RATE,
+            stems: false,
+        }
+    }
+}
+
+/// What an offline render produced
+#[derive(Debug, Clone)]
+pub struct AudioRenderReport {
+    /// The simulation behind the render
+    pub headless: HeadlessReport,
+    /// Mix first, then any stems
+    pub files: Vec<PathBuf>,
+    pub frames: u64,
+    pub sample_rate: u32,
+    /// Largest mix sample magnitude
+    pub peak: f32,
+}
+
+impl AudioRenderReport {
+    pub fn duration(&self) -> f32 {
+        self.frames as f32 / self.sample_rate as f32
+    }
+}
+
+/// Stem file for `bus` next to the mix: `out.wav` gives `out.ambiance.wav`
+pub fn stem_path(mix: &Path, bus: &str) -> PathBuf {
+    mix.with_extension(format!("{}.wav", bus))
+}
+
+/// Stem bus names, in `StemFrame` order
+fn stem_names() -> impl Iterator<Item = &'static str> {
+    ["ambiance", "events"]
+        .into_iter()
+        .chain(TravelerId::all().iter().map(|id| id.name()))
+}
+
+/// Stem samples, in `stem_names` order
+fn stem_samples(frame: &StemFrame) -> impl Iterator<Item = (f32, f32)> {
+    [frame.ambiance, frame.events]
+        .into_iter()
+        .chain(frame.travelers)
+}
+
+/// The audio thread's mixer, driven from a headless app
+struct OfflineMixer {
+    state: AudioState,
+    triggers: TriggerReceiver,
+    spatial: SpatialReceiver,
+    sample_rate: u32,
+    mix: WavWriter,
+    /// One file per bus, in `stem_names` order
+    stems: Option<Vec<WavWriter>>,
+    rendered: u64,
+    peak: f32,
+    /// First write failure; rendering stops there
+    error: Option<io::Error>,
+}
+
+impl OfflineMixer {
+    /// Connect to the app's audio rings and open the output files
+    fn connect(world: &World, config: &AudioRenderConfig) -> io::Result<Self> {
+        let stems = if config.stems {
+            let writers = stem_names()
+                .map(|bus| WavWriter::create(stem_path(&config.path, bus), config.sample_rate))
+                .collect::<io::Result<Vec<_>>>()?;
+            Some(writers)
+        } else {
+            None
+        };
+
+        let master_volume = world.resource::<AudioOutputConfig>().master_volume;
+        Ok(Self {
+            state: AudioState::new(config.sample_rate as f32, master_volume),
+            triggers: world.resource::<AudioTriggerQueue>().connect(),
+            spatial: world.resource::<SharedSpatialData>().connect(),
+            sample_rate: config.sample_rate,
+            mix: WavWriter::create(&config.path, config.sample_rate)?,
+            stems,
+            rendered: 0,
+            peak: 0.0,
+            error: None,
+        })
+    }
+
+    /// Render up to the clock, then apply what the frame sent
+    fn after_update(&mut self, world: &World) {
+        let clock = world.resource::<ExperienceClock>();
+        if clock.has_started() {
+            let until = clock.elapsed().min(clock.duration());
+            self.render_until(until);
+        }
+
+        let state = &mut self.state;
+        self.triggers.drain_untimed(|trigger| state.process_trigger(trigger));
+        if let Some(spatial_data) = self.spatial.latest() {
+            self.state.update_spatial(&spatial_data);
+        }
+    }
+
+    /// Render frames up to `seconds` into the run
+    fn render_until(&mut self, seconds: f32) {
+        let target = (seconds as f64 * self.sample_rate as f64).round() as u64;
+        while self.rendered < target && self.error.is_none() {
+            if let Err(err) = self.render_frame() {
+                self.error = Some(err);
+            }
+            self.rendered += 1;
+        }
+    }
+
+    fn render_frame(&mut self) -> io::Result<()> {
+        let mut stems = StemFrame::default();
+        let (left, right) = if self.stems.is_some() {
+            self.state.generate_frame(Some(&mut stems))
+        } else {
+            self.state.generate_frame(None)
+        };
+
+        self.peak = self.peak.max(left.abs()).max(right.abs());
+        self.mix.write_frame(left, right)?;
+        if let Some(writers) = &mut self.stems {
+            for (writer, (left, right)) in writers.iter_mut().zip(stem_samples(&stems)) {
+                writer.write_frame(left, right)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Render the rest of the run and close the files
+    fn finish(mut self, duration: f32) -> io::Result<(u64, f32)> {
+        self.render_until(duration);
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
+        self.mix.finish()?;
+        for writer in self.stems.into_iter().flatten() {
+            writer.finish()?;
+        }
+        Ok((self.rendered, self.peak))
+    }
+}
+
+/// Run the experience headless and write its audio to `config.path`
+pub fn run_audio_render(config: &AudioRenderConfig) -> io::Result<AudioRenderReport> {
+    let app = build_headless_app(&config.headless);
+    let mut mixer = OfflineMixer::connect(app.world(), config)?;
+    let mut duration = 0.0;
+    let headless = run_headless_app(app, &config.headless, |app| {
+        mixer.after_update(app.world());
+        duration = app.world().resource::<ExperienceClock>().duration();
+    });
+    let (frames, peak) = mixer.finish(duration)?;
+
+    let mut files = vec![config.path.clone()];
+    if config.stems {
+        files.extend(stem_names().map(|bus| stem_path(&config.path, bus)));
+    }
+
+    Ok(AudioRenderReport {
+        headless,
+        files,
+        frames,
+        sample_rate: config.sample_rate,
+        peak,
+    })
+}
@@ -89,7 +89,8 @@ impl Reverb {
         }
     }
 
-    pub fn process(&mut self, input: f32) -> f32 {
+    /// Reverberated signal only; the caller mixes it with the dry signal by `mix`
+    pub fn wet(&mut self, input: f32) -> f32 {
         // Sum comb filter outputs
         let mut wet: f32 = self.comb_filters.iter_mut().map(|f| f.process(input)).sum();
         wet /= self.comb_filters.len() as f32;
@@ -98,9 +99,7 @@ impl Reverb {
         for filter in &mut self.allpass_filters {
             wet = filter.process(wet);
         }
-
-        // Mix dry and wet
-        input * (1.0 - self.mix) + wet * self.mix
+        wet
     }
 }
 
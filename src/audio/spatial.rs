@@ -17,6 +17,10 @@ pub struct SpatialAudioConfig {
     pub speed_of_sound: f32,
     /// Doppler factor
     pub doppler_factor: f32,
+    /// Low-pass cutoff at the reference distance (Hz)
+    pub near_cutoff: f32,
+    /// Low-pass cutoff at the maximum distance (Hz)
+    pub far_cutoff: f32,
     /// Master reverb mix
     #[allow(dead_code)]
     pub reverb_mix: f32,
@@ -30,6 +34,8 @@ impl Default for SpatialAudioConfig {
             rolloff: 1.0,
             speed_of_sound: 343.0,
             doppler_factor: 0.5,
+            near_cutoff: 18000.0,
+            far_cutoff: 1200.0,
             reverb_mix: 0.4,
         }
     }
@@ -51,14 +57,13 @@ pub struct SpatialAudioSource {
     /// Previous position (for Doppler)
     pub previous_position: Vec3,
     /// Computed gain after spatial processing
-    #[allow(dead_code)]
     pub computed_gain: f32,
     /// Computed pan (-1 left, +1 right)
-    #[allow(dead_code)]
     pub computed_pan: f32,
     /// Computed pitch shift (Doppler)
-    #[allow(dead_code)]
     pub computed_pitch: f32,
+    /// Computed low-pass cutoff for distance (Hz)
+    pub computed_cutoff: f32,
 }
 
 impl Default for SpatialAudioSource {
@@ -69,6 +74,7 @@ impl Default for SpatialAudioSource {
             computed_gain: 1.0,
             computed_pan: 0.0,
             computed_pitch: 1.0,
+            computed_cutoff: 18000.0,
         }
     }
 }
@@ -91,6 +97,18 @@ pub fn calculate_attenuation(distance: f32, config: &SpatialAudioConfig) -> f32
     attenuation.clamp(0.0, 1.0)
 }
 
+/// Calculate the distance low-pass cutoff: open up close, darker with distance
+/// (air absorbs high frequencies first)
+pub fn calculate_distance_cutoff(distance: f32, config: &SpatialAudioConfig) -> f32 {
+    let range = (config.max_distance - config.reference_distance).max(f32::EPSILON);
+    let t = ((distance - config.reference_distance) / range).clamp(0.0, 1.0);
+
+    // Interpolate on a log scale so the darkening sounds even
+    let near = config.near_cutoff.max(20.0).ln();
+    let far = config.far_cutoff.max(20.0).ln();
+    (near + (far - near) * t).exp()
+}
+
 /// Calculate stereo panning from 3D position
 pub fn calculate_panning(
     source_pos: Vec3,
@@ -167,6 +185,7 @@ pub fn update_spatial_audio(
         // Distance attenuation
         let distance = (source_pos - listener_pos).length();
         source.computed_gain = source.volume * calculate_attenuation(distance, &config);
+        source.computed_cutoff = calculate_distance_cutoff(distance, &config);
 
         // Stereo panning
         source.computed_pan = calculate_panning(
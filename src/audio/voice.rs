@@ -1,6 +1,101 @@
 //! Synth voice combining oscillator, filter, and envelope
 
 use super::{BiquadFilter, Envelope, FilterType, Oscillator, Waveform};
+use crate::core::TravelerId;
+
+/// Sound of a voice: waveform, envelope shape and filter
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Timbre {
+    pub waveform: Waveform,
+    pub attack: f32,
+    pub decay: f32,
+    pub sustain: f32,
+    pub release: f32,
+    /// Filter cutoff with the filter envelope closed (Hz)
+    pub cutoff: f32,
+    /// Cutoff added at the filter envelope's peak (Hz)
+    pub filter_env_amount: f32,
+    /// Level relative to other timbres
+    pub gain: f32,
+}
+
+impl Default for Timbre {
+    fn default() -> Self {
+        Self {
+            waveform: Waveform::Sine,
+            attack: 0.01,
+            decay: 0.1,
+            sustain: 0.7,
+            release: 0.3,
+            cutoff: 500.0,
+            filter_env_amount: 1000.0,
+            gain: 1.0,
+        }
+    }
+}
+
+impl Timbre {
+    /// Each traveler's leitmotif voice
+    pub fn for_traveler(id: TravelerId) -> Self {
+        match id {
+            // Warm and measured
+            TravelerId::Archivist => Self {
+                waveform: Waveform::Triangle,
+                attack: 0.05,
+                decay: 0.3,
+                sustain: 0.6,
+                release: 1.2,
+                cutoff: 700.0,
+                filter_env_amount: 900.0,
+                gain: 0.5,
+            },
+            // Reedy, restless
+            TravelerId::Wanderer => Self {
+                waveform: Waveform::Saw,
+                attack: 0.02,
+                decay: 0.2,
+                sustain: 0.5,
+                release: 0.6,
+                cutoff: 900.0,
+                filter_env_amount: 1600.0,
+                gain: 0.3,
+            },
+            // Round and steady
+            TravelerId::Keeper => Self {
+                waveform: Waveform::Sine,
+                attack: 0.08,
+                decay: 0.2,
+                sustain: 0.8,
+                release: 1.0,
+                cutoff: 400.0,
+                filter_env_amount: 600.0,
+                gain: 0.6,
+            },
+            // Bright, plucked
+            TravelerId::Child => Self {
+                waveform: Waveform::Triangle,
+                attack: 0.005,
+                decay: 0.15,
+                sustain: 0.4,
+                release: 0.5,
+                cutoff: 1500.0,
+                filter_env_amount: 2000.0,
+                gain: 0.45,
+            },
+            // Hollow, slow to arrive and to leave
+            TravelerId::Other => Self {
+                waveform: Waveform::Square,
+                attack: 0.3,
+                decay: 0.5,
+                sustain: 0.5,
+                release: 2.5,
+                cutoff: 350.0,
+                filter_env_amount: 500.0,
+                gain: 0.25,
+            },
+        }
+    }
+}
 
 /// Synth voice combining oscillator, filter, and envelope
 pub struct Voice {
@@ -8,6 +103,7 @@ pub struct Voice {
     pub filter: BiquadFilter,
     pub amp_envelope: Envelope,
     pub filter_envelope: Envelope,
+    pub base_cutoff: f32,
     pub filter_env_amount: f32,
     sample_rate: f32,
 }
@@ -19,11 +115,23 @@ impl Voice {
             filter: BiquadFilter::new(FilterType::LowPass, 2000.0, 1.0, sample_rate),
             amp_envelope: Envelope::new(0.01, 0.1, 0.7, 0.3),
             filter_envelope: Envelope::new(0.01, 0.2, 0.3, 0.5),
+            base_cutoff: 500.0,
             filter_env_amount: 1000.0,
             sample_rate,
         }
     }
 
+    /// Take on `timbre` (applies from the next trigger)
+    pub fn set_timbre(&mut self, timbre: &Timbre) {
+        self.oscillator.waveform = timbre.waveform;
+        self.amp_envelope.attack = timbre.attack;
+        self.amp_envelope.decay = timbre.decay;
+        self.amp_envelope.sustain = timbre.sustain;
+        self.amp_envelope.release = timbre.release;
+        self.base_cutoff = timbre.cutoff;
+        self.filter_env_amount = timbre.filter_env_amount;
+    }
+
     /// Trigger voice with frequency
     pub fn trigger(&mut self, frequency: f32) {
         self.oscillator.set_frequency(frequency);
@@ -32,7 +140,6 @@ impl Voice {
     }
 
     /// Release voice
-    #[allow(dead_code)]
     pub fn release(&mut self) {
         self.amp_envelope.release();
         self.filter_envelope.release();
@@ -49,8 +156,7 @@ impl Voice {
 
         // Apply filter with envelope modulation
         let filter_env = self.filter_envelope.process(delta_time);
-        let base_cutoff = 500.0;
-        let cutoff = base_cutoff + filter_env * self.filter_env_amount;
+        let cutoff = self.base_cutoff + filter_env * self.filter_env_amount;
         self.filter.set_cutoff(cutoff);
         let filtered = self.filter.process(osc_sample);
 
@@ -63,4 +169,14 @@ impl Voice {
     pub fn is_active(&self) -> bool {
         self.amp_envelope.is_active()
     }
+
+    /// Releasing, or already silent
+    pub fn is_released(&self) -> bool {
+        self.amp_envelope.is_released()
+    }
+
+    /// Current amplitude envelope level
+    pub fn level(&self) -> f32 {
+        self.amp_envelope.level()
+    }
 }
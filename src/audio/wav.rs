@@ -0,0 +1,82 @@
+//! WAV file writer - 32-bit float stereo, streamed to disk
+
+use std::fs::File;
+use std::io::{self, BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Bytes before the sample data
+const HEADER_LEN: u32 = 58;
+
+/// Bytes per stereo frame
+const FRAME_LEN: u32 = 8;
+
+/// WAVE_FORMAT_IEEE_FLOAT
+const FORMAT_FLOAT: u16 = 3;
+
+/// Stereo float WAV file; sizes are filled in by `finish`
+pub struct WavWriter {
+    out: BufWriter<File>,
+    frames: u32,
+}
+
+impl WavWriter {
+    pub fn create(path: impl AsRef<Path>, sample_rate: u32) -> io::Result<Self> {
+        let mut out = BufWriter::new(File::create(path)?);
+
+        out.write_all(b"RIFF")?;
+        out.write_all(&(HEADER_LEN - 8).to_le_bytes())?;
+        out.write_all(b"WAVE")?;
+
+        out.write_all(b"fmt ")?;
+        out.write_all(&18u32.to_le_bytes())?;
+        out.write_all(&FORMAT_FLOAT.to_le_bytes())?;
+        out.write_all(&2u16.to_le_bytes())?;
+        out.write_all(&sample_rate.to_le_bytes())?;
+        out.write_all(&(sample_rate * FRAME_LEN).to_le_bytes())?;
+        out.write_all(&(FRAME_LEN as u16).to_le_bytes())?;
+        out.write_all(&32u16.to_le_bytes())?;
+        out.write_all(&0u16.to_le_bytes())?;
+
+        // Non-PCM formats carry the frame count in a fact chunk
+        out.write_all(b"fact")?;
+        out.write_all(&4u32.to_le_bytes())?;
+        out.write_all(&0u32.to_le_bytes())?;
+
+        out.write_all(b"data")?;
+        out.write_all(&0u32.to_le_bytes())?;
+
+        Ok(Self { out, frames: 0 })
+    }
+
+    pub fn write_frame(&mut self, left: f32, right: f32) -> io::Result<()> {
+        self.out.write_all(&left.to_le_bytes())?;
+        self.out.write_all(&right.to_le_bytes())?;
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Frames written so far
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+
+    /// Write the chunk sizes for the frames so far, so the file is complete
+    /// even if it is never finished
+    pub fn sync(&mut self) -> io::Result<()> {
+        let data_len = self.frames * FRAME_LEN;
+
+        self.out.seek(SeekFrom::Start(4))?;
+        self.out.write_all(&(HEADER_LEN - 8 + data_len).to_le_bytes())?;
+        self.out.seek(SeekFrom::Start(46))?;
+        self.out.write_all(&self.frames.to_le_bytes())?;
+        self.out.seek(SeekFrom::Start(54))?;
+        self.out.write_all(&data_len.to_le_bytes())?;
+        self.out.seek(SeekFrom::End(0))?;
+        self.out.flush()
+    }
+
+    /// Write the chunk sizes and flush
+    pub fn finish(mut self) -> io::Result<()> {
+        self.sync()
+    }
+}
@@ -3,7 +3,9 @@
 use bevy::prelude::*;
 use bevy::render::render_resource::{AsBindGroup, ShaderRef};
 
-use crate::core::ExperienceClock;
+use crate::core::{
+    spawn_warmup_mesh, BangEvent, ExperienceClock, MomentAction, PhaseTable, PipelineWarmupAppExt,
+};
 
 /// Custom bang core material
 #[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
@@ -63,8 +65,8 @@ impl Default for BangCore {
     }
 }
 
-/// Bang timeline configuration
-#[derive(Resource)]
+/// Bang timeline configuration, derived from the Bang phase of the `PhaseTable`
+#[derive(Resource, Debug, Clone, PartialEq)]
 pub struct BangConfig {
     pub start_time: f32,
     pub light_point_time: f32,
@@ -76,25 +78,36 @@ pub struct BangConfig {
 
 impl Default for BangConfig {
     fn default() -> Self {
+        Self::from_table(&PhaseTable::default())
+    }
+}
+
+impl BangConfig {
+    /// Stage times for the table's Bang phase (authored for the standard cut)
+    pub fn from_table(table: &PhaseTable) -> Self {
         Self {
-            start_time: 2.0,
-            light_point_time: 2.5,
-            expansion_start: 3.0,
-            peak_time: 4.0,
-            settle_time: 6.0,
-            complete_time: 10.0,
+            start_time: table.rescale(2.0),
+            light_point_time: table.rescale(2.5),
+            expansion_start: table.rescale(3.0),
+            peak_time: table.rescale(4.0),
+            settle_time: table.rescale(6.0),
+            complete_time: table.rescale(10.0),
         }
     }
 }
 
+/// Follow a changed `PhaseTable`
+pub fn sync_bang_config(table: Res<PhaseTable>, mut config: ResMut<BangConfig>) {
+    *config = BangConfig::from_table(&table);
+}
+
 /// Spawn bang core geometry with custom material
 pub fn spawn_bang_core(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<BangCoreMaterial>>,
 ) {
-    // Core sphere - icosphere for smooth surface
-    let mesh = meshes.add(Sphere::new(0.1).mesh().ico(3).unwrap());
+    let mesh = meshes.add(bang_core_mesh());
 
     let material = materials.add(BangCoreMaterial::default());
 
@@ -113,6 +126,11 @@ pub fn spawn_bang_core(
     info!(target: "lightwatch::bang", "Spawned bang core with custom shader");
 }
 
+/// Core sphere - icosphere for smooth surface
+pub fn bang_core_mesh() -> Mesh {
+    Sphere::new(0.1).mesh().ico(3).unwrap()
+}
+
 /// Update bang core based on timeline
 pub fn update_bang_core(
     clock: Res<ExperienceClock>,
@@ -205,6 +223,16 @@ fn ease_out_expo(t: f32) -> f32 {
     }
 }
 
+/// Moment actions owned by the bang (`BangStage`)
+pub fn handle_bang_moment(world: &mut World, action: &MomentAction, elapsed: f32) {
+    if let MomentAction::BangStage(stage) = action {
+        world.send_event(BangEvent {
+            stage: *stage,
+            elapsed,
+        });
+    }
+}
+
 /// Bang core plugin
 pub struct BangCorePlugin;
 
@@ -212,7 +240,12 @@ impl Plugin for BangCorePlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(MaterialPlugin::<BangCoreMaterial>::default())
             .init_resource::<BangConfig>()
+            .add_pipeline_warmup("bang core", |world, transform| {
+                let material = BangCoreMaterial::default();
+                Some(spawn_warmup_mesh(world, transform, bang_core_mesh(), material))
+            })
             .add_systems(Startup, spawn_bang_core)
+            .add_systems(PreUpdate, sync_bang_config.run_if(resource_changed::<PhaseTable>))
             .add_systems(Update, update_bang_core);
     }
 }
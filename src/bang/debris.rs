@@ -4,7 +4,10 @@ use bevy::prelude::*;
 use rand::prelude::*;
 
 use super::BangConfig;
-use crate::core::{ExperienceClock, TravelerId};
+use crate::core::{
+    despawn_all, reset_resource, spawn_warmup_mesh, ExperienceClock, ExperienceReset,
+    ExperienceRng, InterpolatedTransform, PipelineWarmupAppExt, TravelerId,
+};
 
 /// Debris particle system configuration
 #[derive(Resource)]
@@ -68,7 +71,24 @@ pub struct TravelerSpawnMarker {
     pub id: TravelerId,
 }
 
+/// Particle mesh (small icosphere)
+pub fn debris_mesh() -> Mesh {
+    Sphere::new(0.05).mesh().ico(1).unwrap()
+}
+
+/// Initial particle material (white hot); each particle cools its own copy
+pub fn debris_material() -> StandardMaterial {
+    StandardMaterial {
+        base_color: Color::WHITE,
+        emissive: LinearRgba::new(1.0, 0.98, 0.95, 1.0),
+        unlit: true,
+        alpha_mode: AlphaMode::Add,
+        ..default()
+    }
+}
+
 /// Spawn debris at peak
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_debris(
     mut commands: Commands,
     clock: Res<ExperienceClock>,
@@ -77,6 +97,7 @@ pub fn spawn_debris(
     mut state: ResMut<DebrisState>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    mut rng: ResMut<ExperienceRng>,
 ) {
     if state.spawned {
         return;
@@ -87,10 +108,8 @@ pub fn spawn_debris(
         return;
     }
 
-    let mut rng = rand::thread_rng();
 
-    // Particle mesh (small icosphere)
-    let mesh = meshes.add(Sphere::new(0.05).mesh().ico(1).unwrap());
+    let mesh = meshes.add(debris_mesh());
 
     // Spawn particles
     for i in 0..debris_config.count {
@@ -115,23 +134,19 @@ pub fn spawn_debris(
             rng.gen_range(debris_config.min_lifetime..debris_config.max_lifetime)
         };
 
-        // Initial color (white hot)
-        let material = materials.add(StandardMaterial {
-            base_color: Color::WHITE,
-            emissive: LinearRgba::new(1.0, 0.98, 0.95, 1.0),
-            unlit: true,
-            alpha_mode: AlphaMode::Add,
-            ..default()
-        });
+        let material = materials.add(debris_material());
+
+        let transform = Transform::from_translation(Vec3::ZERO)
+            .with_scale(Vec3::splat(if is_seed { 0.1 } else { 0.05 }));
 
         commands.spawn((
             PbrBundle {
                 mesh: mesh.clone(),
                 material,
-                transform: Transform::from_translation(Vec3::ZERO)
-                    .with_scale(Vec3::splat(if is_seed { 0.1 } else { 0.05 })),
+                transform,
                 ..default()
             },
+            InterpolatedTransform::new(transform),
             DebrisParticle {
                 velocity,
                 age: 0.0,
@@ -294,8 +309,19 @@ impl Plugin for DebrisPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<DebrisConfig>()
             .init_resource::<DebrisState>()
+            .add_pipeline_warmup("debris", |world, transform| {
+                Some(spawn_warmup_mesh(world, transform, debris_mesh(), debris_material()))
+            })
+            .add_systems(
+                ExperienceReset,
+                (
+                    reset_resource::<DebrisState>,
+                    despawn_all::<DebrisParticle>,
+                    despawn_all::<TravelerSpawnMarker>,
+                ),
+            )
             .add_systems(
-                Update,
+                FixedUpdate,
                 (
                     spawn_debris,
                     update_debris,
@@ -6,7 +6,10 @@ use bevy::render::render_asset::RenderAssetUsages;
 use bevy::render::render_resource::PrimitiveTopology;
 
 use super::BangConfig;
-use crate::core::ExperienceClock;
+use crate::core::{
+    despawn_all, reset_resource, spawn_warmup_mesh, ExperienceClock, ExperienceReset,
+    PipelineWarmupAppExt,
+};
 
 /// Individual expansion ring
 #[derive(Component, Debug)]
@@ -84,6 +87,19 @@ pub fn create_ring_mesh(inner_radius: f32, outer_radius: f32, segments: usize) -
     mesh
 }
 
+/// Additive, unlit ring material (each ring gets its own copy to fade)
+pub fn ring_material() -> StandardMaterial {
+    StandardMaterial {
+        base_color: Color::srgba(0.91, 0.64, 0.27, 0.6),
+        emissive: LinearRgba::new(0.91, 0.64, 0.27, 1.0),
+        unlit: true,
+        alpha_mode: AlphaMode::Add,
+        double_sided: true,
+        cull_mode: None,
+        ..default()
+    }
+}
+
 /// Spawn expansion rings during bang
 pub fn spawn_expansion_rings(
     mut commands: Commands,
@@ -116,15 +132,7 @@ pub fn spawn_expansion_rings(
     // Spawn ring
     let mesh = meshes.add(create_ring_mesh(0.1, 0.15, 64));
 
-    let material = materials.add(StandardMaterial {
-        base_color: Color::srgba(0.91, 0.64, 0.27, 0.6),
-        emissive: LinearRgba::new(0.91, 0.64, 0.27, 1.0),
-        unlit: true,
-        alpha_mode: AlphaMode::Add,
-        double_sided: true,
-        cull_mode: None,
-        ..default()
-    });
+    let material = materials.add(ring_material());
 
     commands.spawn((
         PbrBundle {
@@ -229,6 +237,14 @@ impl Plugin for ExpansionPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ExpansionConfig>()
             .init_resource::<RingSpawnState>()
+            .add_pipeline_warmup("expansion ring", |world, transform| {
+                let mesh = create_ring_mesh(0.1, 0.15, 64);
+                Some(spawn_warmup_mesh(world, transform, mesh, ring_material()))
+            })
+            .add_systems(
+                ExperienceReset,
+                (reset_resource::<RingSpawnState>, despawn_all::<ExpansionRing>),
+            )
             .add_systems(
                 Update,
                 (spawn_expansion_rings, update_expansion_rings, reset_ring_state),
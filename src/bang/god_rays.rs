@@ -6,7 +6,7 @@ use bevy::prelude::*;
 
 use super::{BangConfig, BangCore};
 use crate::camera::ExperienceCamera;
-use crate::core::ExperienceClock;
+use crate::core::{reset_resource, ExperienceClock, ExperienceReset};
 
 /// God ray effect configuration
 #[derive(Resource)]
@@ -129,13 +129,11 @@ impl Plugin for GodRaysPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<GodRayConfig>()
             .init_resource::<GodRayState>()
+            .add_systems(ExperienceReset, reset_resource::<GodRayState>)
+            .add_systems(Update, update_light_screen_position)
             .add_systems(
-                Update,
-                (
-                    update_light_screen_position,
-                    update_god_ray_intensity,
-                    interpolate_god_rays,
-                ),
+                FixedUpdate,
+                (update_god_ray_intensity, interpolate_god_rays.after(update_god_ray_intensity)),
             );
 
         info!(target: "lightwatch::bang", "God rays system initialized (render integration in post-processing)");
@@ -2,6 +2,8 @@
 
 use bevy::prelude::*;
 
+use crate::core::MomentActionAppExt;
+
 mod core;
 mod debris;
 mod expansion;
@@ -23,6 +25,7 @@ impl Plugin for BangPlugin {
             .add_plugins(ExpansionPlugin)
             .add_plugins(GodRaysPlugin)
             .add_plugins(ShockwavePlugin)
-            .add_plugins(DebrisPlugin);
+            .add_plugins(DebrisPlugin)
+            .add_moment_actions(&["BangStage"], handle_bang_moment);
     }
 }
@@ -6,7 +6,10 @@ use bevy::render::render_asset::RenderAssetUsages;
 use bevy::render::render_resource::PrimitiveTopology;
 
 use crate::camera::ExperienceCamera;
-use crate::core::ExperienceClock;
+use crate::core::{
+    despawn_all, reset_resource, spawn_warmup_mesh, ExperienceClock, ExperienceReset,
+    InterpolatedTransform, PipelineWarmupAppExt,
+};
 
 /// Shockwave state
 #[derive(Component, Debug)]
@@ -133,6 +136,21 @@ pub fn create_shockwave_mesh(
     mesh
 }
 
+/// Transparent, refractive shockwave material
+pub fn shockwave_material() -> StandardMaterial {
+    StandardMaterial {
+        base_color: Color::srgba(1.0, 0.95, 0.9, 0.3),
+        emissive: LinearRgba::new(0.5, 0.4, 0.3, 1.0),
+        alpha_mode: AlphaMode::Blend,
+        specular_transmission: 0.8,
+        thickness: 0.2,
+        ior: 1.1,
+        double_sided: true,
+        cull_mode: None,
+        ..default()
+    }
+}
+
 /// Spawn shockwave at peak
 pub fn spawn_shockwave(
     mut commands: Commands,
@@ -151,27 +169,18 @@ pub fn spawn_shockwave(
     }
 
     let mesh = meshes.add(create_shockwave_mesh(1.0, 0.3, 64, 16));
+    let material = materials.add(shockwave_material());
 
-    // Transparent, refractive material
-    let material = materials.add(StandardMaterial {
-        base_color: Color::srgba(1.0, 0.95, 0.9, 0.3),
-        emissive: LinearRgba::new(0.5, 0.4, 0.3, 1.0),
-        alpha_mode: AlphaMode::Blend,
-        specular_transmission: 0.8,
-        thickness: 0.2,
-        ior: 1.1,
-        double_sided: true,
-        cull_mode: None,
-        ..default()
-    });
+    let transform = Transform::from_scale(Vec3::splat(0.1));
 
     commands.spawn((
         PbrBundle {
             mesh,
             material,
-            transform: Transform::from_scale(Vec3::splat(0.1)),
+            transform,
             ..default()
         },
+        InterpolatedTransform::new(transform),
         Shockwave {
             speed: config.initial_speed,
             max_radius: config.max_radius,
@@ -268,8 +277,16 @@ impl Plugin for ShockwavePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ShockwaveConfig>()
             .init_resource::<ShockwaveState>()
+            .add_pipeline_warmup("shockwave", |world, transform| {
+                let mesh = create_shockwave_mesh(1.0, 0.3, 64, 16);
+                Some(spawn_warmup_mesh(world, transform, mesh, shockwave_material()))
+            })
+            .add_systems(
+                ExperienceReset,
+                (reset_resource::<ShockwaveState>, despawn_all::<Shockwave>),
+            )
             .add_systems(
-                Update,
+                FixedUpdate,
                 (spawn_shockwave, update_shockwave, reset_shockwave_state),
             );
     }
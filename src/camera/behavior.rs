@@ -4,9 +4,10 @@
 
 use bevy::prelude::*;
 
-use super::CameraRig;
+use super::{focus_distance, CameraRig};
 use crate::core::easing::{ease_in_out_cubic, smooth_lerp_factor};
-use crate::core::{CameraBehavior, CameraBehaviorChangedEvent};
+use crate::core::{CameraBehavior, CameraBehaviorChangedEvent, CameraFocusEvent, CameraShakeEvent};
+use crate::core::{MomentAction, WideEventLog};
 use crate::wide_event;
 
 /// Current camera behavior state
@@ -74,10 +75,43 @@ impl Default for BehaviorParams {
     }
 }
 
+/// Moment actions owned by the camera (behavior, shake, focus)
+pub fn handle_camera_moment(world: &mut World, action: &MomentAction, elapsed: f32) {
+    match action {
+        MomentAction::SetCameraBehavior(behavior) => {
+            let from = world
+                .get_resource::<CameraBehaviorState>()
+                .map_or(CameraBehavior::Static, |state| state.current);
+            world.send_event(CameraBehaviorChangedEvent {
+                from,
+                to: *behavior,
+                elapsed,
+            });
+        }
+        MomentAction::CameraShake { intensity, duration } => {
+            world.send_event(CameraShakeEvent {
+                intensity: *intensity,
+                duration: *duration,
+                elapsed,
+            });
+        }
+        MomentAction::FocusOn(target) => {
+            let distance = focus_distance(world, *target);
+            world.send_event(CameraFocusEvent {
+                target: *target,
+                distance,
+                elapsed,
+            });
+        }
+        _ => {}
+    }
+}
+
 /// Handle behavior change events
 pub fn handle_behavior_changes(
     mut state: ResMut<CameraBehaviorState>,
     mut events: EventReader<CameraBehaviorChangedEvent>,
+    log: Res<WideEventLog>,
 ) {
     for event in events.read() {
         if state.current != event.to {
@@ -89,7 +123,7 @@ pub fn handle_behavior_changes(
             wide_event!("camera_behavior_changed")
                 .with_str("from", format!("{:?}", state.previous))
                 .with_str("to", format!("{:?}", state.current))
-                .emit(event.elapsed);
+                .emit(&log, event.elapsed);
         }
     }
 }
@@ -3,6 +3,7 @@
 use bevy::prelude::*;
 
 use super::CameraRig;
+use crate::core::ExperienceClock;
 
 /// Breathing motion parameters
 #[derive(Resource)]
@@ -32,8 +33,19 @@ impl Default for BreathingConfig {
 }
 
 /// Update breathing motion
-pub fn update_breathing(time: Res<Time>, config: Res<BreathingConfig>, mut rigs: Query<&mut CameraRig>) {
-    let t = time.elapsed_seconds();
+pub fn update_breathing(
+    time: Res<Time>,
+    clock: Res<ExperienceClock>,
+    config: Res<BreathingConfig>,
+    mut rigs: Query<&mut CameraRig>,
+) {
+    // Experience time once the show starts, so seeks land on the same breath;
+    // the ready screen breathes on app time
+    let t = if clock.has_started() {
+        clock.elapsed()
+    } else {
+        time.elapsed_seconds()
+    };
 
     for mut rig in rigs.iter_mut() {
         // Primary breathing (vertical)
@@ -27,3 +27,15 @@ impl Default for CameraConfig {
         }
     }
 }
+
+/// Extra view rotation for one screen of a multi-screen panorama
+///
+/// Applied on top of the interpolated camera transform, so the simulation
+/// (and every synchronised instance) shares one camera path.
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct CameraViewOffset {
+    /// Rotation about the world up axis, in radians (positive turns left)
+    pub yaw: f32,
+    /// Rotation about the camera's right axis, in radians (positive tilts up)
+    pub pitch: f32,
+}
@@ -2,11 +2,12 @@
 
 #![allow(dead_code)]
 
-use bevy::core_pipeline::dof::DepthOfFieldSettings as BevyDofSettings;
+use bevy::core_pipeline::dof::{DepthOfFieldMode, DepthOfFieldSettings as BevyDofSettings};
 use bevy::prelude::*;
 
 use super::ExperienceCamera;
-use crate::core::{CameraFocusEvent, ExperienceClock, Phase};
+use crate::core::{CameraFocusEvent, ExperienceClock, PerformanceConfig, Phase, TravelerId};
+use crate::travelers::Traveler;
 
 /// Depth of field configuration
 #[derive(Resource)]
@@ -15,6 +16,8 @@ pub struct DepthOfFieldSettings {
     pub focus_distance: f32,
     /// Target focus distance (for lerping)
     pub target_focus: f32,
+    /// Distance held by a `FocusOn` moment (None = follow the phase)
+    pub focus_override: Option<f32>,
     /// Focus transition speed
     pub focus_speed: f32,
     /// Aperture (f-stop) - lower = more blur
@@ -34,6 +37,7 @@ impl Default for DepthOfFieldSettings {
         Self {
             focus_distance: 15.0,
             target_focus: 15.0,
+            focus_override: None,
             focus_speed: 2.0,
             aperture: 2.8,
             max_blur: 8.0,
@@ -69,9 +73,9 @@ impl DepthOfFieldSettings {
     }
 }
 
-/// Update DOF focus based on phase
-pub fn update_dof_for_phase(clock: Res<ExperienceClock>, mut settings: ResMut<DepthOfFieldSettings>) {
-    let target = match clock.phase() {
+/// Focus distance for each phase
+fn phase_focus(phase: Phase) -> f32 {
+    match phase {
         Phase::Signal => 15.0,     // Normal distance
         Phase::Bang => 5.0,        // Close focus for intensity
         Phase::Awakening => 12.0,  // Medium
@@ -79,9 +83,41 @@ pub fn update_dof_for_phase(clock: Res<ExperienceClock>, mut settings: ResMut<De
         Phase::Connection => 8.0,  // Sharp focus on travelers
         Phase::Acceptance => 25.0, // Pull back, softer focus
         Phase::Ended => 50.0,      // Very soft
+    }
+}
+
+/// Update DOF focus based on phase
+pub fn update_dof_for_phase(clock: Res<ExperienceClock>, mut settings: ResMut<DepthOfFieldSettings>) {
+    settings.target_focus = settings
+        .focus_override
+        .unwrap_or_else(|| phase_focus(clock.phase()));
+}
+
+/// Distance from the camera to `target`, or the phase focus for `None`
+/// (and for travelers not in the scene)
+pub fn focus_distance(world: &mut World, target: Option<TravelerId>) -> f32 {
+    let phase = world
+        .get_resource::<ExperienceClock>()
+        .map_or(Phase::Signal, |clock| clock.phase());
+    let Some(target) = target else {
+        return phase_focus(phase);
     };
 
-    settings.target_focus = target;
+    let camera = world
+        .query_filtered::<&Transform, With<ExperienceCamera>>()
+        .iter(world)
+        .next()
+        .map(|transform| transform.translation);
+    let traveler = world
+        .query::<(&Traveler, &Transform)>()
+        .iter(world)
+        .find(|(traveler, _)| traveler.id == target)
+        .map(|(_, transform)| transform.translation);
+
+    match (camera, traveler) {
+        (Some(camera), Some(traveler)) => camera.distance(traveler).max(0.1),
+        _ => phase_focus(phase),
+    }
 }
 
 /// Handle explicit focus events
@@ -90,6 +126,7 @@ pub fn handle_focus_events(
     mut events: EventReader<CameraFocusEvent>,
 ) {
     for event in events.read() {
+        settings.focus_override = event.target.map(|_| event.distance);
         settings.target_focus = event.distance;
     }
 }
@@ -143,3 +180,39 @@ pub fn sync_dof_to_camera(
         dof.max_circle_of_confusion_diameter = settings.max_blur;
     }
 }
+
+/// Bevy DOF settings for the experience camera
+pub fn camera_dof(mode: DepthOfFieldMode) -> BevyDofSettings {
+    BevyDofSettings {
+        mode,
+        focal_distance: 15.0, // Updated per-phase
+        sensor_height: 0.01866, // Super 35 format
+        aperture_f_stops: 2.8,
+        max_circle_of_confusion_diameter: 8.0,
+        max_depth: 1000.0,
+    }
+}
+
+/// Switch the camera's DOF mode, or remove DOF, for the quality tier
+pub fn apply_dof_quality(
+    mut commands: Commands,
+    performance: Res<PerformanceConfig>,
+    mut cameras: Query<(Entity, Option<&mut BevyDofSettings>), With<ExperienceCamera>>,
+) {
+    for (entity, dof) in cameras.iter_mut() {
+        match (performance.dof_mode, dof) {
+            (Some(mode), Some(mut dof)) => {
+                if dof.mode != mode {
+                    dof.mode = mode;
+                }
+            }
+            (Some(mode), None) => {
+                commands.entity(entity).insert(camera_dof(mode));
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).remove::<BevyDofSettings>();
+            }
+            (None, None) => {}
+        }
+    }
+}
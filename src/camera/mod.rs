@@ -2,6 +2,8 @@
 
 use bevy::prelude::*;
 
+use crate::core::{reset_resource, ExperienceReset, MomentActionAppExt};
+
 mod behavior;
 mod breathing;
 mod config;
@@ -25,14 +27,37 @@ impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<BreathingConfig>()
             .init_resource::<CameraConfig>()
+            .init_resource::<CameraViewOffset>()
             .init_resource::<CameraBehaviorState>()
             .init_resource::<DepthOfFieldSettings>()
             .init_resource::<CameraShake>()
             .init_resource::<ActiveTransition>()
             .add_event::<TriggerTransitionEvent>()
+            .add_moment_actions(
+                &["SetCameraBehavior", "CameraShake", "FocusOn"],
+                handle_camera_moment,
+            )
             .add_systems(Startup, spawn_camera)
             .add_systems(
-                Update,
+                ExperienceReset,
+                (
+                    reset_resource::<CameraBehaviorState>,
+                    reset_resource::<ActiveTransition>,
+                    reset_resource::<CameraShake>,
+                    reset_resource::<DepthOfFieldSettings>,
+                    reset_camera_rig,
+                ),
+            )
+            .add_systems(Update, (apply_dof_quality, sync_dof_to_camera).chain())
+            // Panorama view offset, on top of the interpolated transform
+            .add_systems(
+                PostUpdate,
+                apply_view_offset
+                    .after(crate::core::interpolate_transforms)
+                    .before(bevy::transform::TransformSystem::TransformPropagate),
+            )
+            .add_systems(
+                FixedUpdate,
                 (
                     // Breathing (always runs)
                     update_breathing,
@@ -50,9 +75,8 @@ impl Plugin for CameraPlugin {
                     reset_static_behavior,
                     // DOF systems
                     update_dof_for_phase,
-                    handle_focus_events,
+                    handle_focus_events.after(update_dof_for_phase),
                     interpolate_focus,
-                    sync_dof_to_camera.after(interpolate_focus),
                     // Shake systems
                     handle_shake_events,
                     update_shake,
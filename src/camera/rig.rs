@@ -3,11 +3,14 @@
 #![allow(dead_code)]
 
 use bevy::core_pipeline::bloom::BloomSettings;
-use bevy::core_pipeline::dof::{DepthOfFieldMode, DepthOfFieldSettings};
+use bevy::core_pipeline::dof::DepthOfFieldMode;
 use bevy::core_pipeline::prepass::DepthPrepass;
 use bevy::core_pipeline::tonemapping::Tonemapping;
 use bevy::prelude::*;
 
+use super::config::CameraViewOffset;
+use super::dof::camera_dof;
+use crate::core::InterpolatedTransform;
 use crate::post::{ChromaticAberrationSettings, FilmGrainSettings, GodRaysSettings, VignetteSettings};
 
 /// Marker component for the main experience camera
@@ -56,10 +59,23 @@ impl CameraRig {
     }
 }
 
+/// Return the camera to its starting position (on `ExperienceReset`)
+pub fn reset_camera_rig(
+    mut cameras: Query<(&mut CameraRig, &mut Transform, &mut InterpolatedTransform), With<ExperienceCamera>>,
+) {
+    for (mut rig, mut transform, mut interpolated) in cameras.iter_mut() {
+        *rig = CameraRig::default();
+        *transform =
+            Transform::from_translation(rig.base_position).looking_at(Vec3::ZERO, Vec3::Y);
+        interpolated.snap(*transform);
+    }
+}
+
 /// Spawn the experience camera
 pub fn spawn_camera(mut commands: Commands) {
     let rig = CameraRig::default();
     let position = rig.base_position;
+    let transform = Transform::from_translation(position).looking_at(Vec3::ZERO, Vec3::Y);
 
     commands.spawn((
         Camera3dBundle {
@@ -68,9 +84,10 @@ pub fn spawn_camera(mut commands: Commands) {
                 ..default()
             },
             tonemapping: Tonemapping::AcesFitted,
-            transform: Transform::from_translation(position).looking_at(Vec3::ZERO, Vec3::Y),
+            transform,
             ..default()
         },
+        InterpolatedTransform::new(transform),
         BloomSettings {
             intensity: 0.15,
             low_frequency_boost: 0.7,
@@ -80,14 +97,7 @@ pub fn spawn_camera(mut commands: Commands) {
             ..default()
         },
         // Depth of field - Gaussian for performance, updated dynamically
-        DepthOfFieldSettings {
-            mode: DepthOfFieldMode::Gaussian,
-            focal_distance: 15.0, // Updated per-phase
-            sensor_height: 0.01866, // Super 35 format
-            aperture_f_stops: 2.8,
-            max_circle_of_confusion_diameter: 8.0,
-            max_depth: 1000.0,
-        },
+        camera_dof(DepthOfFieldMode::Gaussian),
         DepthPrepass, // Required for DOF
         ChromaticAberrationSettings::new(0.002), // Base intensity, updated dynamically
         GodRaysSettings::default(), // Updated from bang::GodRayState
@@ -118,3 +128,19 @@ pub fn apply_rig_to_transform(
         transform.look_at(look_target, Vec3::Y);
     }
 }
+
+/// Turn the rendered view by `CameraViewOffset` (after interpolation)
+pub fn apply_view_offset(
+    offset: Res<CameraViewOffset>,
+    mut cameras: Query<&mut Transform, With<ExperienceCamera>>,
+) {
+    if offset.yaw == 0.0 && offset.pitch == 0.0 {
+        return;
+    }
+
+    for mut transform in cameras.iter_mut() {
+        transform.rotation = Quat::from_rotation_y(offset.yaw)
+            * transform.rotation
+            * Quat::from_rotation_x(offset.pitch);
+    }
+}
@@ -6,7 +6,7 @@ use bevy::prelude::*;
 
 use super::CameraRig;
 use crate::core::easing::smooth_lerp_factor;
-use crate::core::CameraShakeEvent;
+use crate::core::{CameraShakeEvent, WideEventLog};
 use crate::wide_event;
 
 /// Camera shake state using trauma system
@@ -118,6 +118,7 @@ pub fn fbm_noise(x: f32, octaves: i32) -> f32 {
 pub fn handle_shake_events(
     mut shake: ResMut<CameraShake>,
     mut events: EventReader<CameraShakeEvent>,
+    log: Res<WideEventLog>,
 ) {
     for event in events.read() {
         shake.add_trauma(event.intensity);
@@ -125,7 +126,7 @@ pub fn handle_shake_events(
         wide_event!("camera_shake_triggered")
             .with_f32("intensity", event.intensity)
             .with_f32("duration", event.duration)
-            .emit(event.elapsed);
+            .emit(&log, event.elapsed);
     }
 }
 
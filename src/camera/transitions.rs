@@ -5,7 +5,7 @@
 use bevy::prelude::*;
 
 use super::CameraRig;
-use crate::core::{Phase, PhaseChangedEvent};
+use crate::core::{Phase, PhaseChangedEvent, WideEventLog};
 use crate::wide_event;
 
 /// A camera transition between two states
@@ -208,6 +208,7 @@ pub struct TriggerTransitionEvent {
 /// Start transitions on phase change
 pub fn start_phase_transitions(
     clock: Res<crate::core::ExperienceClock>,
+    log: Res<WideEventLog>,
     mut active: ResMut<ActiveTransition>,
     mut events: EventReader<PhaseChangedEvent>,
 ) {
@@ -219,7 +220,7 @@ pub fn start_phase_transitions(
             wide_event!("camera_transition_started")
                 .with_str("phase", event.to.name())
                 .with_f32("duration", duration)
-                .emit(clock.elapsed());
+                .emit(&log, clock.elapsed());
         }
     }
 }
@@ -238,6 +239,7 @@ pub fn handle_transition_triggers(
 pub fn update_transition(
     time: Res<Time>,
     clock: Res<crate::core::ExperienceClock>,
+    log: Res<WideEventLog>,
     mut active: ResMut<ActiveTransition>,
     mut rigs: Query<&mut CameraRig>,
 ) {
@@ -278,6 +280,6 @@ pub fn update_transition(
 
     // Log completion
     if active.progress >= 1.0 {
-        wide_event!("camera_transition_completed").emit(clock.elapsed());
+        wide_event!("camera_transition_completed").emit(&log, clock.elapsed());
     }
 }
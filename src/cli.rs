@@ -0,0 +1,353 @@
+//! Command line for the `lightwatch` binary
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use bevy::prelude::default;
+use clap::{Parser, ValueEnum};
+use lightwatch::audio;
+use lightwatch::camera::CameraViewOffset;
+use lightwatch::core;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "lightwatch",
+    version,
+    about = "LIGHTWATCH - A 143-second contemplative experience"
+)]
+pub struct Cli {
+    /// Validate the moment schedule and exit
+    #[arg(long, value_name = "FILE", num_args = 0..=1, default_missing_value = core::DEFAULT_TIMELINE_PATH)]
+    pub check_timeline: Option<String>,
+
+    /// Simulate the full experience without a window and print the event log
+    #[arg(long)]
+    pub headless: bool,
+
+    /// Simulate the full experience without a window and write its audio to FILE (WAV)
+    #[arg(long, value_name = "FILE", conflicts_with = "headless")]
+    pub render_audio: Option<PathBuf>,
+
+    /// Sample rate for --render-audio
+    #[arg(
+        long,
+        value_name = "HZ",
+        requires = "render_audio",
+        default_value_t = audio::DEFAULT_RENDER_SAMPLE_RATE,
+        value_parser = parse_sample_rate
+    )]
+    pub sample_rate: u32,
+
+    /// Also write one WAV per bus (ambiance, events, each traveler) next to --render-audio
+    #[arg(long, requires = "render_audio")]
+    pub stems: bool,
+
+    /// Simulation step in seconds (--headless and --render-audio)
+    #[arg(long, value_name = "SECS")]
+    pub timestep: Option<f32>,
+
+    /// Open borderless fullscreen
+    #[arg(long, overrides_with = "windowed")]
+    pub fullscreen: bool,
+
+    /// Open in a window
+    #[arg(long, overrides_with = "fullscreen")]
+    pub windowed: bool,
+
+    /// Window size
+    #[arg(long, value_name = "WxH", value_parser = parse_resolution)]
+    pub resolution: Option<(f32, f32)>,
+
+    /// Monitor to open on (0 is the first)
+    #[arg(long, value_name = "INDEX")]
+    pub monitor: Option<usize>,
+
+    /// Start at this time in seconds as soon as loading finishes
+    #[arg(long, value_name = "SECS")]
+    pub start: Option<f32>,
+
+    /// Clock speed, 0.1 to 10 (1 is real time)
+    #[arg(long, value_name = "SCALE", default_value_t = 1.0, value_parser = parse_time_scale)]
+    pub time_scale: f32,
+
+    /// Seed for the experience RNG (random by default, 0 when --headless;
+    /// --sync-follow uses the leader's)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Run without audio
+    #[arg(long)]
+    pub mute: bool,
+
+    /// Audio output device by (part of) its name, or "null" for none [default: settings file]
+    #[arg(long, value_name = "NAME")]
+    pub audio_device: Option<String>,
+
+    /// Write the mix to FILE (WAV) when no audio device is available
+    #[arg(long, value_name = "FILE")]
+    pub audio_file: Option<PathBuf>,
+
+    /// Print the audio output device names and exit
+    #[arg(long)]
+    pub list_audio_devices: bool,
+
+    /// Rendering quality: low, medium or high (default from the settings file)
+    #[arg(long, value_name = "PRESET")]
+    pub quality: Option<core::QualityPreset>,
+
+    /// Keep the quality preset even when frames are slow
+    #[arg(long)]
+    pub no_adaptive_quality: bool,
+
+    /// Keep the cursor visible during the run
+    #[arg(long)]
+    pub show_cursor: bool,
+
+    /// Settings file [default: $XDG_CONFIG_HOME/lightwatch/settings.ron]
+    #[arg(long, value_name = "FILE")]
+    pub settings: Option<PathBuf>,
+
+    /// Ignore the settings file and don't save menu changes
+    #[arg(long, conflicts_with = "settings")]
+    pub no_settings: bool,
+
+    /// Log filter, e.g. "lightwatch=debug,wgpu=warn" (overrides RUST_LOG)
+    #[arg(long, value_name = "FILTER")]
+    pub log_level: Option<String>,
+
+    /// Log line format
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t = LogFormat::Full)]
+    pub log_format: LogFormat,
+
+    /// Write wide events to FILE as JSON Lines
+    #[arg(long, value_name = "FILE")]
+    pub events: Option<PathBuf>,
+
+    /// Pretty-print wide events to stdout
+    #[arg(long)]
+    pub events_stdout: bool,
+
+    /// Write per-phase frame-time percentiles to FILE when the run ends (.csv or JSON)
+    #[arg(long, value_name = "FILE")]
+    pub perf_report: Option<PathBuf>,
+
+    /// Replay a recorded --events log and report differences
+    #[arg(long, value_name = "FILE")]
+    pub replay: Option<PathBuf>,
+
+    /// Installation mode: restart SECS after the end (default 10)
+    #[arg(long = "loop", value_name = "SECS", num_args = 0..=1)]
+    pub looping: Option<Option<f32>>,
+
+    /// Accept OSC commands (/lightwatch/start, /seek, /speed, ...) on ADDR, e.g. 0.0.0.0:9000
+    #[arg(long, value_name = "ADDR")]
+    pub osc_listen: Option<SocketAddr>,
+
+    /// Send phase and moment OSC telemetry to ADDR (repeatable)
+    #[arg(long, value_name = "ADDR")]
+    pub osc_send: Vec<SocketAddr>,
+
+    /// Lead synchronised playback, sending the clock to ADDR (repeatable, broadcast allowed)
+    #[arg(long, value_name = "ADDR", conflicts_with = "sync_follow")]
+    pub sync_lead: Vec<SocketAddr>,
+
+    /// Follow a leader sending to ADDR, e.g. 0.0.0.0:9100 (takes the leader's seed)
+    #[arg(long, value_name = "ADDR")]
+    pub sync_follow: Option<SocketAddr>,
+
+    /// Turn this screen's view by DEG degrees (positive is left), for panoramas
+    #[arg(long, value_name = "DEG", default_value_t = 0.0, allow_negative_numbers = true)]
+    pub view_yaw: f32,
+
+    /// Tilt this screen's view by DEG degrees (positive is up)
+    #[arg(long, value_name = "DEG", default_value_t = 0.0, allow_negative_numbers = true)]
+    pub view_pitch: f32,
+}
+
+/// How log lines are laid out
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// One line per event with target
+    Full,
+    /// Shorter single lines
+    Compact,
+    /// Multi-line, for reading by eye
+    Pretty,
+}
+
+fn parse_resolution(value: &str) -> Result<(f32, f32), String> {
+    let (width, height) = value
+        .split_once(['x', 'X'])
+        .ok_or_else(|| format!("expected WIDTHxHEIGHT, got '{}'", value))?;
+    let parse = |side: &str| match side.trim().parse::<u32>() {
+        Ok(pixels) if pixels > 0 => Ok(pixels as f32),
+        _ => Err(format!("bad size '{}' in '{}'", side, value)),
+    };
+
+    Ok((parse(width)?, parse(height)?))
+}
+
+fn parse_time_scale(value: &str) -> Result<f32, String> {
+    match value.parse::<f32>() {
+        Ok(scale) if (0.1..=10.0).contains(&scale) => Ok(scale),
+        _ => Err(format!("time scale must be between 0.1 and 10, got '{}'", value)),
+    }
+}
+
+fn parse_sample_rate(value: &str) -> Result<u32, String> {
+    match value.parse::<u32>() {
+        Ok(rate) if (8000..=192000).contains(&rate) => Ok(rate),
+        _ => Err(format!("sample rate must be between 8000 and 192000, got '{}'", value)),
+    }
+}
+
+impl Cli {
+    /// Wide event sinks from --events and --events-stdout
+    pub fn sinks(&self) -> core::WideEventSinkConfig {
+        core::WideEventSinkConfig {
+            jsonl_path: self.events.clone(),
+            stdout: self.events_stdout,
+            ..default()
+        }
+    }
+
+    /// OSC remote control, if --osc-listen or --osc-send was given
+    pub fn remote(&self) -> Option<core::RemoteConfig> {
+        if self.osc_listen.is_none() && self.osc_send.is_empty() {
+            return None;
+        }
+        Some(core::RemoteConfig {
+            listen: self.osc_listen,
+            telemetry: self.osc_send.clone(),
+        })
+    }
+
+    /// Leader or follower sync, if --sync-lead or --sync-follow was given
+    pub fn sync(&self) -> Option<core::SyncConfig> {
+        if let Some(listen) = self.sync_follow {
+            return Some(core::SyncConfig::follower(listen));
+        }
+        if self.sync_lead.is_empty() {
+            return None;
+        }
+        Some(core::SyncConfig::leader(self.sync_lead.clone()))
+    }
+
+    /// Settings file to use, unless --no-settings
+    pub fn settings_path(&self) -> Option<PathBuf> {
+        if self.no_settings {
+            return None;
+        }
+        self.settings.clone().or_else(core::default_settings_path)
+    }
+
+    /// Settings for a windowed run, on top of the saved user settings
+    pub fn launch_config(&self, mut settings: core::UserSettings) -> core::LaunchConfig {
+        if self.fullscreen {
+            settings.fullscreen = true;
+        }
+        if self.windowed {
+            settings.fullscreen = false;
+        }
+        if let Some(quality) = self.quality {
+            settings.quality = quality;
+        }
+        if let Some(device) = &self.audio_device {
+            settings.audio_device = Some(device.clone());
+        }
+
+        let mut window = core::WindowSettings {
+            fullscreen: settings.fullscreen,
+            monitor: self.monitor,
+            ..default()
+        };
+        if let Some((width, height)) = self.resolution {
+            window.width = width;
+            window.height = height;
+        }
+
+        core::LaunchConfig {
+            window,
+            start_at: self.start,
+            time_scale: self.time_scale,
+            seed: self.seed,
+            muted: self.mute,
+            audio_file: self.audio_file.clone(),
+            settings,
+            settings_path: self.settings_path(),
+            show_cursor: self.show_cursor,
+            sinks: self.sinks(),
+            looping: self.looping.map(|idle| {
+                core::LoopConfig::looping(idle.unwrap_or(core::LoopConfig::default().idle_delay))
+            }),
+            remote: self.remote(),
+            sync: self.sync(),
+            view_offset: CameraViewOffset {
+                yaw: self.view_yaw.to_radians(),
+                pitch: self.view_pitch.to_radians(),
+            },
+            perf_report: self.perf_report.clone(),
+            adaptive_quality: !self.no_adaptive_quality,
+        }
+    }
+
+    /// Settings for a --headless run
+    pub fn headless_config(&self) -> core::HeadlessConfig {
+        let mut config = core::HeadlessConfig {
+            sinks: self.sinks(),
+            remote: self.remote(),
+            sync: self.sync(),
+            perf_report: self.perf_report.clone(),
+            ..default()
+        };
+        if let Some(seed) = self.seed {
+            config.seed = seed;
+        }
+        if let Some(timestep) = self.timestep {
+            config.timestep = timestep;
+        }
+        config
+    }
+
+    /// Settings for a --render-audio run, if requested
+    pub fn render_audio_config(&self) -> Option<audio::AudioRenderConfig> {
+        let path = self.render_audio.clone()?;
+        Some(audio::AudioRenderConfig {
+            headless: self.headless_config(),
+            path,
+            sample_rate: self.sample_rate,
+            stems: self.stems,
+        })
+    }
+
+    /// Install the tracing subscriber
+    ///
+    /// `--log-level` wins over `RUST_LOG`, which wins over `default_filter`.
+    pub fn init_tracing(&self, default_filter: &str, to_stderr: bool) {
+        let filter = match &self.log_level {
+            Some(filter) => EnvFilter::new(filter),
+            None => EnvFilter::try_from_default_env().unwrap_or_else(|_| default_filter.into()),
+        };
+        let writer = if to_stderr {
+            BoxMakeWriter::new(std::io::stderr)
+        } else {
+            BoxMakeWriter::new(std::io::stdout)
+        };
+
+        let layer = tracing_subscriber::fmt::layer()
+            .with_writer(writer)
+            .with_target(true)
+            .with_thread_ids(false)
+            .with_file(false)
+            .with_line_number(false);
+        let layer = match self.log_format {
+            LogFormat::Full => layer.boxed(),
+            LogFormat::Compact => layer.compact().boxed(),
+            LogFormat::Pretty => layer.pretty().boxed(),
+        };
+
+        tracing_subscriber::registry().with(layer).with(filter).init();
+    }
+}
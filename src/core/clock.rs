@@ -1,19 +1,24 @@
 //! Experience clock - the 143-second heartbeat of LIGHTWATCH
+//!
+//! Phase boundaries come from the `PhaseTable` resource; the clock keeps a
+//! copy, refreshed by `sync_phase_table` whenever the table changes.
 
 #![allow(dead_code)]
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use super::easing::smooth_step;
+use super::logging::WideEventLog;
+use super::phase_table::PhaseTable;
 
-/// The total duration of the LIGHTWATCH experience
+/// The total duration of the standard cut of LIGHTWATCH
 pub const EXPERIENCE_DURATION: f32 = 143.0;
 
 /// Duration of transition buffer at phase boundaries (seconds)
 pub const PHASE_TRANSITION_BUFFER: f32 = 0.5;
 
-/// Experience timeline phases
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+/// Experience timeline phases (times are for the standard cut)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum Phase {
     #[default]
     Signal,     // 0-2s: Detection
@@ -26,57 +31,6 @@ pub enum Phase {
 }
 
 impl Phase {
-    /// Get the phase for a given elapsed time
-    pub fn from_elapsed(elapsed: f32) -> Self {
-        match elapsed {
-            t if t < 2.0 => Phase::Signal,
-            t if t < 12.0 => Phase::Bang,
-            t if t < 27.0 => Phase::Awakening,
-            t if t < 57.0 => Phase::Discovery,
-            t if t < 87.0 => Phase::Connection,
-            t if t < EXPERIENCE_DURATION => Phase::Acceptance,
-            _ => Phase::Ended,
-        }
-    }
-
-    /// Get phase start time
-    pub fn start_time(&self) -> f32 {
-        match self {
-            Phase::Signal => 0.0,
-            Phase::Bang => 2.0,
-            Phase::Awakening => 12.0,
-            Phase::Discovery => 27.0,
-            Phase::Connection => 57.0,
-            Phase::Acceptance => 87.0,
-            Phase::Ended => EXPERIENCE_DURATION,
-        }
-    }
-
-    /// Get phase end time
-    pub fn end_time(&self) -> f32 {
-        match self {
-            Phase::Signal => 2.0,
-            Phase::Bang => 12.0,
-            Phase::Awakening => 27.0,
-            Phase::Discovery => 57.0,
-            Phase::Connection => 87.0,
-            Phase::Acceptance => EXPERIENCE_DURATION,
-            Phase::Ended => f32::INFINITY,
-        }
-    }
-
-    /// Get phase duration
-    pub fn duration(&self) -> f32 {
-        self.end_time() - self.start_time()
-    }
-
-    /// Get progress within this phase (0.0 to 1.0)
-    pub fn progress(&self, elapsed: f32) -> f32 {
-        let start = self.start_time();
-        let duration = self.duration();
-        ((elapsed - start) / duration).clamp(0.0, 1.0)
-    }
-
     /// Get display name
     pub fn name(&self) -> &'static str {
         match self {
@@ -89,40 +43,6 @@ impl Phase {
             Phase::Ended => "ended",
         }
     }
-
-    /// Get smooth entry factor (0.0 at start, 1.0 after buffer)
-    /// Use this to smoothly ramp up effects at phase start
-    pub fn entry_factor(&self, elapsed: f32) -> f32 {
-        let time_in_phase = elapsed - self.start_time();
-        if time_in_phase < 0.0 {
-            return 0.0;
-        }
-        let factor = (time_in_phase / PHASE_TRANSITION_BUFFER).clamp(0.0, 1.0);
-        smooth_step(factor)
-    }
-
-    /// Get smooth exit factor (1.0 at start, 0.0 at end after buffer)
-    /// Use this to smoothly ramp down effects before phase end
-    pub fn exit_factor(&self, elapsed: f32) -> f32 {
-        let time_until_end = self.end_time() - elapsed;
-        if time_until_end < 0.0 {
-            return 0.0;
-        }
-        let factor = (time_until_end / PHASE_TRANSITION_BUFFER).clamp(0.0, 1.0);
-        smooth_step(factor)
-    }
-
-    /// Check if we're in the entry buffer zone
-    pub fn is_entering(&self, elapsed: f32) -> bool {
-        let time_in_phase = elapsed - self.start_time();
-        time_in_phase >= 0.0 && time_in_phase < PHASE_TRANSITION_BUFFER
-    }
-
-    /// Check if we're in the exit buffer zone
-    pub fn is_exiting(&self, elapsed: f32) -> bool {
-        let time_until_end = self.end_time() - elapsed;
-        time_until_end >= 0.0 && time_until_end < PHASE_TRANSITION_BUFFER
-    }
 }
 
 /// The master clock for the experience
@@ -140,6 +60,8 @@ pub struct ExperienceClock {
     previous_phase: Phase,
     /// Has the experience started?
     started: bool,
+    /// Phase boundaries in use (copy of the `PhaseTable` resource)
+    table: PhaseTable,
 }
 
 impl Default for ExperienceClock {
@@ -151,6 +73,7 @@ impl Default for ExperienceClock {
             current_phase: Phase::Signal,
             previous_phase: Phase::Signal,
             started: false,
+            table: PhaseTable::default(),
         }
     }
 }
@@ -166,14 +89,12 @@ impl ExperienceClock {
         }
     }
 
-    /// Pause the clock (dev only)
-    #[cfg(debug_assertions)]
+    /// Pause the clock
     pub fn pause(&mut self) {
         self.running = false;
     }
 
-    /// Resume the clock (dev only)
-    #[cfg(debug_assertions)]
+    /// Resume the clock
     pub fn resume(&mut self) {
         self.running = true;
     }
@@ -199,14 +120,31 @@ impl ExperienceClock {
         self.elapsed
     }
 
+    /// Get total duration of the current cut
+    pub fn duration(&self) -> f32 {
+        self.table.duration()
+    }
+
+    /// Phase table the clock is running on
+    pub fn phase_table(&self) -> &PhaseTable {
+        &self.table
+    }
+
+    /// Switch to a new phase table, keeping the elapsed time
+    pub fn set_phase_table(&mut self, table: PhaseTable) {
+        self.table = table;
+        self.current_phase = self.table.phase_at(self.elapsed);
+        self.previous_phase = self.current_phase;
+    }
+
     /// Get remaining time
     pub fn remaining(&self) -> f32 {
-        (EXPERIENCE_DURATION - self.elapsed).max(0.0)
+        (self.duration() - self.elapsed).max(0.0)
     }
 
     /// Get progress through entire experience (0.0 to 1.0)
     pub fn progress(&self) -> f32 {
-        (self.elapsed / EXPERIENCE_DURATION).clamp(0.0, 1.0)
+        (self.elapsed / self.duration()).clamp(0.0, 1.0)
     }
 
     /// Get current phase
@@ -216,7 +154,7 @@ impl ExperienceClock {
 
     /// Get progress within current phase (0.0 to 1.0)
     pub fn phase_progress(&self) -> f32 {
-        self.current_phase.progress(self.elapsed)
+        self.table.progress(self.current_phase, self.elapsed)
     }
 
     /// Did we just transition to a new phase?
@@ -231,22 +169,22 @@ impl ExperienceClock {
 
     /// Get smooth entry factor for current phase (0.0 at start, 1.0 after buffer)
     pub fn phase_entry_factor(&self) -> f32 {
-        self.current_phase.entry_factor(self.elapsed)
+        self.table.entry_factor(self.current_phase, self.elapsed)
     }
 
     /// Get smooth exit factor for current phase (1.0 normally, 0.0 at end)
     pub fn phase_exit_factor(&self) -> f32 {
-        self.current_phase.exit_factor(self.elapsed)
+        self.table.exit_factor(self.current_phase, self.elapsed)
     }
 
     /// Are we in the entry transition zone?
     pub fn is_entering_phase(&self) -> bool {
-        self.current_phase.is_entering(self.elapsed)
+        self.table.is_entering(self.current_phase, self.elapsed)
     }
 
     /// Are we in the exit transition zone?
     pub fn is_exiting_phase(&self) -> bool {
-        self.current_phase.is_exiting(self.elapsed)
+        self.table.is_exiting(self.current_phase, self.elapsed)
     }
 
     /// Are we in any transition zone?
@@ -254,8 +192,7 @@ impl ExperienceClock {
         self.is_entering_phase() || self.is_exiting_phase()
     }
 
-    /// Set time scale (dev only)
-    #[cfg(debug_assertions)]
+    /// Set time scale
     pub fn set_time_scale(&mut self, scale: f32) {
         self.time_scale = scale.clamp(0.1, 10.0);
     }
@@ -265,17 +202,22 @@ impl ExperienceClock {
         self.time_scale
     }
 
-    /// Jump to specific time (dev only)
-    #[cfg(debug_assertions)]
-    pub fn jump_to(&mut self, time: f32) {
-        self.elapsed = time.clamp(0.0, EXPERIENCE_DURATION + 1.0);
-        self.update_phase();
-        info!(
-            target: "lightwatch::clock",
-            "Jumped to {:.2}s ({})",
-            self.elapsed,
-            self.current_phase.name()
-        );
+    /// Stop at 0 as if the experience had never started (keeps the phase table)
+    pub fn reset(&mut self) {
+        self.started = false;
+        self.running = false;
+        self.elapsed = 0.0;
+        self.current_phase = Phase::Signal;
+        self.previous_phase = Phase::Signal;
+    }
+
+    /// Rewind to 0 and run, even after the end (used by seek)
+    pub fn restart(&mut self) {
+        self.started = true;
+        self.running = true;
+        self.elapsed = 0.0;
+        self.current_phase = Phase::Signal;
+        self.previous_phase = Phase::Signal;
     }
 
     /// Update the clock - call every frame
@@ -288,8 +230,8 @@ impl ExperienceClock {
         self.elapsed += delta * self.time_scale;
 
         // Clamp at end
-        if self.elapsed >= EXPERIENCE_DURATION {
-            self.elapsed = EXPERIENCE_DURATION;
+        if self.elapsed >= self.duration() {
+            self.elapsed = self.duration();
             self.running = false;
         }
 
@@ -299,49 +241,39 @@ impl ExperienceClock {
 
     fn update_phase(&mut self) {
         self.previous_phase = self.current_phase;
-        self.current_phase = Phase::from_elapsed(self.elapsed);
+        self.current_phase = self.table.phase_at(self.elapsed);
     }
 }
 
+/// Hand a changed `PhaseTable` to the clock
+pub fn sync_phase_table(table: Res<PhaseTable>, mut clock: ResMut<ExperienceClock>) {
+    clock.set_phase_table(table.clone());
+}
+
 /// System to update the experience clock
 pub fn update_clock(
     mut clock: ResMut<ExperienceClock>,
     time: Res<Time>,
     time_control: Res<super::TimeControl>,
 ) {
-    // Apply time control overrides (dev only)
-    #[cfg(debug_assertions)]
-    {
-        if clock.has_started() && time_control.paused != !clock.is_running() {
-            if time_control.paused {
-                clock.pause();
-            } else {
-                clock.resume();
-            }
-        }
-
-        if time_control.speed != clock.time_scale() {
-            clock.set_time_scale(time_control.speed);
-        }
-
-        if let Some(target_time) = time_control.scrub_position {
-            clock.jump_to(target_time);
+    // Pause and speed can also come from launch flags or a show controller,
+    // so they apply in all builds
+    if clock.has_started() && time_control.paused == clock.is_running() {
+        if time_control.paused {
+            clock.pause();
+        } else {
+            clock.resume();
         }
     }
 
-    // Suppress unused warning in release
-    let _ = &time_control;
+    if time_control.speed != clock.time_scale() {
+        clock.set_time_scale(time_control.speed);
+    }
 
     // Tick the clock
     clock.tick(time.delta_seconds());
 }
 
-/// Clear scrub position after applying
-#[cfg(debug_assertions)]
-pub fn clear_scrub_position(mut time_control: ResMut<super::TimeControl>) {
-    time_control.scrub_position = None;
-}
-
 /// Event fired when phase changes
 #[derive(Event, Debug, Clone)]
 pub struct PhaseChangedEvent {
@@ -353,6 +285,7 @@ pub struct PhaseChangedEvent {
 /// System to emit phase change events
 pub fn emit_phase_changes(
     clock: Res<ExperienceClock>,
+    log: Res<WideEventLog>,
     mut events: EventWriter<PhaseChangedEvent>,
 ) {
     if clock.phase_just_changed() {
@@ -368,6 +301,6 @@ pub fn emit_phase_changes(
             .with_str("from", event.from.name())
             .with_str("to", event.to.name())
             .with_f32("elapsed", event.elapsed)
-            .emit(event.elapsed);
+            .emit(&log, event.elapsed);
     }
 }
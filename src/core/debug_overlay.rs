@@ -1,8 +1,33 @@
-//! Debug overlay - FPS, time, phase display
+//! Debug overlay - FPS, time, phase display and the timeline HUD
+//!
+//! F3 toggles it. Besides the status text it shows the whole cut as a bar with
+//! phase segments, a tick per moment (green once triggered) and the playhead;
+//! clicking the bar seeks there. A table of traveler state and a panel of the
+//! latest wide events sit alongside. The cursor stays visible while it is open.
 
-use super::clock::{ExperienceClock, EXPERIENCE_DURATION};
 use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
 use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+use bevy::window::CursorGrabMode;
+
+use super::clock::{ExperienceClock, Phase};
+use super::event_sinks::RecentWideEvents;
+use super::input::{hide_cursor_for_run, InputConfig};
+use super::loading::EmbeddedFont;
+use super::logging::WideValue;
+use super::phase_controller::PhaseController;
+use super::phase_table::PhaseTable;
+use super::seek::ExperienceController;
+use super::state::ExperienceState;
+use crate::travelers::{Traveler, TravelerGrief, TravelerPulse, TravelerState, TravelerVisibility};
+
+/// Wide events listed in the events panel
+const EVENT_LINES: usize = 14;
+
+const OVERLAY_GREEN: Color = Color::srgb(0.0, 1.0, 0.5);
+const PANEL_BACKGROUND: Color = Color::srgba(0.0, 0.0, 0.0, 0.7);
+const TICK_TRIGGERED: Color = Color::srgb(0.3, 0.95, 0.5);
+const TICK_PENDING: Color = Color::srgba(0.85, 0.85, 0.85, 0.6);
 
 /// Marker for debug overlay text
 #[derive(Component)]
@@ -12,6 +37,30 @@ pub struct DebugOverlayText;
 #[derive(Component)]
 pub struct DebugOverlay;
 
+/// Traveler table text
+#[derive(Component)]
+pub struct DebugTravelerTable;
+
+/// Recent wide events text
+#[derive(Component)]
+pub struct DebugEventPanel;
+
+/// The clickable timeline bar
+#[derive(Component)]
+pub struct TimelineBar;
+
+/// One phase's stretch of the bar
+#[derive(Component)]
+pub struct TimelineSegment;
+
+/// Tick for the moment at this index in `PhaseController::moments`
+#[derive(Component)]
+pub struct TimelineTick(pub usize);
+
+/// Current time marker
+#[derive(Component)]
+pub struct TimelinePlayhead;
+
 /// Debug overlay state
 #[derive(Resource)]
 pub struct DebugOverlayState {
@@ -30,44 +79,274 @@ impl Default for DebugOverlayState {
     }
 }
 
+fn overlay_visibility(visible: bool) -> Visibility {
+    if visible {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    }
+}
+
+/// Bar colour for each phase
+fn phase_color(phase: Phase) -> Color {
+    match phase {
+        Phase::Signal => Color::srgba(0.35, 0.35, 0.45, 0.8),
+        Phase::Bang => Color::srgba(0.95, 0.75, 0.35, 0.8),
+        Phase::Awakening => Color::srgba(0.45, 0.6, 0.85, 0.8),
+        Phase::Discovery => Color::srgba(0.3, 0.75, 0.7, 0.8),
+        Phase::Connection => Color::srgba(0.9, 0.55, 0.35, 0.8),
+        Phase::Acceptance => Color::srgba(0.5, 0.4, 0.65, 0.8),
+        Phase::Ended => Color::srgba(0.2, 0.2, 0.2, 0.8),
+    }
+}
+
 /// Spawn the debug overlay UI
-pub fn spawn_debug_overlay(mut commands: Commands) {
+pub fn spawn_debug_overlay(
+    mut commands: Commands,
+    state: Res<DebugOverlayState>,
+    font: Option<Res<EmbeddedFont>>,
+) {
+    let style = |size: f32, color: Color| TextStyle {
+        font: font.as_ref().map(|font| font.0.clone()).unwrap_or_default(),
+        font_size: size,
+        color,
+    };
+    let panel = |top: Val, left: Val, right: Val| NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            top,
+            left,
+            right,
+            padding: UiRect::all(Val::Px(8.0)),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(8.0),
+            ..default()
+        },
+        background_color: BackgroundColor(PANEL_BACKGROUND),
+        ..default()
+    };
+
     commands
         .spawn((
             NodeBundle {
                 style: Style {
                     position_type: PositionType::Absolute,
-                    top: Val::Px(10.0),
-                    left: Val::Px(10.0),
-                    padding: UiRect::all(Val::Px(8.0)),
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
                     ..default()
                 },
-                background_color: BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+                visibility: overlay_visibility(state.visible),
                 ..default()
             },
             DebugOverlay,
         ))
-        .with_children(|parent| {
+        .with_children(|root| {
+            // Status and traveler table, top left
+            root.spawn(panel(Val::Px(10.0), Val::Px(10.0), Val::Auto))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section("LIGHTWATCH DEBUG", style(14.0, OVERLAY_GREEN)),
+                        DebugOverlayText,
+                    ));
+                    parent.spawn((
+                        TextBundle::from_section("", style(12.0, OVERLAY_GREEN)),
+                        DebugTravelerTable,
+                    ));
+                });
+
+            // Recent wide events, top right
+            root.spawn(panel(Val::Px(10.0), Val::Auto, Val::Px(10.0)))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section("", style(11.0, OVERLAY_GREEN)),
+                        DebugEventPanel,
+                    ));
+                });
+
+            // Timeline, along the bottom
+            root.spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(5.0),
+                    right: Val::Percent(5.0),
+                    bottom: Val::Px(24.0),
+                    height: Val::Px(16.0),
+                    ..default()
+                },
+                background_color: BackgroundColor(PANEL_BACKGROUND),
+                ..default()
+            })
+            .insert((TimelineBar, Interaction::None, RelativeCursorPosition::default()));
+        });
+}
+
+/// Rebuild the bar's phase segments and moment ticks when the cut changes
+pub fn build_timeline_bar(
+    mut commands: Commands,
+    table: Res<PhaseTable>,
+    controller: Res<PhaseController>,
+    bars: Query<Entity, With<TimelineBar>>,
+    mut built: Local<Option<(PhaseTable, usize)>>,
+) {
+    let current = (table.clone(), controller.moments.len());
+    if built.as_ref() == Some(&current) {
+        return;
+    }
+    let Ok(bar) = bars.get_single() else {
+        return;
+    };
+    *built = Some(current);
+
+    let duration = table.duration().max(f32::EPSILON);
+    let percent = |time: f32| Val::Percent((time / duration).clamp(0.0, 1.0) * 100.0);
+
+    commands.entity(bar).despawn_descendants().with_children(|parent| {
+        for span in &table.phases {
+            let end = table.end(span.phase);
             parent.spawn((
-                TextBundle::from_section(
-                    "LIGHTWATCH DEBUG",
-                    TextStyle {
-                        font_size: 14.0,
-                        color: Color::srgb(0.0, 1.0, 0.5),
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: percent(span.start),
+                        width: percent(end - span.start),
+                        top: Val::Px(5.0),
+                        height: Val::Px(6.0),
                         ..default()
                     },
-                ),
-                DebugOverlayText,
+                    background_color: BackgroundColor(phase_color(span.phase)),
+                    ..default()
+                },
+                TimelineSegment,
             ));
-        });
+        }
+
+        for index in 0..controller.moments.len() {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        width: Val::Px(1.0),
+                        top: Val::Px(0.0),
+                        height: Val::Px(5.0),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(TICK_PENDING),
+                    ..default()
+                },
+                TimelineTick(index),
+            ));
+        }
+
+        parent.spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Px(2.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::WHITE),
+                ..default()
+            },
+            TimelinePlayhead,
+        ));
+    });
 }
 
-/// Update debug overlay text
-pub fn update_debug_overlay(
+/// Move the playhead and place and colour the moment ticks
+pub fn update_timeline_bar(
     clock: Res<ExperienceClock>,
+    controller: Res<PhaseController>,
     state: Res<DebugOverlayState>,
+    mut ticks: Query<(&TimelineTick, &mut Style, &mut BackgroundColor), Without<TimelinePlayhead>>,
+    mut playheads: Query<&mut Style, With<TimelinePlayhead>>,
+) {
+    if !state.visible {
+        return;
+    }
+
+    let duration = clock.duration().max(f32::EPSILON);
+    let percent = |time: f32| Val::Percent((time / duration).clamp(0.0, 1.0) * 100.0);
+
+    for mut style in playheads.iter_mut() {
+        style.left = percent(clock.elapsed());
+    }
+
+    let table = controller.schedule_table(&clock);
+    for (tick, mut style, mut color) in ticks.iter_mut() {
+        let Some(moment) = controller.moments.get(tick.0) else {
+            continue;
+        };
+        // Relative moments have no place on the bar until their anchor happens
+        match moment.due(&table) {
+            Some(due) => {
+                style.display = Display::Flex;
+                style.left = percent(due);
+            }
+            None => style.display = Display::None,
+        }
+        color.0 = if moment.triggered {
+            TICK_TRIGGERED
+        } else {
+            TICK_PENDING
+        };
+    }
+}
+
+/// Seek to where the timeline bar was clicked
+pub fn seek_from_timeline(
+    mouse: Res<ButtonInput<MouseButton>>,
+    state: Res<DebugOverlayState>,
+    clock: Res<ExperienceClock>,
+    bars: Query<&RelativeCursorPosition, With<TimelineBar>>,
+    mut controller: ResMut<ExperienceController>,
+) {
+    if !state.visible || !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    for cursor in bars.iter() {
+        if !cursor.mouse_over() {
+            continue;
+        }
+        if let Some(position) = cursor.normalized {
+            let target = position.x.clamp(0.0, 1.0) * clock.duration();
+            info!(target: "lightwatch::debug", "Timeline seek to {:.2}s", target);
+            controller.seek(target);
+        }
+    }
+}
+
+/// Compact value for the events panel
+fn short_value(value: &WideValue) -> String {
+    match value {
+        WideValue::String(text) => text.clone(),
+        WideValue::Float(number) => format!("{:.2}", number),
+        WideValue::Int(number) => number.to_string(),
+        WideValue::Bool(flag) => flag.to_string(),
+        WideValue::Vec3(vector) => format!("({:.1}, {:.1}, {:.1})", vector.x, vector.y, vector.z),
+    }
+}
+
+/// Update debug overlay text, traveler table and events panel
+#[allow(clippy::type_complexity)]
+pub fn update_debug_overlay(
+    clock: Res<ExperienceClock>,
+    mut state: ResMut<DebugOverlayState>,
     diagnostics: Res<DiagnosticsStore>,
-    mut query: Query<&mut Text, With<DebugOverlayText>>,
+    recent: Res<RecentWideEvents>,
+    travelers: Query<(
+        &Traveler,
+        &TravelerState,
+        &TravelerVisibility,
+        &TravelerPulse,
+        Option<&TravelerGrief>,
+    )>,
+    mut texts: ParamSet<(
+        Query<&mut Text, With<DebugOverlayText>>,
+        Query<&mut Text, With<DebugTravelerTable>>,
+        Query<&mut Text, With<DebugEventPanel>>,
+    )>,
 ) {
     if !state.visible {
         return;
@@ -78,41 +357,99 @@ pub fn update_debug_overlay(
         .and_then(|d| d.smoothed())
         .unwrap_or(0.0);
 
-    for mut text in query.iter_mut() {
-        text.sections[0].value = format!(
-            "LIGHTWATCH DEBUG\n\
-             FPS: {:.0}\n\
-             Time: {:.2}s / {:.0}s\n\
-             Phase: {} ({:.0}%)\n\
-             Running: {}\n\
-             Travelers: {}\n\
-             Particles: {}",
-            fps,
-            clock.elapsed(),
-            EXPERIENCE_DURATION,
-            clock.phase().name(),
-            clock.phase_progress() * 100.0,
-            if clock.is_running() { "yes" } else { "PAUSED" },
-            state.traveler_count,
-            state.particle_count,
-        );
+    let mut rows: Vec<_> = travelers.iter().collect();
+    rows.sort_by_key(|(traveler, ..)| traveler.id as usize);
+    state.traveler_count = rows.len();
+
+    let status = format!(
+        "LIGHTWATCH DEBUG\n\
+         FPS: {:.0}\n\
+         Time: {:.2}s / {:.0}s\n\
+         Phase: {} ({:.0}%)\n\
+         Running: {}\n\
+         Travelers: {}\n\
+         Particles: {}",
+        fps,
+        clock.elapsed(),
+        clock.duration(),
+        clock.phase().name(),
+        clock.phase_progress() * 100.0,
+        if clock.is_running() { "yes" } else { "PAUSED" },
+        state.traveler_count,
+        state.particle_count,
+    );
+    for mut text in texts.p0().iter_mut() {
+        text.sections[0].value.clone_from(&status);
+    }
+
+    let mut table = String::from("traveler   state     opacity pulse grief");
+    for (traveler, traveler_state, visibility, pulse, grief) in rows {
+        table.push_str(&format!(
+            "\n{:<10} {:<9} {:>7.2} {:>5.2} {:>5.2}",
+            traveler.id.name(),
+            format!("{:?}", traveler_state),
+            visibility.opacity,
+            pulse.phase,
+            grief.map_or(0.0, |grief| grief.intensity),
+        ));
+    }
+    for mut text in texts.p1().iter_mut() {
+        text.sections[0].value.clone_from(&table);
+    }
+
+    // Newest first
+    let events: Vec<String> = recent
+        .0
+        .latest(EVENT_LINES)
+        .iter()
+        .rev()
+        .map(|event| {
+            let fields: Vec<String> = event
+                .context
+                .iter()
+                .take(3)
+                .map(|(key, value)| format!("{}={}", key, short_value(value)))
+                .collect();
+            format!("[{:>6.2}s] {} {}", event.elapsed_secs, event.name, fields.join(" "))
+        })
+        .collect();
+    for mut text in texts.p2().iter_mut() {
+        text.sections[0].value = events.join("\n");
     }
 }
 
 /// Toggle debug overlay visibility with F3
 pub fn toggle_debug_overlay(
     keyboard: Res<ButtonInput<KeyCode>>,
+    experience: Res<State<ExperienceState>>,
+    input: Res<InputConfig>,
     mut state: ResMut<DebugOverlayState>,
     mut query: Query<&mut Visibility, With<DebugOverlay>>,
+    mut windows: Query<&mut Window>,
 ) {
     if keyboard.just_pressed(KeyCode::F3) {
         state.visible = !state.visible;
         for mut vis in query.iter_mut() {
-            *vis = if state.visible {
-                Visibility::Visible
-            } else {
-                Visibility::Hidden
-            };
+            *vis = overlay_visibility(state.visible);
+        }
+
+        // Hand the cursor back to the run when the overlay closes
+        if !state.visible && *experience.get() == ExperienceState::Running {
+            hide_cursor_for_run(&input, &mut windows);
+        }
+    }
+}
+
+/// Keep the cursor free while the overlay is open, so the bar can be clicked
+pub fn show_cursor_for_overlay(state: Res<DebugOverlayState>, mut windows: Query<&mut Window>) {
+    if !state.visible {
+        return;
+    }
+
+    for mut window in windows.iter_mut() {
+        if !window.cursor.visible || window.cursor.grab_mode != CursorGrabMode::None {
+            window.cursor.visible = true;
+            window.cursor.grab_mode = CursorGrabMode::None;
         }
     }
 }
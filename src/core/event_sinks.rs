@@ -0,0 +1,369 @@
+//! Wide event sinks - where emitted wide events end up
+//!
+//! `WideEvent::emit` queues events in the app's `WideEventLog`;
+//! `write_wide_events` hands them to every sink in `WideEventSinks` once per
+//! frame. Built-in sinks write JSON Lines for post-show analysis, keep a
+//! bounded ring buffer for the debug overlay and tests, or pretty-print to
+//! stdout.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+use super::logging::{WideEvent, WideEventLog, WideValue};
+
+/// Default number of events kept by `RecentWideEvents`
+pub const DEFAULT_RING_CAPACITY: usize = 256;
+
+/// Destination for wide events
+pub trait WideEventSink: Send + Sync {
+    /// Record one event
+    fn write(&mut self, event: &WideEvent);
+
+    /// Push buffered output through (called after each batch)
+    fn flush(&mut self) {}
+}
+
+/// All registered sinks
+#[derive(Resource, Default)]
+pub struct WideEventSinks {
+    sinks: Vec<Box<dyn WideEventSink>>,
+}
+
+impl WideEventSinks {
+    /// Register a sink; it receives every event emitted from now on
+    pub fn add(&mut self, sink: impl WideEventSink + 'static) {
+        self.sinks.push(Box::new(sink));
+    }
+
+    pub fn len(&self) -> usize {
+        self.sinks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    /// Hand a batch of events to every sink
+    pub fn write_all(&mut self, events: &[WideEvent]) {
+        if events.is_empty() {
+            return;
+        }
+
+        for sink in self.sinks.iter_mut() {
+            for event in events {
+                sink.write(event);
+            }
+            sink.flush();
+        }
+    }
+}
+
+/// One JSON object per line
+pub struct JsonLinesSink {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    failed: bool,
+}
+
+impl JsonLinesSink {
+    /// Create (or truncate) the file at `path`
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = File::create(&path)?;
+
+        Ok(Self {
+            path,
+            writer: BufWriter::new(file),
+            failed: false,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn report(&mut self, err: impl std::fmt::Display) {
+        // One error per file is enough; a full disk would otherwise log every frame
+        if !self.failed {
+            self.failed = true;
+            error!(
+                target: "lightwatch::events",
+                "Failed to write wide events to {}: {}",
+                self.path.display(),
+                err
+            );
+        }
+    }
+}
+
+impl WideEventSink for JsonLinesSink {
+    fn write(&mut self, event: &WideEvent) {
+        let result = serde_json::to_writer(&mut self.writer, event)
+            .map_err(io::Error::from)
+            .and_then(|_| self.writer.write_all(b"\n"));
+
+        if let Err(err) = result {
+            self.report(err);
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Err(err) = self.writer.flush() {
+            self.report(err);
+        }
+    }
+}
+
+/// Read a JSON Lines file written by `JsonLinesSink`
+pub fn read_json_lines(path: impl AsRef<Path>) -> io::Result<Vec<WideEvent>> {
+    let text = std::fs::read_to_string(path)?;
+
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: {}", index + 1, err),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Bounded in-memory history; clones share the same buffer
+#[derive(Clone)]
+pub struct RingBufferSink {
+    events: Arc<Mutex<VecDeque<WideEvent>>>,
+    capacity: usize,
+}
+
+impl RingBufferSink {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            events: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Every buffered event, oldest first
+    pub fn events(&self) -> Vec<WideEvent> {
+        self.events
+            .lock()
+            .map(|events| events.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// The newest `count` events, oldest first
+    pub fn latest(&self, count: usize) -> Vec<WideEvent> {
+        self.events
+            .lock()
+            .map(|events| {
+                let skip = events.len().saturating_sub(count);
+                events.iter().skip(skip).cloned().collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Buffered events with the given name, oldest first
+    pub fn named(&self, name: &str) -> Vec<WideEvent> {
+        self.events
+            .lock()
+            .map(|events| events.iter().filter(|e| e.name == name).cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.lock().map(|events| events.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut events) = self.events.lock() {
+            events.clear();
+        }
+    }
+}
+
+impl WideEventSink for RingBufferSink {
+    fn write(&mut self, event: &WideEvent) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if let Ok(mut events) = self.events.lock() {
+            if events.len() == self.capacity {
+                events.pop_front();
+            }
+            events.push_back(event.clone());
+        }
+    }
+}
+
+/// Human-readable output, one field per line
+pub struct StdoutSink;
+
+impl WideEventSink for StdoutSink {
+    fn write(&mut self, event: &WideEvent) {
+        let mut out = format!("[{:>7.2}s] {}", event.elapsed_secs, event.name);
+        for (key, value) in &event.context {
+            let value = match value {
+                WideValue::String(v) => v.clone(),
+                WideValue::Float(v) => format!("{:.3}", v),
+                WideValue::Int(v) => v.to_string(),
+                WideValue::Bool(v) => v.to_string(),
+                WideValue::Vec3(v) => format!("({:.3}, {:.3}, {:.3})", v.x, v.y, v.z),
+            };
+            out.push_str(&format!("\n           {:<14} {}", key, value));
+        }
+        println!("{}", out);
+    }
+}
+
+/// Which built-in sinks to open at startup
+///
+/// Insert before `LightwatchPlugin` to change the defaults.
+#[derive(Resource, Debug, Clone)]
+pub struct WideEventSinkConfig {
+    /// Write every event as JSON Lines to this file
+    pub jsonl_path: Option<PathBuf>,
+    /// Pretty-print every event to stdout
+    pub stdout: bool,
+    /// Size of the `RecentWideEvents` ring buffer
+    pub ring_capacity: usize,
+}
+
+impl Default for WideEventSinkConfig {
+    fn default() -> Self {
+        Self {
+            jsonl_path: None,
+            stdout: false,
+            ring_capacity: DEFAULT_RING_CAPACITY,
+        }
+    }
+}
+
+/// The always-on ring buffer, for the debug overlay and tests
+#[derive(Resource, Clone)]
+pub struct RecentWideEvents(pub RingBufferSink);
+
+/// Hand queued events to the sinks
+pub fn write_wide_events(log: Res<WideEventLog>, mut sinks: ResMut<WideEventSinks>) {
+    let events = log.take_pending();
+    sinks.write_all(&events);
+}
+
+/// Plugin that opens the configured sinks and feeds them every frame
+pub struct WideEventSinkPlugin;
+
+impl Plugin for WideEventSinkPlugin {
+    fn build(&self, app: &mut App) {
+        let config = app
+            .world()
+            .get_resource::<WideEventSinkConfig>()
+            .cloned()
+            .unwrap_or_default();
+
+        let ring = RingBufferSink::new(config.ring_capacity);
+        let mut sinks = WideEventSinks::default();
+        sinks.add(ring.clone());
+
+        if let Some(path) = &config.jsonl_path {
+            match JsonLinesSink::create(path) {
+                Ok(sink) => {
+                    info!(
+                        target: "lightwatch::events",
+                        "Writing wide events to {}",
+                        sink.path().display()
+                    );
+                    sinks.add(sink);
+                }
+                Err(err) => error!(
+                    target: "lightwatch::events",
+                    "Cannot open {} for wide events: {}",
+                    path.display(),
+                    err
+                ),
+            }
+        }
+
+        if config.stdout {
+            sinks.add(StdoutSink);
+        }
+
+        app.init_resource::<WideEventLog>();
+        app.world().resource::<WideEventLog>().enable_sinks();
+
+        app.insert_resource(config)
+            .insert_resource(sinks)
+            .insert_resource(RecentWideEvents(ring))
+            .add_systems(Last, write_wide_events);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_lines_read_back_every_value_type() {
+        let path = std::env::temp_dir()
+            .join(format!("lightwatch-sink-{}.jsonl", std::process::id()));
+        let written = vec![
+            WideEvent::new("every_type")
+                .with_str("text", "signal detected")
+                .with_f32("float", 0.25)
+                .with_i64("int", -42)
+                .with_bool("flag", true)
+                .with_vec3("position", Vec3::new(1.0, -2.5, 3.0)),
+            WideEvent::new("not_finite")
+                .with_f32("nan", f32::NAN)
+                .with_f32("inf", f32::INFINITY)
+                .with_f32("neg_inf", f32::NEG_INFINITY)
+                .with_vec3("position", Vec3::new(f32::NAN, f32::INFINITY, 0.5)),
+        ];
+
+        let mut sink = JsonLinesSink::create(&path).unwrap();
+        for event in &written {
+            sink.write(event);
+        }
+        sink.flush();
+        let read = read_json_lines(&path);
+        let _ = std::fs::remove_file(&path);
+        let read = read.unwrap();
+
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[0].name, "every_type");
+        assert_eq!(read[0].context, written[0].context);
+
+        // NaN never equals itself, so compare by kind
+        let float = |key| match read[1].get(key) {
+            Some(WideValue::Float(value)) => *value,
+            other => panic!("{} read back as {:?}", key, other),
+        };
+        assert!(float("nan").is_nan());
+        assert_eq!(float("inf"), f32::INFINITY);
+        assert_eq!(float("neg_inf"), f32::NEG_INFINITY);
+        match read[1].get("position") {
+            Some(WideValue::Vec3(position)) => {
+                assert!(position.x.is_nan());
+                assert_eq!(position.y, f32::INFINITY);
+                assert_eq!(position.z, 0.5);
+            }
+            other => panic!("position read back as {:?}", other),
+        }
+    }
+}
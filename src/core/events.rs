@@ -7,6 +7,7 @@
 
 use super::Phase;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 // ============================================================================
 // PHASE EVENTS
@@ -25,8 +26,8 @@ pub struct MomentEvent {
 // TRAVELER EVENTS
 // ============================================================================
 
-/// Traveler identity
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Traveler identity (ordered as `TravelerId::all`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum TravelerId {
     Archivist,
     Wanderer,
@@ -118,7 +119,7 @@ pub struct TravelersSyncedEvent {
 // ============================================================================
 
 /// Camera behavior modes
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum CameraBehavior {
     #[default]
     Static,    // No movement
@@ -239,7 +240,7 @@ pub struct BangEvent {
     pub elapsed: f32,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BangStage {
     Start,
     LightPoint,
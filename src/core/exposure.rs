@@ -5,6 +5,8 @@
 use bevy::prelude::*;
 use bevy::render::camera::Exposure;
 
+use super::{Phase, PhaseTable};
+
 /// Dynamic exposure control tied to experience phase
 #[derive(Resource)]
 pub struct ExposureControl {
@@ -27,22 +29,14 @@ impl Default for ExposureControl {
 }
 
 impl ExposureControl {
-    /// Get exposure value for each phase
-    pub fn for_phase(phase: &str) -> f32 {
-        match phase {
-            "signal" => 0.5,      // Dark, waiting
-            "bang" => 15.0,       // Blindingly bright at peak
-            "awakening" => 8.0,   // Settling down
-            "discovery" => 10.0,  // Normal exposure
-            "connection" => 12.0, // Warm, bright
-            "acceptance" => 6.0,  // Dimming toward darkness
-            _ => 10.0,
-        }
+    /// Get exposure value for a phase from the phase table
+    pub fn for_phase(table: &PhaseTable, phase: Phase) -> f32 {
+        table.exposure(phase)
     }
 
     /// Set target exposure for a phase
-    pub fn set_phase(&mut self, phase: &str) {
-        self.target = Self::for_phase(phase);
+    pub fn set_phase(&mut self, table: &PhaseTable, phase: Phase) {
+        self.target = Self::for_phase(table, phase);
     }
 
     /// Set target exposure with custom transition speed
@@ -0,0 +1,87 @@
+//! Fixed-timestep simulation - choreography runs at a fixed rate, rendering interpolates
+//!
+//! Behaviour, clock and camera systems live in `FixedUpdate`, so the piece plays
+//! the same at 30, 60 or 144 Hz. Entities with `InterpolatedTransform` are
+//! blended between the last two simulation steps before transforms propagate.
+
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+
+/// Simulation rate in Hz
+pub const SIMULATION_HZ: f64 = 60.0;
+
+/// Transform snapshots from the last two simulation steps
+///
+/// Simulation systems read and write `Transform` as usual; it holds the
+/// authoritative state while `FixedUpdate` runs and the blended state otherwise.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct InterpolatedTransform {
+    pub previous: Transform,
+    pub current: Transform,
+}
+
+impl InterpolatedTransform {
+    pub fn new(transform: Transform) -> Self {
+        Self {
+            previous: transform,
+            current: transform,
+        }
+    }
+
+    /// Jump without blending from the old position (teleports, seeks)
+    pub fn snap(&mut self, transform: Transform) {
+        self.previous = transform;
+        self.current = transform;
+    }
+
+    /// Blend between the two snapshots
+    pub fn lerp(&self, t: f32) -> Transform {
+        Transform {
+            translation: self.previous.translation.lerp(self.current.translation, t),
+            rotation: self.previous.rotation.slerp(self.current.rotation, t),
+            scale: self.previous.scale.lerp(self.current.scale, t),
+        }
+    }
+}
+
+/// Restore the simulated transform before a step and remember it as the previous state
+pub fn begin_simulation_step(mut query: Query<(&mut Transform, &mut InterpolatedTransform)>) {
+    for (mut transform, mut interpolated) in query.iter_mut() {
+        interpolated.previous = interpolated.current;
+        *transform = interpolated.current;
+    }
+}
+
+/// Record the result of a step
+pub fn end_simulation_step(mut query: Query<(&Transform, &mut InterpolatedTransform)>) {
+    for (transform, mut interpolated) in query.iter_mut() {
+        interpolated.current = *transform;
+    }
+}
+
+/// Blend rendered transforms by how far real time has run past the last step
+pub fn interpolate_transforms(
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&mut Transform, &InterpolatedTransform)>,
+) {
+    let t = fixed_time.overstep_fraction();
+
+    for (mut transform, interpolated) in query.iter_mut() {
+        *transform = interpolated.lerp(t);
+    }
+}
+
+/// Plugin for the fixed simulation rate and transform interpolation
+pub struct FixedStepPlugin;
+
+impl Plugin for FixedStepPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Time::<Fixed>::from_hz(SIMULATION_HZ))
+            .add_systems(FixedFirst, begin_simulation_step)
+            .add_systems(FixedLast, end_simulation_step)
+            .add_systems(
+                PostUpdate,
+                interpolate_transforms.before(TransformSystem::TransformPropagate),
+            );
+    }
+}
@@ -0,0 +1,381 @@
+//! Headless simulation - run the full experience with no window, GPU or audio
+//!
+//! Steps the app on a fixed timestep as fast as possible and collects the
+//! wide-event stream plus a final state summary. Runs with the same seed and
+//! timestep produce identical event logs.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy::app::AppExit;
+use bevy::ecs::schedule::{ExecutorKind, ScheduleLabel};
+use bevy::prelude::*;
+use bevy::render::settings::{RenderCreation, WgpuSettings};
+use bevy::render::RenderPlugin;
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::ExitCondition;
+use bevy::winit::WinitPlugin;
+
+use super::event_sinks::WideEventSinkConfig;
+use super::fixed_step::InterpolatedTransform;
+use super::logging::{WideEvent, WideEventLog};
+use super::perf_report::{hitch_threshold, PerfReportConfig, PerformanceReport, PhasePerformance};
+use super::performance::PerformanceConfig;
+use super::remote::RemoteConfig;
+use super::sync::SyncConfig;
+use super::replay::ReplayLog;
+use super::{ExperienceClock, ExperienceRng, ExperienceState, TravelerId};
+use crate::camera::{CameraRig, ExperienceCamera};
+use crate::travelers::{Traveler, TravelerRegistry, TravelerState, TravelerVisibility};
+use crate::wide_event;
+
+/// Marker resource: the app is running without window, renderer or audio device
+#[derive(Resource, Debug, Default)]
+pub struct HeadlessMode;
+
+/// Headless run parameters
+#[derive(Debug, Clone)]
+pub struct HeadlessConfig {
+    /// Seed for `ExperienceRng`
+    pub seed: u64,
+    /// Simulation step in seconds
+    pub timestep: f32,
+    /// Extra wide-event sinks (the event log is always captured)
+    pub sinks: WideEventSinkConfig,
+    /// Drive the run from a recording instead of the timeline
+    pub replay: Option<ReplayLog>,
+    /// Accept OSC commands and send telemetry during the run
+    pub remote: Option<RemoteConfig>,
+    /// Lead or follow other instances
+    pub sync: Option<SyncConfig>,
+    /// Also write the performance report here when the run ends
+    pub perf_report: Option<PathBuf>,
+}
+
+impl Default for HeadlessConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            timestep: 1.0 / 60.0,
+            sinks: WideEventSinkConfig::default(),
+            replay: None,
+            remote: None,
+            sync: None,
+            perf_report: None,
+        }
+    }
+}
+
+/// Final state of one traveler
+#[derive(Debug, Clone)]
+pub struct TravelerSummary {
+    pub id: TravelerId,
+    pub state: TravelerState,
+    pub opacity: f32,
+    pub position: Vec3,
+}
+
+/// End-of-run snapshot
+#[derive(Debug, Clone)]
+pub struct HeadlessSummary {
+    pub seed: u64,
+    pub frames: u32,
+    pub elapsed: f32,
+    pub state: ExperienceState,
+    pub travelers: Vec<TravelerSummary>,
+    pub spawned: Vec<TravelerId>,
+    pub active: Vec<TravelerId>,
+    pub faded: Vec<TravelerId>,
+    pub camera_position: Option<Vec3>,
+}
+
+/// Everything a headless run produces
+#[derive(Debug, Clone)]
+pub struct HeadlessReport {
+    pub events: Vec<WideEvent>,
+    pub summary: HeadlessSummary,
+    /// Per-phase compute time of each simulated frame
+    pub performance: PerformanceReport,
+}
+
+impl fmt::Display for HeadlessSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = |ids: &[TravelerId]| {
+            ids.iter().map(|id| id.name()).collect::<Vec<_>>().join(", ")
+        };
+
+        writeln!(f, "seed: {}", self.seed)?;
+        writeln!(f, "frames: {}", self.frames)?;
+        writeln!(f, "elapsed: {:.2}s", self.elapsed)?;
+        writeln!(f, "state: {}", self.state.name())?;
+        writeln!(f, "registry spawned: [{}]", names(&self.spawned))?;
+        writeln!(f, "registry active: [{}]", names(&self.active))?;
+        writeln!(f, "registry faded: [{}]", names(&self.faded))?;
+        for traveler in &self.travelers {
+            writeln!(
+                f,
+                "traveler {}: {:?} opacity={:.3} position={:.3?}",
+                traveler.id.name(),
+                traveler.state,
+                traveler.opacity,
+                traveler.position
+            )?;
+        }
+        match self.camera_position {
+            Some(position) => write!(f, "camera rig position: {:.3?}", position),
+            None => write!(f, "camera rig position: none"),
+        }
+    }
+}
+
+/// Build an app running `LightwatchPlugin` with no window, GPU or audio device
+pub fn build_headless_app(config: &HeadlessConfig) -> App {
+    let mut app = App::new();
+
+    app.add_plugins(
+        DefaultPlugins
+            .set(WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                close_when_requested: false,
+            })
+            .set(RenderPlugin {
+                render_creation: RenderCreation::Automatic(WgpuSettings {
+                    backends: None,
+                    ..default()
+                }),
+                ..default()
+            })
+            .disable::<WinitPlugin>()
+            .disable::<bevy::audio::AudioPlugin>()
+            .disable::<bevy::log::LogPlugin>(),
+    )
+    // Must exist before the plugins so they skip device setup and keep the seed,
+    // and so the capture sees events emitted while the plugins build
+    .insert_resource(HeadlessMode)
+    .insert_resource(capturing_log())
+    .insert_resource(ExperienceRng::new(config.seed))
+    .insert_resource(config.sinks.clone());
+    if let Some(log) = &config.replay {
+        app.insert_resource(log.clone());
+    }
+    if let Some(remote) = &config.remote {
+        app.insert_resource(remote.clone());
+    }
+    if let Some(sync) = &config.sync {
+        app.insert_resource(sync.clone());
+    }
+    app.insert_resource(PerfReportConfig {
+        path: config.perf_report.clone(),
+    });
+    app.add_plugins(crate::LightwatchPlugin);
+
+    // One simulation step per update, so the fixed loop never skips or doubles
+    let step = Duration::from_secs_f32(config.timestep);
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(step))
+        .insert_resource(Time::<Fixed>::from_duration(step));
+
+    // Single-threaded executors give a fixed system order, which keeps RNG draws
+    // and event order identical between runs
+    for label in [
+        Startup.intern(),
+        PreUpdate.intern(),
+        FixedFirst.intern(),
+        FixedPreUpdate.intern(),
+        FixedUpdate.intern(),
+        FixedPostUpdate.intern(),
+        FixedLast.intern(),
+        Update.intern(),
+        PostUpdate.intern(),
+        Last.intern(),
+    ] {
+        app.edit_schedule(label, |schedule| {
+            schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        });
+    }
+
+    app
+}
+
+/// A wide-event log that captures from the start
+fn capturing_log() -> WideEventLog {
+    let log = WideEventLog::default();
+    log.start_capture();
+    log
+}
+
+/// Run the whole experience headless and return the event log and summary
+pub fn run_headless(config: &HeadlessConfig) -> HeadlessReport {
+    run_headless_app(build_headless_app(config), config, |_| {})
+}
+
+/// Run an app from `build_headless_app` to the end, calling `after_update`
+/// after every frame
+pub fn run_headless_app(
+    mut app: App,
+    config: &HeadlessConfig,
+    mut after_update: impl FnMut(&mut App),
+) -> HeadlessReport {
+    app.finish();
+    app.cleanup();
+
+    // Enough frames for the full timeline plus the ending fade, with margin;
+    // the phase table is loaded on the first update, so the limit follows it
+    let max_frames = |app: &App| {
+        let duration = app.world().resource::<ExperienceClock>().duration();
+        ((duration + 10.0) / config.timestep).ceil() as u32
+    };
+    let mut frames = 0;
+    let mut started = false;
+
+    while frames < max_frames(&app) {
+        app.update();
+        frames += 1;
+        after_update(&mut app);
+
+        let state = *app.world().resource::<State<ExperienceState>>().get();
+
+        // Stand in for the click on the ready screen
+        if state == ExperienceState::Ready && !started {
+            started = true;
+            app.world_mut()
+                .resource_mut::<NextState<ExperienceState>>()
+                .set(ExperienceState::Running);
+            app.world_mut().resource_mut::<ExperienceClock>().start();
+
+            wide_event!("experience_started")
+                .with_str("trigger", "headless")
+                .emit(app.world().resource::<WideEventLog>(), 0.0);
+        }
+
+        if state == ExperienceState::Ended {
+            break;
+        }
+
+        if !app.world().resource::<Events<AppExit>>().is_empty() {
+            break;
+        }
+    }
+
+    if frames >= max_frames(&app) {
+        warn!(target: "lightwatch::headless", "Stopped after {} frames without reaching Ended", frames);
+    }
+
+    let summary = summarize(&mut app, config.seed, frames);
+    let performance = {
+        let world = app.world();
+        world
+            .resource::<PhasePerformance>()
+            .report(hitch_threshold(world.resource::<PerformanceConfig>()))
+    };
+
+    HeadlessReport {
+        events: app.world().resource::<WideEventLog>().finish_capture(),
+        summary,
+        performance,
+    }
+}
+
+/// Build a headless app and start the run from the ready screen
+#[cfg(test)]
+pub(crate) fn running_app(config: &HeadlessConfig) -> App {
+    let mut app = build_headless_app(config);
+    app.finish();
+    app.cleanup();
+
+    for _ in 0..600 {
+        if *app.world().resource::<State<ExperienceState>>().get() == ExperienceState::Ready {
+            break;
+        }
+        app.update();
+    }
+    app.world_mut()
+        .resource_mut::<NextState<ExperienceState>>()
+        .set(ExperienceState::Running);
+    app.world_mut().resource_mut::<ExperienceClock>().start();
+    app
+}
+
+/// Step a running app until the clock reaches `time`
+#[cfg(test)]
+pub(crate) fn run_until(app: &mut App, time: f32) {
+    while app.world().resource::<ExperienceClock>().elapsed() < time {
+        app.update();
+    }
+}
+
+/// Snapshot traveler, registry and camera state from the world
+pub(crate) fn summarize(app: &mut App, seed: u64, frames: u32) -> HeadlessSummary {
+    let world = app.world_mut();
+
+    let elapsed = world.resource::<ExperienceClock>().elapsed();
+    let state = *world.resource::<State<ExperienceState>>().get();
+    let registry = world.resource::<TravelerRegistry>();
+    let (spawned, active, faded) = (
+        registry.spawned.clone(),
+        registry.active.clone(),
+        registry.faded.clone(),
+    );
+
+    // Report simulated positions, not the render-interpolated ones
+    let mut travelers: Vec<TravelerSummary> = world
+        .query::<(&Traveler, &TravelerState, &TravelerVisibility, &InterpolatedTransform)>()
+        .iter(world)
+        .map(|(traveler, state, visibility, transform)| TravelerSummary {
+            id: traveler.id,
+            state: *state,
+            opacity: visibility.opacity,
+            position: transform.current.translation,
+        })
+        .collect();
+    travelers.sort_by_key(|traveler| traveler.id as usize);
+
+    let camera_position = world
+        .query_filtered::<(&CameraRig, &InterpolatedTransform), With<ExperienceCamera>>()
+        .iter(world)
+        .next()
+        .map(|(_, transform)| transform.current.translation);
+
+    HeadlessSummary {
+        seed,
+        frames,
+        elapsed,
+        state,
+        travelers,
+        spawned,
+        active,
+        faded,
+        camera_position,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_gives_identical_event_logs() {
+        // A coarser step than the default keeps the two full runs quick
+        let config = HeadlessConfig {
+            seed: 7,
+            timestep: 1.0 / 30.0,
+            ..default()
+        };
+
+        // Each app has its own wide-event log, so the runs can share the process
+        let (first, second) = std::thread::scope(|scope| {
+            let first = scope.spawn(|| run_headless(&config));
+            let second = scope.spawn(|| run_headless(&config));
+            (first.join().unwrap(), second.join().unwrap())
+        });
+
+        assert_eq!(first.summary.state, ExperienceState::Ended);
+        let lines = |report: &HeadlessReport| {
+            report.events.iter().map(WideEvent::to_line).collect::<Vec<_>>()
+        };
+        assert!(!first.events.is_empty());
+        assert_eq!(lines(&first), lines(&second));
+        assert_eq!(first.summary.camera_position, second.summary.camera_position);
+    }
+}
@@ -4,9 +4,10 @@ use bevy::prelude::*;
 use bevy::window::CursorGrabMode;
 
 use super::state::ExperienceState;
-use super::ExperienceClock;
+use super::{ExperienceClock, ResetExperience};
 #[cfg(debug_assertions)]
-use super::TimeControl;
+use super::ExperienceController;
+use super::logging::WideEventLog;
 use crate::wide_event;
 
 /// Input handler configuration
@@ -35,6 +36,7 @@ pub fn handle_click_to_start(
     mut clock: ResMut<ExperienceClock>,
     mut windows: Query<&mut Window>,
     config: Res<InputConfig>,
+    log: Res<WideEventLog>,
 ) {
     // Only in Ready state
     if *state.get() != ExperienceState::Ready {
@@ -54,26 +56,36 @@ pub fn handle_click_to_start(
         next_state.set(ExperienceState::Running);
         clock.start();
 
-        // Hide cursor
-        if config.hide_cursor {
-            if let Ok(mut window) = windows.get_single_mut() {
-                window.cursor.visible = false;
-                window.cursor.grab_mode = CursorGrabMode::Confined;
-            }
-        }
+        hide_cursor_for_run(&config, &mut windows);
 
         wide_event!("experience_started")
             .with_str("trigger", "click")
-            .emit(0.0);
+            .emit(&log, 0.0);
+    }
+}
+
+/// Hide and confine the cursor while the experience runs, if configured
+pub fn hide_cursor_for_run(config: &InputConfig, windows: &mut Query<&mut Window>) {
+    if config.hide_cursor {
+        if let Ok(mut window) = windows.get_single_mut() {
+            window.cursor.visible = false;
+            window.cursor.grab_mode = CursorGrabMode::Confined;
+        }
     }
 }
 
-/// Disable input after experience starts
+/// Disable input after experience starts (re-enabled by a reset)
 pub fn disable_input_during_experience(
     state: Res<State<ExperienceState>>,
     mut config: ResMut<InputConfig>,
+    mut resets: EventReader<ResetExperience>,
     mut done: Local<bool>,
 ) {
+    if resets.read().count() > 0 {
+        config.enabled = true;
+        *done = false;
+    }
+
     // Only disable once when transitioning to Running
     if *state.get() == ExperienceState::Running && !*done {
         config.enabled = false;
@@ -81,12 +93,17 @@ pub fn disable_input_during_experience(
     }
 }
 
-/// Restore cursor when experience ends
+/// Restore cursor when experience ends (once per run)
 pub fn restore_cursor_on_end(
     state: Res<State<ExperienceState>>,
     mut windows: Query<&mut Window>,
+    mut resets: EventReader<ResetExperience>,
     mut done: Local<bool>,
 ) {
+    if resets.read().count() > 0 {
+        *done = false;
+    }
+
     if *state.get() == ExperienceState::Ended && !*done {
         if let Ok(mut window) = windows.get_single_mut() {
             window.cursor.visible = true;
@@ -100,7 +117,7 @@ pub fn restore_cursor_on_end(
 #[cfg(debug_assertions)]
 pub fn handle_extended_dev_controls(
     keyboard: Res<ButtonInput<KeyCode>>,
-    mut time_control: ResMut<TimeControl>,
+    mut controller: ResMut<ExperienceController>,
     clock: Res<ExperienceClock>,
     state: Res<State<ExperienceState>>,
 ) {
@@ -111,31 +128,31 @@ pub fn handle_extended_dev_controls(
 
     // R: reset to beginning
     if keyboard.just_pressed(KeyCode::KeyR) {
-        time_control.scrub_position = Some(0.0);
+        controller.seek(0.0);
         info!(target: "lightwatch::input", "Resetting to start");
     }
 
-    // Additional phase jump keys (7, 8)
+    // Additional phase jump keys (7, 8), relative to the end of the cut
     if keyboard.just_pressed(KeyCode::Digit7) {
-        time_control.scrub_position = Some(130.0);
-        info!(target: "lightwatch::input", "Jumping to near end (130s)");
+        let time = clock.duration() - 13.0;
+        controller.seek(time);
+        info!(target: "lightwatch::input", "Jumping to near end ({:.0}s)", time);
     }
     if keyboard.just_pressed(KeyCode::Digit8) {
-        time_control.scrub_position = Some(140.0);
-        info!(target: "lightwatch::input", "Jumping to end (140s)");
+        let time = clock.duration() - 3.0;
+        controller.seek(time);
+        info!(target: "lightwatch::input", "Jumping to end ({:.0}s)", time);
     }
 
-    // Arrow keys: scrub
+    // Arrow keys: scrub (one seek per press, each one rebuilds the world)
     let shift_held = keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
     let scrub_amount = if shift_held { 5.0 } else { 0.5 };
 
-    if keyboard.pressed(KeyCode::ArrowLeft) {
-        let new_time = (clock.elapsed() - scrub_amount).max(0.0);
-        time_control.scrub_position = Some(new_time);
+    if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        controller.seek(clock.elapsed() - scrub_amount);
     }
-    if keyboard.pressed(KeyCode::ArrowRight) {
-        let new_time = (clock.elapsed() + scrub_amount).min(143.0);
-        time_control.scrub_position = Some(new_time);
+    if keyboard.just_pressed(KeyCode::ArrowRight) {
+        controller.seek(clock.elapsed() + scrub_amount);
     }
 }
 
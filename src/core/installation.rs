@@ -0,0 +1,198 @@
+//! Installation loop mode - run the experience unattended, over and over
+//!
+//! After the experience ends, the screen stays dark for `idle_delay`
+//! seconds, then the world is reset (`ResetExperience`) and the ready screen
+//! returns. A visitor can click to begin; otherwise each run, the first
+//! included, starts by itself after `auto_start_delay`.
+
+use bevy::prelude::*;
+
+use super::input::{hide_cursor_for_run, InputConfig};
+use super::logging::WideEventLog;
+use super::reset::{reset_experience, ResetReason};
+use super::state::{request_transition, request_transition_in};
+use super::{ExperienceClock, ExperienceState};
+use crate::wide_event;
+
+/// Loop mode settings; insert before `LightwatchPlugin` to enable
+#[derive(Resource, Debug, Clone)]
+pub struct LoopConfig {
+    /// Restart after the experience ends
+    pub enabled: bool,
+    /// Seconds to stay on the ended (black) screen before resetting
+    pub idle_delay: f32,
+    /// Seconds on the ready screen before starting without a click
+    pub auto_start_delay: Option<f32>,
+}
+
+impl Default for LoopConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            idle_delay: 10.0,
+            auto_start_delay: Some(30.0),
+        }
+    }
+}
+
+impl LoopConfig {
+    /// Loop mode with the given idle delay
+    pub fn looping(idle_delay: f32) -> Self {
+        Self {
+            enabled: true,
+            idle_delay,
+            ..default()
+        }
+    }
+}
+
+/// Progress of the current loop
+#[derive(Resource, Debug, Default)]
+pub struct LoopState {
+    /// Completed runs
+    pub loops: u32,
+    /// Seconds spent in the current Ended or Ready state
+    pub waited: f32,
+}
+
+/// Leave the ended screen once the idle delay has passed
+pub fn advance_after_end(
+    config: Res<LoopConfig>,
+    time: Res<Time>,
+    state: Res<State<ExperienceState>>,
+    mut loop_state: ResMut<LoopState>,
+    mut next_state: ResMut<NextState<ExperienceState>>,
+) {
+    loop_state.waited += time.delta_seconds();
+
+    if loop_state.waited >= config.idle_delay {
+        request_transition(*state.get(), ExperienceState::Idle, &mut next_state);
+    }
+}
+
+/// Reset the world between runs and return to the ready screen (exclusive)
+pub fn reset_for_next_loop(world: &mut World) {
+    reset_experience(world, ResetReason::Loop);
+
+    let loops = {
+        let mut loop_state = world.resource_mut::<LoopState>();
+        loop_state.loops += 1;
+        loop_state.waited = 0.0;
+        loop_state.loops
+    };
+
+    info!(target: "lightwatch::loop", "Run {} complete, resetting", loops);
+    wide_event!("experience_reset")
+        .with_str("reason", ResetReason::Loop.name())
+        .with_i64("loops", loops as i64)
+        .emit(world.resource::<WideEventLog>(), 0.0);
+
+    request_transition_in(world, ExperienceState::Ready);
+}
+
+/// Start the next run if nobody clicks
+#[allow(clippy::too_many_arguments)]
+pub fn auto_start_next_run(
+    config: Res<LoopConfig>,
+    time: Res<Time>,
+    input: Res<InputConfig>,
+    state: Res<State<ExperienceState>>,
+    mut loop_state: ResMut<LoopState>,
+    mut next_state: ResMut<NextState<ExperienceState>>,
+    mut clock: ResMut<ExperienceClock>,
+    mut windows: Query<&mut Window>,
+    log: Res<WideEventLog>,
+) {
+    let Some(delay) = config.auto_start_delay else {
+        return;
+    };
+
+    loop_state.waited += time.delta_seconds();
+    if loop_state.waited < delay {
+        return;
+    }
+
+    if !request_transition(*state.get(), ExperienceState::Running, &mut next_state) {
+        return;
+    }
+    clock.start();
+    hide_cursor_for_run(&input, &mut windows);
+
+    wide_event!("experience_started")
+        .with_str("trigger", "loop")
+        .emit(&log, 0.0);
+}
+
+/// Clear the wait timer when a run starts or ends
+pub fn clear_loop_wait(mut loop_state: ResMut<LoopState>) {
+    loop_state.waited = 0.0;
+}
+
+fn loop_enabled(config: Res<LoopConfig>) -> bool {
+    config.enabled
+}
+
+/// Installation loop plugin; inert unless `LoopConfig::enabled`
+pub struct InstallationPlugin;
+
+impl Plugin for InstallationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LoopConfig>()
+            .init_resource::<LoopState>()
+            .add_systems(
+                Update,
+                (
+                    advance_after_end.run_if(in_state(ExperienceState::Ended)),
+                    auto_start_next_run.run_if(in_state(ExperienceState::Ready)),
+                )
+                    .run_if(loop_enabled),
+            )
+            .add_systems(OnEnter(ExperienceState::Idle), reset_for_next_loop)
+            .add_systems(OnEnter(ExperienceState::Ended), clear_loop_wait)
+            .add_systems(OnEnter(ExperienceState::Running), clear_loop_wait);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::headless::{run_until, running_app, summarize, HeadlessConfig};
+
+    #[test]
+    fn next_loop_replays_the_first_run() {
+        let config = HeadlessConfig {
+            seed: 5,
+            timestep: 1.0 / 30.0,
+            ..default()
+        };
+        let mut app = running_app(&config);
+        run_until(&mut app, 20.0);
+        let first = summarize(&mut app, config.seed, 0);
+
+        // Straight from the run to the next one, as a remote reset would
+        reset_for_next_loop(app.world_mut());
+        app.update();
+        assert_eq!(
+            *app.world().resource::<State<ExperienceState>>().get(),
+            ExperienceState::Ready
+        );
+        request_transition_in(app.world_mut(), ExperienceState::Running);
+        app.world_mut().resource_mut::<ExperienceClock>().start();
+        run_until(&mut app, 20.0);
+        let second = summarize(&mut app, config.seed, 0);
+
+        assert_eq!(second.elapsed, first.elapsed);
+        assert_eq!(second.active, first.active);
+        assert!(!first.travelers.is_empty());
+        for (second, first) in second.travelers.iter().zip(&first.travelers) {
+            assert_eq!(second.id, first.id);
+            assert!(
+                second.position.distance(first.position) < 1e-3,
+                "{} at {:?}, first run at {:?}",
+                second.id.name(),
+                second.position,
+                first.position
+            );
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! Launch configuration - window, timing, audio and quality for one run
+//!
+//! The binary fills a `LaunchConfig` from the user settings file and its
+//! command line (flags win over the file). `insert_resources` puts the
+//! matching resources into the app before `LightwatchPlugin` is added; the
+//! plugins only `init_resource`, so these values win.
+
+use bevy::prelude::*;
+
+use super::event_sinks::WideEventSinkConfig;
+use super::input::InputConfig;
+use super::installation::LoopConfig;
+use super::perf_report::PerfReportConfig;
+use super::performance::AdaptiveQuality;
+use super::remote::RemoteConfig;
+use super::rng::ExperienceRng;
+use super::seek::ExperienceController;
+use super::settings::{SettingsFile, UserSettings};
+use super::sync::SyncConfig;
+use super::time_control::TimeControl;
+use super::window::{configure_window, WindowSettings};
+use crate::audio::AudioOutputConfig;
+use crate::camera::CameraViewOffset;
+
+/// Everything that can be chosen when launching a windowed run
+#[derive(Debug, Clone)]
+pub struct LaunchConfig {
+    pub window: WindowSettings,
+    /// Begin at this time as soon as loading finishes, without a click
+    pub start_at: Option<f32>,
+    /// Clock speed (1.0 = real time)
+    pub time_scale: f32,
+    /// Seed for `ExperienceRng`; random when `None`
+    pub seed: Option<u64>,
+    /// Run without opening an audio device
+    pub muted: bool,
+    /// Write the mix to this WAV when no audio device is available
+    pub audio_file: Option<std::path::PathBuf>,
+    /// Volume, quality, text size and motion preferences
+    pub settings: UserSettings,
+    /// Where menu changes to `settings` are saved
+    pub settings_path: Option<std::path::PathBuf>,
+    /// Keep the cursor visible during the run
+    pub show_cursor: bool,
+    pub sinks: WideEventSinkConfig,
+    /// Installation loop mode
+    pub looping: Option<LoopConfig>,
+    /// OSC remote control and telemetry
+    pub remote: Option<RemoteConfig>,
+    /// Lead or follow other instances
+    pub sync: Option<SyncConfig>,
+    /// This screen's slice of a panorama
+    pub view_offset: CameraViewOffset,
+    /// Write the per-phase performance report here when the run ends
+    pub perf_report: Option<std::path::PathBuf>,
+    /// Drop and restore quality tiers with the frame rate
+    pub adaptive_quality: bool,
+}
+
+impl Default for LaunchConfig {
+    fn default() -> Self {
+        Self {
+            window: WindowSettings::default(),
+            start_at: None,
+            time_scale: 1.0,
+            seed: None,
+            muted: false,
+            audio_file: None,
+            settings: UserSettings::default(),
+            settings_path: None,
+            show_cursor: false,
+            sinks: WideEventSinkConfig::default(),
+            looping: None,
+            remote: None,
+            sync: None,
+            view_offset: CameraViewOffset::default(),
+            perf_report: None,
+            adaptive_quality: true,
+        }
+    }
+}
+
+impl LaunchConfig {
+    /// Window plugin for `DefaultPlugins`
+    pub fn window_plugin(&self) -> WindowPlugin {
+        configure_window(&self.window)
+    }
+
+    /// Insert the configured resources (call before adding `LightwatchPlugin`)
+    pub fn insert_resources(&self, app: &mut App) {
+        let (performance, post) = self.settings.quality_configs();
+
+        app.insert_resource(performance)
+            .insert_resource(post)
+            .insert_resource(self.settings.quality.msaa())
+            .insert_resource(TimeControl {
+                speed: self.time_scale,
+                ..default()
+            })
+            .insert_resource(InputConfig {
+                hide_cursor: !self.show_cursor,
+                ..default()
+            })
+            .insert_resource(AudioOutputConfig {
+                muted: self.muted,
+                master_volume: self.settings.volume,
+                device: self.settings.audio_device.clone(),
+                sink_file: self.audio_file.clone(),
+            })
+            .insert_resource(self.settings.clone())
+            .insert_resource(SettingsFile {
+                path: self.settings_path.clone(),
+            })
+            // Must exist before LightwatchPlugin opens the sinks
+            .insert_resource(self.sinks.clone())
+            .insert_resource(self.view_offset)
+            .insert_resource(PerfReportConfig {
+                path: self.perf_report.clone(),
+            });
+
+        if let Some(seed) = self.seed {
+            app.insert_resource(ExperienceRng::new(seed));
+        }
+        if !self.adaptive_quality {
+            app.insert_resource(AdaptiveQuality::disabled());
+        }
+        if let Some(config) = &self.looping {
+            app.insert_resource(config.clone());
+        }
+        if let Some(config) = &self.remote {
+            app.insert_resource(config.clone());
+        }
+        if let Some(config) = &self.sync {
+            app.insert_resource(config.clone());
+        }
+
+        // The seek waits for loading to finish, then starts the run at the target
+        if let Some(start_at) = self.start_at {
+            app.add_systems(
+                Startup,
+                move |mut controller: ResMut<ExperienceController>| controller.seek(start_at),
+            );
+        }
+    }
+}
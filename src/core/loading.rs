@@ -0,0 +1,422 @@
+//! Loading state - waits for assets, render pipelines and the audio device
+//!
+//! Plugins add what they need to `LoadingTracker`: asset handles are polled
+//! through the `AssetServer`, other work (pipelines compiled in the render
+//! world, the audio device) reports its own result. Loading moves to Ready once
+//! every task has finished. A failed required task keeps the experience on the
+//! loading screen with the error shown; a failed optional task (audio) is shown
+//! on the ready screen and the run goes ahead without it.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use bevy::asset::{LoadState, UntypedHandle};
+use bevy::prelude::*;
+
+use super::headless::HeadlessMode;
+use super::logging::WideEventLog;
+use super::state::ExperienceState;
+use crate::wide_event;
+
+/// Font for the loading screen itself, so it can show a failed font load
+const LOADING_FONT: &[u8] = include_bytes!("../../assets/fonts/JetBrainsMono-Regular.ttf");
+
+/// Shader files the materials and post-processing nodes load by path
+pub const REQUIRED_SHADERS: &[&str] = &[
+    "shaders/traveler_glow.wgsl",
+    "shaders/traveler_shell.wgsl",
+    "shaders/traveler_edge.wgsl",
+    "shaders/nebula.wgsl",
+    "shaders/bang_core.wgsl",
+    "shaders/chromatic_aberration.wgsl",
+    "shaders/god_rays.wgsl",
+    "shaders/vignette.wgsl",
+    "shaders/film_grain.wgsl",
+];
+
+/// Where a loading task stands
+#[derive(Debug, Clone, PartialEq)]
+pub enum LoadStatus {
+    Pending,
+    Done,
+    Failed(String),
+}
+
+impl LoadStatus {
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, LoadStatus::Pending)
+    }
+}
+
+/// One thing Loading waits for
+#[derive(Debug, Clone)]
+pub struct LoadingTask {
+    pub name: String,
+    pub status: LoadStatus,
+    /// Failures of optional tasks do not hold the experience in Loading
+    pub optional: bool,
+    asset: Option<UntypedHandle>,
+}
+
+/// Everything Loading waits for
+#[derive(Resource)]
+pub struct LoadingTracker {
+    tasks: Vec<LoadingTask>,
+    /// Headless runs draw nothing, so asset loads are not waited for
+    track_assets: bool,
+    /// `loading_failed` has been logged
+    failure_reported: bool,
+}
+
+impl Default for LoadingTracker {
+    fn default() -> Self {
+        Self {
+            tasks: Vec::new(),
+            track_assets: true,
+            failure_reported: false,
+        }
+    }
+}
+
+impl LoadingTracker {
+    /// Wait for an asset to load
+    pub fn track_asset(&mut self, name: impl Into<String>, handle: impl Into<UntypedHandle>) {
+        if !self.track_assets {
+            return;
+        }
+        self.push(name.into(), false, Some(handle.into()));
+    }
+
+    /// Wait for work that reports itself with `finish` or `fail`
+    pub fn add_task(&mut self, name: impl Into<String>) {
+        self.push(name.into(), false, None);
+    }
+
+    /// Like `add_task`, but a failure only gets reported
+    pub fn add_optional_task(&mut self, name: impl Into<String>) {
+        self.push(name.into(), true, None);
+    }
+
+    fn push(&mut self, name: String, optional: bool, asset: Option<UntypedHandle>) {
+        if self.tasks.iter().any(|task| task.name == name) {
+            return;
+        }
+        self.tasks.push(LoadingTask {
+            name,
+            status: LoadStatus::Pending,
+            optional,
+            asset,
+        });
+    }
+
+    pub fn finish(&mut self, name: &str) {
+        self.set_status(name, LoadStatus::Done);
+    }
+
+    pub fn fail(&mut self, name: &str, error: impl Into<String>) {
+        self.set_status(name, LoadStatus::Failed(error.into()));
+    }
+
+    fn set_status(&mut self, name: &str, status: LoadStatus) {
+        if let Some(task) = self.tasks.iter_mut().find(|task| task.name == name) {
+            if task.status != status {
+                if let LoadStatus::Failed(error) = &status {
+                    if task.optional {
+                        warn!(target: "lightwatch::loading", "{} failed: {}", name, error);
+                    } else {
+                        error!(target: "lightwatch::loading", "{} failed: {}", name, error);
+                    }
+                }
+                task.status = status;
+            }
+        }
+    }
+
+    pub fn tasks(&self) -> &[LoadingTask] {
+        &self.tasks
+    }
+
+    /// Finished tasks and the total
+    pub fn progress(&self) -> (usize, usize) {
+        let finished = self
+            .tasks
+            .iter()
+            .filter(|task| task.status.is_finished())
+            .count();
+        (finished, self.tasks.len())
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.tasks.iter().all(|task| task.status.is_finished())
+    }
+
+    /// Failed tasks with their errors
+    pub fn failures(&self) -> impl Iterator<Item = (&LoadingTask, &str)> {
+        self.tasks.iter().filter_map(|task| match &task.status {
+            LoadStatus::Failed(error) => Some((task, error.as_str())),
+            _ => None,
+        })
+    }
+
+    /// A required task failed, so Loading cannot finish
+    pub fn is_blocked(&self) -> bool {
+        self.failures().any(|(task, _)| !task.optional)
+    }
+}
+
+/// `LOADING_FONT`, for UI that has to show text before (or without) the asset
+/// fonts: the loading screen and the debug overlay
+#[derive(Resource, Clone)]
+pub struct EmbeddedFont(pub Handle<Font>);
+
+impl FromWorld for EmbeddedFont {
+    fn from_world(world: &mut World) -> Self {
+        let Some(mut fonts) = world.get_resource_mut::<Assets<Font>>() else {
+            return Self(Handle::default());
+        };
+        match Font::try_from_bytes(LOADING_FONT.to_vec()) {
+            Ok(font) => Self(fonts.add(font)),
+            Err(err) => {
+                warn!(target: "lightwatch::loading", "Embedded font: {}", err);
+                Self(Handle::default())
+            }
+        }
+    }
+}
+
+/// Results reported from outside the main world (the render world)
+#[derive(Resource, Clone, Default)]
+pub struct LoadingReports {
+    statuses: Arc<Mutex<HashMap<String, LoadStatus>>>,
+}
+
+impl LoadingReports {
+    pub fn report(&self, name: &str, status: LoadStatus) {
+        if let Ok(mut statuses) = self.statuses.lock() {
+            statuses.insert(name.to_string(), status);
+        }
+    }
+}
+
+/// Start loading the shader files so their handles can be tracked
+pub fn track_required_shaders(
+    asset_server: Res<AssetServer>,
+    mut tracker: ResMut<LoadingTracker>,
+) {
+    for path in REQUIRED_SHADERS {
+        let handle: Handle<Shader> = asset_server.load(*path);
+        tracker.track_asset(*path, handle);
+    }
+}
+
+/// Pick up asset load states and reported results
+pub fn poll_loading_tasks(
+    asset_server: Res<AssetServer>,
+    reports: Res<LoadingReports>,
+    mut tracker: ResMut<LoadingTracker>,
+) {
+    let mut updates = Vec::new();
+    for task in tracker.tasks.iter().filter(|task| !task.status.is_finished()) {
+        if let Some(handle) = &task.asset {
+            match asset_server.get_load_state(handle.id()) {
+                Some(LoadState::Loaded) => updates.push((task.name.clone(), LoadStatus::Done)),
+                Some(LoadState::Failed(err)) => {
+                    updates.push((task.name.clone(), LoadStatus::Failed(err.to_string())));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if let Ok(statuses) = reports.statuses.lock() {
+        for (name, status) in statuses.iter() {
+            updates.push((name.clone(), status.clone()));
+        }
+    }
+
+    for (name, status) in updates {
+        tracker.set_status(&name, status);
+    }
+}
+
+/// Move to Ready once every task has finished and none of the required ones failed
+pub fn check_loading_complete(
+    time: Res<Time<Real>>,
+    mut tracker: ResMut<LoadingTracker>,
+    mut next_state: ResMut<NextState<ExperienceState>>,
+    log: Res<WideEventLog>,
+) {
+    let (finished, total) = tracker.progress();
+
+    if tracker.is_blocked() {
+        if !tracker.failure_reported {
+            tracker.failure_reported = true;
+            let failed: Vec<&str> = tracker
+                .failures()
+                .filter(|(task, _)| !task.optional)
+                .map(|(task, _)| task.name.as_str())
+                .collect();
+            wide_event!("loading_failed")
+                .with_str("tasks", failed.join(","))
+                .emit(&log, 0.0);
+        }
+        return;
+    }
+
+    if !tracker.is_finished() {
+        return;
+    }
+
+    next_state.set(ExperienceState::Ready);
+
+    let failed = tracker.failures().count();
+    info!(
+        target: "lightwatch::loading",
+        "Loaded {}/{} in {:.2}s",
+        finished,
+        total,
+        time.elapsed_seconds()
+    );
+    wide_event!("loading_complete")
+        .with_i64("tasks", total as i64)
+        .with_i64("failed", failed as i64)
+        .emit(&log, 0.0);
+}
+
+/// Marker for the loading screen
+#[derive(Component)]
+pub struct LoadingScreen;
+
+/// Progress bar fill
+#[derive(Component)]
+pub struct LoadingBar;
+
+/// "loading 3/12" line and failure list
+#[derive(Component)]
+pub struct LoadingLabel;
+
+/// Spawn the loading screen: a thin progress bar and a status line
+pub fn spawn_loading_screen(mut commands: Commands, font: Res<EmbeddedFont>) {
+    let font = font.0.clone();
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(12.0),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::BLACK),
+                ..default()
+            },
+            LoadingScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(240.0),
+                        height: Val::Px(2.0),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(Color::srgba(0.9, 0.87, 0.82, 0.1)),
+                    ..default()
+                })
+                .with_children(|track| {
+                    track.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(0.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            background_color: BackgroundColor(Color::srgba(0.9, 0.87, 0.82, 0.6)),
+                            ..default()
+                        },
+                        LoadingBar,
+                    ));
+                });
+
+            parent.spawn((
+                TextBundle::from_section(
+                    "loading",
+                    TextStyle {
+                        font,
+                        font_size: 12.0,
+                        color: Color::srgba(0.9, 0.87, 0.82, 0.4),
+                    },
+                )
+                .with_text_justify(JustifyText::Center),
+                LoadingLabel,
+            ));
+        });
+}
+
+/// Keep the bar and status line in step with the tracker
+pub fn update_loading_screen(
+    tracker: Res<LoadingTracker>,
+    mut bars: Query<&mut Style, With<LoadingBar>>,
+    mut labels: Query<&mut Text, With<LoadingLabel>>,
+) {
+    if !tracker.is_changed() {
+        return;
+    }
+
+    let (finished, total) = tracker.progress();
+    let fraction = if total == 0 {
+        1.0
+    } else {
+        finished as f32 / total as f32
+    };
+    for mut style in bars.iter_mut() {
+        style.width = Val::Percent(fraction * 100.0);
+    }
+
+    let mut status = format!("loading {}/{}", finished, total);
+    for (task, error) in tracker.failures() {
+        status.push_str(&format!("\n{} failed: {}", task.name, error));
+    }
+    for mut text in labels.iter_mut() {
+        if let Some(section) = text.sections.first_mut() {
+            section.value.clone_from(&status);
+            if tracker.is_blocked() {
+                section.style.color = Color::srgba(0.95, 0.45, 0.4, 0.8);
+            }
+        }
+    }
+}
+
+/// Remove the loading screen when Ready is reached
+pub fn despawn_loading_screen(mut commands: Commands, screens: Query<Entity, With<LoadingScreen>>) {
+    for entity in screens.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Loading plugin: tracker, progress screen and the move to Ready
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        let track_assets = !app.world().contains_resource::<HeadlessMode>();
+        app.insert_resource(LoadingTracker {
+            track_assets,
+            ..default()
+        })
+        .init_resource::<LoadingReports>()
+        .init_resource::<EmbeddedFont>()
+        .add_systems(Startup, (spawn_loading_screen, track_required_shaders))
+        .add_systems(OnExit(ExperienceState::Loading), despawn_loading_screen)
+        .add_systems(
+            Update,
+            (poll_loading_tasks, check_loading_complete, update_loading_screen)
+                .chain()
+                .run_if(in_state(ExperienceState::Loading)),
+        );
+    }
+}
@@ -3,30 +3,175 @@
 #![allow(dead_code)]
 
 use bevy::prelude::*;
-use std::collections::HashMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
+/// Where an app's emitted wide events go
+///
+/// Each app has its own log, so captures and seek muting never reach another
+/// app running in the same process. Clones share the same queues.
+#[derive(Resource, Clone, Default)]
+pub struct WideEventLog {
+    inner: Arc<WideEventQueues>,
+}
+
+#[derive(Default)]
+struct WideEventQueues {
+    /// Events collected while a capture is active (see `start_capture`)
+    capture: Mutex<Option<Vec<WideEvent>>>,
+    /// Events waiting for `WideEventSinks`, once sinks are enabled (see `enable_sinks`)
+    pending: Mutex<Option<Vec<WideEvent>>>,
+    /// While set, `emit` drops events (a seek replaying the timeline)
+    muted: AtomicBool,
+}
+
+impl WideEventLog {
+    /// Drop or resume emitted wide events
+    pub fn set_muted(&self, muted: bool) {
+        self.inner.muted.store(muted, Ordering::Relaxed);
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.inner.muted.load(Ordering::Relaxed)
+    }
+
+    /// Start collecting every emitted wide event
+    pub fn start_capture(&self) {
+        if let Ok(mut capture) = self.inner.capture.lock() {
+            *capture = Some(Vec::new());
+        }
+    }
+
+    /// Stop collecting and return the captured events in emission order
+    pub fn finish_capture(&self) -> Vec<WideEvent> {
+        self.inner
+            .capture
+            .lock()
+            .ok()
+            .and_then(|mut capture| capture.take())
+            .unwrap_or_default()
+    }
+
+    /// Start queueing emitted events for `WideEventSinks`
+    pub fn enable_sinks(&self) {
+        if let Ok(mut pending) = self.inner.pending.lock() {
+            pending.get_or_insert_with(Vec::new);
+        }
+    }
+
+    /// Take the events emitted since the last call, in emission order
+    pub fn take_pending(&self) -> Vec<WideEvent> {
+        self.inner
+            .pending
+            .lock()
+            .ok()
+            .and_then(|mut pending| pending.as_mut().map(std::mem::take))
+            .unwrap_or_default()
+    }
+
+    fn record(&self, event: &WideEvent) {
+        for queue in [&self.inner.capture, &self.inner.pending] {
+            if let Ok(mut queue) = queue.lock() {
+                if let Some(events) = queue.as_mut() {
+                    events.push(event.clone());
+                }
+            }
+        }
+    }
+}
+
 /// A structured wide event - carries all context in fields
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WideEvent {
     /// Event name (snake_case)
     pub name: String,
     /// Timestamp since experience start
     pub elapsed_secs: f32,
-    /// Arbitrary context fields
-    pub context: HashMap<String, WideValue>,
-    /// When this event was created
+    /// Arbitrary context fields, sorted by key
+    pub context: BTreeMap<String, WideValue>,
+    /// When this event was created (not serialized; reset on load)
+    #[serde(skip, default = "Instant::now")]
     pub created_at: Instant,
 }
 
 /// Values that can be stored in wide event context
-#[derive(Debug, Clone)]
+///
+/// Serialized with an explicit type tag, e.g. `{"type":"vec3","value":[0.0,1.0,0.0]}`,
+/// so every variant reads back as itself. JSON has no literal for NaN or
+/// infinity, so those floats are written as the strings `"NaN"`, `"inf"`
+/// and `"-inf"`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
 pub enum WideValue {
     String(String),
-    Float(f32),
+    Float(#[serde(with = "json_f32")] f32),
     Int(i64),
     Bool(bool),
-    Vec3(Vec3),
+    Vec3(#[serde(with = "vec3_array")] Vec3),
+}
+
+/// `f32` as a JSON number, or a string when it is not finite
+struct JsonF32(f32);
+
+impl Serialize for JsonF32 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0.is_finite() {
+            serializer.serialize_f32(self.0)
+        } else {
+            serializer.collect_str(&self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonF32 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Number(f32),
+            Text(String),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Number(value) => Ok(JsonF32(value)),
+            Repr::Text(text) => text.parse().map(JsonF32).map_err(|_| {
+                serde::de::Error::custom(format!("expected a float, found {:?}", text))
+            }),
+        }
+    }
+}
+
+mod json_f32 {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::JsonF32;
+
+    pub fn serialize<S: Serializer>(value: &f32, serializer: S) -> Result<S::Ok, S::Error> {
+        JsonF32(*value).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+        JsonF32::deserialize(deserializer).map(|value| value.0)
+    }
+}
+
+/// `Vec3` as a plain `[x, y, z]` array
+mod vec3_array {
+    use bevy::math::Vec3;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::JsonF32;
+
+    pub fn serialize<S: Serializer>(value: &Vec3, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(value.to_array().map(JsonF32))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec3, D::Error> {
+        <[JsonF32; 3]>::deserialize(deserializer).map(|[x, y, z]| Vec3::new(x.0, y.0, z.0))
+    }
 }
 
 impl WideEvent {
@@ -35,7 +180,7 @@ impl WideEvent {
         Self {
             name: name.into(),
             elapsed_secs: 0.0,
-            context: HashMap::new(),
+            context: BTreeMap::new(),
             created_at: Instant::now(),
         }
     }
@@ -71,10 +216,13 @@ impl WideEvent {
         self
     }
 
-    /// Emit the event (log with structured format)
-    pub fn emit(mut self, elapsed: f32) -> Self {
-        self.elapsed_secs = elapsed;
+    /// Look up a context field
+    pub fn get(&self, key: &str) -> Option<&WideValue> {
+        self.context.get(key)
+    }
 
+    /// Format as a single log line; context keys are sorted so output is stable
+    pub fn to_line(&self) -> String {
         let context_str: String = self
             .context
             .iter()
@@ -82,13 +230,19 @@ impl WideEvent {
             .collect::<Vec<_>>()
             .join(" ");
 
-        debug!(
-            target: "lightwatch::events",
-            "[{:.2}s] {} {}",
-            self.elapsed_secs,
-            self.name,
-            context_str
-        );
+        format!("[{:.2}s] {} {}", self.elapsed_secs, self.name, context_str)
+    }
+
+    /// Emit the event into `log` (and the debug log, in structured format)
+    pub fn emit(mut self, log: &WideEventLog, elapsed: f32) -> Self {
+        self.elapsed_secs = elapsed;
+
+        if log.is_muted() {
+            return self;
+        }
+
+        debug!(target: "lightwatch::events", "{}", self.to_line());
+        log.record(&self);
 
         self
     }
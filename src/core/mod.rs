@@ -7,36 +7,76 @@ pub mod build_info;
 pub mod clock;
 pub mod debug_overlay;
 pub mod easing;
+pub mod event_sinks;
 pub mod events;
 pub mod exposure;
+pub mod fixed_step;
+pub mod headless;
 pub mod hot_reload;
 pub mod input;
+pub mod installation;
+pub mod launch;
+pub mod loading;
 pub mod logging;
+pub mod moment_actions;
+pub mod osc;
+pub mod perf_report;
 pub mod performance;
 pub mod phase_controller;
+pub mod phase_table;
 pub mod polish;
 pub mod ready_screen;
+pub mod remote;
 pub mod renderer;
+pub mod replay;
+pub mod reset;
+pub mod rng;
+pub mod seek;
+pub mod settings;
+pub mod settings_menu;
 pub mod state;
+pub mod sync;
 pub mod time_control;
+pub mod timeline_data;
 pub mod timeline_verify;
+pub mod warmup;
 pub mod window;
 
 pub use build_info::*;
 pub use clock::*;
 pub use debug_overlay::*;
+pub use event_sinks::*;
 pub use events::*;
 pub use exposure::*;
+pub use fixed_step::*;
+pub use headless::*;
 pub use hot_reload::*;
 pub use input::*;
+pub use installation::*;
+pub use launch::*;
+pub use loading::*;
+pub use moment_actions::*;
 pub use phase_controller::*;
+pub use phase_table::*;
 pub use ready_screen::*;
+pub use remote::*;
 pub use renderer::*;
+pub use replay::*;
+pub use reset::*;
+pub use rng::*;
+pub use seek::*;
+pub use settings::*;
+pub use settings_menu::*;
 pub use state::*;
+pub use sync::*;
 pub use time_control::*;
+pub use timeline_data::*;
 pub use timeline_verify::*;
+pub use warmup::*;
 pub use window::*;
-pub use performance::{PerformanceConfig, PerformanceMetrics};
+pub use perf_report::*;
+pub use performance::{AdaptiveQuality, PerformanceConfig, PerformanceMetrics, QualityPreset};
+pub use logging::WideEventLog;
 pub use polish::FadeState;
 
 /// Core plugin for window, rendering, and core systems
@@ -46,18 +86,35 @@ impl Plugin for CorePlugin {
     fn build(&self, app: &mut App) {
         app
             // Diagnostics for FPS display
-            .add_plugins(FrameTimeDiagnosticsPlugin::default())
+            .add_plugins(FrameTimeDiagnosticsPlugin)
+            // Wide event sinks (JSON Lines, ring buffer, stdout)
+            .add_plugins(WideEventSinkPlugin)
+            // Replay from a recorded log (after the sinks it records into)
+            .add_plugins(ReplayPlugin)
+            // Asset, pipeline and audio device loading before Ready
+            .add_plugins(LoadingPlugin)
+            // Render pipeline warm-up under the loading screen
+            .add_plugins(WarmupPlugin)
+            // Fixed simulation rate and render interpolation
+            .add_plugins(FixedStepPlugin)
             // Events plugin
             .add_plugins(EventsPlugin)
             // Phase controller
             .add_plugins(PhaseControllerPlugin)
             // Input handling
             .add_plugins(InputPlugin)
+            // Installation loop mode
+            .add_plugins(InstallationPlugin)
+            // OSC remote control and telemetry
+            .add_plugins(RemotePlugin)
+            // Leader/follower sync between instances
+            .add_plugins(SyncPlugin)
             // State
             .init_state::<ExperienceState>()
             // Events
             .add_event::<PhaseChangedEvent>()
             .add_event::<StateChangedEvent>()
+            .add_event::<ResetExperience>()
             // Resources
             .init_resource::<ExperienceClock>()
             .init_resource::<EndingTimer>()
@@ -65,6 +122,13 @@ impl Plugin for CorePlugin {
             .init_resource::<DebugOverlayState>()
             .init_resource::<HotReloadConfig>()
             .init_resource::<TimeControl>()
+            .init_resource::<ExperienceRng>()
+            .init_resource::<ExperienceController>()
+            .init_resource::<PhaseTableConfig>()
+            .init_resource::<PhaseTable>()
+            .init_resource::<UserSettings>()
+            .init_resource::<SettingsFile>()
+            .init_resource::<SettingsMenuState>()
             // System sets
             .configure_sets(
                 Update,
@@ -76,38 +140,79 @@ impl Plugin for CorePlugin {
                     }),
                 ),
             )
+            .configure_sets(FixedUpdate, RunningSet.run_if(in_running_state))
             // Startup systems
-            .add_systems(Startup, (spawn_debug_overlay, spawn_ready_screen, setup_hot_reload))
+            .add_systems(
+                Startup,
+                (
+                    spawn_debug_overlay,
+                    setup_hot_reload,
+                    log_rng_seed,
+                    load_phase_table_file,
+                ),
+            )
+            // The clock follows the phase table; seeks then rebuild the world
+            // before the fixed-step loop runs
+            .add_systems(
+                PreUpdate,
+                (
+                    sync_phase_table.run_if(resource_changed::<PhaseTable>),
+                    apply_pending_seek,
+                )
+                    .chain(),
+            )
+            // Reset protocol (plugins add their own reset systems)
+            .init_schedule(ExperienceReset)
+            .add_systems(
+                ExperienceReset,
+                (
+                    reset_clock,
+                    restart_rng,
+                    reset_resource::<EndingTimer>,
+                    reset_resource::<SettingsMenuState>,
+                ),
+            )
             // State transitions
-            .add_systems(OnEnter(ExperienceState::Running), hide_ready_screen)
+            .add_systems(OnEnter(ExperienceState::Ready), spawn_ready_screen)
+            .add_systems(OnEnter(ExperienceState::Running), (hide_ready_screen, restart_rng))
+            .add_systems(OnExit(ExperienceState::Ready), close_settings_menu)
             // Update systems
             .add_systems(
                 Update,
                 (
                     handle_window_close,
-                    check_loading_complete,
-                    update_clock.in_set(RunningSet),
-                    emit_phase_changes.after(update_clock).in_set(RunningSet),
                     check_experience_end.in_set(RunningSet),
                     handle_ending_phase.in_set(EndingSet),
                     log_state_transitions,
                     update_exposure,
-                    update_debug_overlay,
-                    toggle_debug_overlay,
+                    (
+                        toggle_debug_overlay,
+                        show_cursor_for_overlay,
+                        build_timeline_bar,
+                        update_timeline_bar,
+                        seek_from_timeline,
+                        update_debug_overlay,
+                    )
+                        .chain(),
                     toggle_fullscreen, // Available in all builds
+                    apply_user_settings,
+                    (handle_settings_menu_input, update_settings_menu)
+                        .chain()
+                        .in_set(ReadySet),
+                ),
+            )
+            // Simulation systems
+            .add_systems(
+                FixedUpdate,
+                (
+                    update_clock.in_set(RunningSet),
+                    emit_phase_changes.after(update_clock).in_set(RunningSet),
                 ),
             );
 
         // Debug-only systems
         #[cfg(debug_assertions)]
-        app.add_systems(
-            Update,
-            (
-                manual_reload_trigger,
-                time_control::handle_time_control,
-                clear_scrub_position.after(update_clock),
-            ),
-        );
+        app.add_systems(Update, (manual_reload_trigger, time_control::handle_time_control));
     }
 }
 
@@ -116,20 +221,45 @@ pub struct IntegrationPlugin;
 
 impl Plugin for IntegrationPlugin {
     fn build(&self, app: &mut App) {
+        // Headless frames say nothing about the display, and tier changes
+        // would make event logs differ between runs
+        if app.world().contains_resource::<HeadlessMode>() {
+            app.insert_resource(AdaptiveQuality::disabled());
+        }
+
         app.init_resource::<PerformanceConfig>()
+            .init_resource::<AdaptiveQuality>()
             .init_resource::<PerformanceMetrics>()
+            .init_resource::<PhasePerformance>()
+            .init_resource::<PerfReportConfig>()
             .init_resource::<TimelineVerification>()
             .init_resource::<FadeState>()
+            .add_systems(
+                ExperienceReset,
+                (
+                    reset_resource::<FadeState>,
+                    reset_resource::<PhasePerformance>,
+                    reset_resource::<TimelineVerification>,
+                    polish::clear_end_fade,
+                ),
+            )
             .add_systems(
                 Update,
                 (
                     performance::update_metrics,
-                    performance::adaptive_quality,
+                    performance::adaptive_quality
+                        .after(performance::update_metrics)
+                        .run_if(in_state(ExperienceState::Running)),
+                    perf_report::record_phase_performance.after(performance::adaptive_quality),
                     timeline_verify::verify_timeline,
                     polish::fade_visuals_at_end,
                     polish::log_experience_ending,
                 ),
             )
+            .add_systems(
+                OnEnter(ExperienceState::Ended),
+                perf_report::write_performance_report,
+            )
             .add_systems(Last, timeline_verify::log_timeline_verification);
 
         info!(target: "lightwatch::integration", "Integration plugin initialized");
@@ -0,0 +1,175 @@
+//! Moment action registry - which plugin carries out each `MomentAction`
+//!
+//! Plugins register a handler for the action kinds they own with
+//! `App::add_moment_actions`; `process_moments` hands each fired moment to the
+//! handler for its kind. Handlers run inside the fixed step (and inside seeks),
+//! so they should only send events; presentation systems read those events in
+//! `Update`, which lets a seek skip sounds and text that have already passed.
+//!
+//! Downstream crates add their own kinds with `MomentAction::Custom`:
+//!
+//! ```ignore
+//! app.add_moment_actions(&["strobe"], |world: &mut World, action: &MomentAction, elapsed: f32| {
+//!     if let MomentAction::Custom { data, .. } = action {
+//!         // data.clone().into_rust::<StrobeParams>()
+//!     }
+//! });
+//! ```
+//!
+//! Kinds used by the schedule that nothing handles are reported at startup.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use bevy::prelude::*;
+
+use super::logging::WideEventLog;
+use super::phase_controller::{Moment, MomentAction, PhaseController};
+use crate::wide_event;
+
+/// Carries out a fired moment's action; gets the clock time it fired at
+pub type MomentActionHandler = Arc<dyn Fn(&mut World, &MomentAction, f32) + Send + Sync>;
+
+/// Handlers by action kind (`MomentAction::kind`)
+#[derive(Resource, Default)]
+pub struct MomentActionRegistry {
+    handlers: HashMap<String, MomentActionHandler>,
+}
+
+impl MomentActionRegistry {
+    /// Route `kind` to `handler`, replacing any earlier handler
+    pub fn register(&mut self, kind: impl Into<String>, handler: MomentActionHandler) {
+        let kind = kind.into();
+        if self.handlers.insert(kind.clone(), handler).is_some() {
+            debug!(target: "lightwatch::timeline", "Replaced the handler for {}", kind);
+        }
+    }
+
+    pub fn handler(&self, kind: &str) -> Option<MomentActionHandler> {
+        self.handlers.get(kind).cloned()
+    }
+
+    pub fn is_handled(&self, kind: &str) -> bool {
+        self.handlers.contains_key(kind)
+    }
+
+    /// Action kinds in `moments` with no handler, each with the moments that use it
+    pub fn unhandled<'a>(
+        &self,
+        moments: impl IntoIterator<Item = &'a Moment>,
+    ) -> BTreeMap<String, Vec<String>> {
+        let mut unhandled: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for moment in moments {
+            let kind = moment.action.kind();
+            if !self.is_handled(kind) {
+                unhandled
+                    .entry(kind.to_string())
+                    .or_default()
+                    .push(moment.name.clone());
+            }
+        }
+        unhandled
+    }
+}
+
+/// Registering moment action handlers from a plugin
+pub trait MomentActionAppExt {
+    /// Send moments whose action is one of `kinds` to `handler`
+    fn add_moment_actions<F>(&mut self, kinds: &[&str], handler: F) -> &mut Self
+    where
+        F: Fn(&mut World, &MomentAction, f32) + Send + Sync + 'static;
+}
+
+impl MomentActionAppExt for App {
+    fn add_moment_actions<F>(&mut self, kinds: &[&str], handler: F) -> &mut Self
+    where
+        F: Fn(&mut World, &MomentAction, f32) + Send + Sync + 'static,
+    {
+        let handler: MomentActionHandler = Arc::new(handler);
+        let mut registry = self
+            .world_mut()
+            .get_resource_or_insert_with(MomentActionRegistry::default);
+        for kind in kinds {
+            registry.register(*kind, handler.clone());
+        }
+        self
+    }
+}
+
+/// Run the handler for a fired moment's action
+pub fn dispatch_moment_action(world: &mut World, name: &str, action: &MomentAction, elapsed: f32) {
+    let handler = world
+        .get_resource::<MomentActionRegistry>()
+        .and_then(|registry| registry.handler(action.kind()));
+
+    match handler {
+        Some(handler) => handler(world, action, elapsed),
+        None => debug!(
+            target: "lightwatch::timeline",
+            "Moment '{}' did nothing: no handler for {}",
+            name,
+            action.kind()
+        ),
+    }
+}
+
+/// Warn about schedule actions nothing will carry out (after the schedule loads)
+pub fn report_unhandled_moment_actions(
+    registry: Res<MomentActionRegistry>,
+    controller: Res<PhaseController>,
+    log: Res<WideEventLog>,
+) {
+    let unhandled = registry.unhandled(&controller.moments);
+    if unhandled.is_empty() {
+        return;
+    }
+
+    for (kind, moments) in &unhandled {
+        warn!(
+            target: "lightwatch::timeline",
+            "No handler for {}: {} will do nothing",
+            kind,
+            moments.join(", ")
+        );
+    }
+
+    wide_event!("moment_actions_unhandled")
+        .with_str("kinds", unhandled.keys().cloned().collect::<Vec<_>>().join(","))
+        .with_i64(
+            "moments",
+            unhandled.values().map(|moments| moments.len() as i64).sum(),
+        )
+        .emit(&log, 0.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::headless::{build_headless_app, HeadlessConfig};
+
+    /// Variant names of `MomentAction`, from the error for a kind it doesn't have
+    fn builtin_kinds() -> Vec<&'static str> {
+        match ron::from_str::<MomentAction>("NotAnAction").map_err(|err| err.code) {
+            Err(ron::Error::NoSuchEnumVariant { expected, .. }) => expected.to_vec(),
+            other => panic!("expected an unknown variant error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn every_builtin_action_has_a_handler() {
+        let mut app = build_headless_app(&HeadlessConfig::default());
+        app.update();
+
+        let kinds = builtin_kinds();
+        assert!(kinds.contains(&"SpawnTraveler"));
+        let registry = app.world().resource::<MomentActionRegistry>();
+        let unhandled: Vec<_> = kinds
+            .into_iter()
+            .filter(|kind| *kind != "Custom" && !registry.is_handled(kind))
+            .collect();
+        assert!(unhandled.is_empty(), "no handler for {:?}", unhandled);
+
+        let controller = app.world().resource::<PhaseController>();
+        assert!(registry.unhandled(&controller.moments).is_empty());
+    }
+}
@@ -0,0 +1,272 @@
+//! OSC 1.0 messages - just enough of the format for show control
+//!
+//! Supports int32 (`i`), float32 (`f`), string (`s`) and the argument-less
+//! true/false tags (`T`/`F`). Bundles are unpacked into their messages; time
+//! tags are ignored, everything runs as soon as it arrives.
+
+use std::fmt;
+
+/// One OSC argument
+#[derive(Debug, Clone, PartialEq)]
+pub enum OscArg {
+    Int(i32),
+    Float(f32),
+    String(String),
+    Bool(bool),
+}
+
+impl OscArg {
+    /// Numeric value, for addresses that take a number of either type
+    pub fn as_f32(&self) -> Option<f32> {
+        match self {
+            OscArg::Int(v) => Some(*v as f32),
+            OscArg::Float(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Truth value; numbers are true when non-zero
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            OscArg::Bool(v) => Some(*v),
+            OscArg::Int(v) => Some(*v != 0),
+            OscArg::Float(v) => Some(*v != 0.0),
+            OscArg::String(_) => None,
+        }
+    }
+
+    fn tag(&self) -> char {
+        match self {
+            OscArg::Int(_) => 'i',
+            OscArg::Float(_) => 'f',
+            OscArg::String(_) => 's',
+            OscArg::Bool(true) => 'T',
+            OscArg::Bool(false) => 'F',
+        }
+    }
+}
+
+/// An address plus arguments
+#[derive(Debug, Clone, PartialEq)]
+pub struct OscMessage {
+    pub address: String,
+    pub args: Vec<OscArg>,
+}
+
+impl OscMessage {
+    pub fn new(address: impl Into<String>, args: Vec<OscArg>) -> Self {
+        Self {
+            address: address.into(),
+            args,
+        }
+    }
+
+    /// Encode as an OSC packet
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_string(&mut out, &self.address);
+
+        let tags: String = std::iter::once(',')
+            .chain(self.args.iter().map(OscArg::tag))
+            .collect();
+        write_string(&mut out, &tags);
+
+        for arg in &self.args {
+            match arg {
+                OscArg::Int(v) => out.extend_from_slice(&v.to_be_bytes()),
+                OscArg::Float(v) => out.extend_from_slice(&v.to_be_bytes()),
+                OscArg::String(v) => write_string(&mut out, v),
+                OscArg::Bool(_) => {}
+            }
+        }
+
+        out
+    }
+}
+
+/// Why a packet could not be decoded
+#[derive(Debug, Clone, PartialEq)]
+pub struct OscError(pub String);
+
+impl fmt::Display for OscError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bad OSC packet: {}", self.0)
+    }
+}
+
+impl std::error::Error for OscError {}
+
+/// Decode a packet (a message or a bundle) into its messages
+pub fn decode_packet(bytes: &[u8]) -> Result<Vec<OscMessage>, OscError> {
+    if !bytes.len().is_multiple_of(4) {
+        return Err(OscError(format!(
+            "size {} is not a multiple of four",
+            bytes.len()
+        )));
+    }
+
+    if bytes.starts_with(b"#bundle\0") {
+        let mut reader = Reader { bytes, pos: 16 };
+        if bytes.len() < 16 {
+            return Err(OscError("bundle shorter than its header".into()));
+        }
+
+        let mut messages = Vec::new();
+        while reader.pos < bytes.len() {
+            let size = reader.i32()? as usize;
+            let element = reader.take(size)?;
+            messages.extend(decode_packet(element)?);
+        }
+        return Ok(messages);
+    }
+
+    decode_message(bytes).map(|message| vec![message])
+}
+
+fn decode_message(bytes: &[u8]) -> Result<OscMessage, OscError> {
+    let mut reader = Reader { bytes, pos: 0 };
+
+    let address = reader.string()?;
+    if !address.starts_with('/') {
+        return Err(OscError(format!("address '{}' must start with '/'", address)));
+    }
+
+    // Type tags are optional in old senders; no tags means no arguments
+    if reader.pos >= bytes.len() {
+        return Ok(OscMessage::new(address, Vec::new()));
+    }
+    let tags = reader.string()?;
+    let Some(tags) = tags.strip_prefix(',') else {
+        return Err(OscError(format!("type tags '{}' must start with ','", tags)));
+    };
+
+    let mut args = Vec::with_capacity(tags.len());
+    for tag in tags.chars() {
+        let arg = match tag {
+            'i' => OscArg::Int(reader.i32()?),
+            'f' => OscArg::Float(f32::from_bits(reader.i32()? as u32)),
+            's' => OscArg::String(reader.string()?),
+            'T' => OscArg::Bool(true),
+            'F' => OscArg::Bool(false),
+            other => return Err(OscError(format!("unsupported type tag '{}'", other))),
+        };
+        args.push(arg);
+    }
+
+    Ok(OscMessage::new(address, args))
+}
+
+/// Null-terminated, padded to a multiple of four bytes
+fn write_string(out: &mut Vec<u8>, value: &str) {
+    out.extend_from_slice(value.as_bytes());
+    let padding = 4 - value.len() % 4;
+    out.resize(out.len() + padding, 0);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], OscError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| OscError("truncated packet".into()))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn i32(&mut self) -> Result<i32, OscError> {
+        let bytes = self.take(4)?;
+        Ok(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn string(&mut self) -> Result<String, OscError> {
+        let rest = &self.bytes[self.pos..];
+        let len = rest
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or_else(|| OscError("unterminated string".into()))?;
+        let value = std::str::from_utf8(&rest[..len])
+            .map_err(|_| OscError("string is not UTF-8".into()))?
+            .to_string();
+
+        // Skip the terminator and padding
+        self.take((len / 4 + 1) * 4)?;
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle(messages: &[OscMessage]) -> Vec<u8> {
+        let mut out = b"#bundle\0".to_vec();
+        out.extend_from_slice(&1u64.to_be_bytes());
+        for message in messages {
+            let element = message.encode();
+            out.extend_from_slice(&(element.len() as i32).to_be_bytes());
+            out.extend_from_slice(&element);
+        }
+        out
+    }
+
+    #[test]
+    fn messages_round_trip() {
+        let messages = [
+            OscMessage::new("/lightwatch/start", Vec::new()),
+            OscMessage::new("/a", vec![OscArg::Int(-7), OscArg::Float(2.5)]),
+            // Strings on both sides of the four-byte padding boundary
+            OscMessage::new(
+                "/abc",
+                vec![
+                    OscArg::String(String::new()),
+                    OscArg::String("abc".into()),
+                    OscArg::String("abcd".into()),
+                    OscArg::Bool(true),
+                    OscArg::Bool(false),
+                ],
+            ),
+        ];
+
+        for message in &messages {
+            let bytes = message.encode();
+            assert!(bytes.len().is_multiple_of(4));
+            assert_eq!(decode_packet(&bytes), Ok(vec![message.clone()]));
+        }
+        assert_eq!(decode_packet(&bundle(&messages)), Ok(messages.to_vec()));
+    }
+
+    #[test]
+    fn truncated_packets_are_rejected() {
+        let bytes = OscMessage::new("/seek", vec![OscArg::Float(30.0)]).encode();
+        assert!(decode_packet(&bytes[..bytes.len() - 4]).is_err());
+
+        let string = OscMessage::new("/s", vec![OscArg::String("abcdefg".into())]).encode();
+        assert!(decode_packet(&string[..string.len() - 4]).is_err());
+
+        // Bundle element claiming more bytes than remain
+        let mut bundled = bundle(&[OscMessage::new("/start", Vec::new())]);
+        bundled.truncate(bundled.len() - 4);
+        assert!(decode_packet(&bundled).is_err());
+        assert!(decode_packet(b"#bundle\0").is_err());
+    }
+
+    #[test]
+    fn misaligned_packets_are_rejected() {
+        let bytes = OscMessage::new("/speed", vec![OscArg::Int(2)]).encode();
+
+        let mut longer = bytes.clone();
+        longer.push(0);
+        assert!(decode_packet(&longer).is_err());
+        assert!(decode_packet(&bytes[..bytes.len() - 1]).is_err());
+
+        // Address without a terminator in its last word
+        assert!(decode_packet(b"/abc").is_err());
+    }
+}
@@ -0,0 +1,422 @@
+//! Per-phase performance report - frame-time percentiles, hitches and entity counts
+//!
+//! Every frame of a run is binned into a histogram for the phase it falls in,
+//! along with the quality tier adaptive quality had in effect.
+//! Frame times are wall-clock time between updates, so a headless run measures
+//! how long each simulated frame took to compute. When the experience ends the
+//! report is logged and, with `--perf-report FILE`, written as CSV (for a
+//! `.csv` path) or JSON, so builds can be compared phase by phase. Headless
+//! runs also return it in `HeadlessReport::performance`.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use bevy::prelude::*;
+use serde::Serialize;
+
+use super::build_info::BuildInfo;
+use super::clock::{ExperienceClock, Phase};
+use super::performance::{AdaptiveQuality, PerformanceConfig, PerformanceMetrics, QualityPreset};
+use super::state::ExperienceState;
+
+/// Histogram bucket width (ms)
+const BUCKET_MS: f32 = 0.1;
+
+/// Buckets up to 250 ms; longer frames share the last one (`max` stays exact)
+const BUCKETS: usize = 2500;
+
+/// A frame is a hitch when it takes this many target frame times
+pub const HITCH_FACTOR: f32 = 2.0;
+
+/// Where the report goes when the experience ends
+#[derive(Resource, Debug, Clone, Default)]
+pub struct PerfReportConfig {
+    /// `.csv` writes CSV, anything else JSON
+    pub path: Option<PathBuf>,
+}
+
+/// Frame-time histogram with 0.1 ms buckets
+#[derive(Debug, Clone)]
+pub struct FrameTimeHistogram {
+    buckets: Vec<u32>,
+    count: u32,
+    total_ms: f64,
+    max_ms: f32,
+}
+
+impl Default for FrameTimeHistogram {
+    fn default() -> Self {
+        Self {
+            buckets: vec![0; BUCKETS],
+            count: 0,
+            total_ms: 0.0,
+            max_ms: 0.0,
+        }
+    }
+}
+
+impl FrameTimeHistogram {
+    pub fn record(&mut self, frame_ms: f32) {
+        let bucket = ((frame_ms / BUCKET_MS) as usize).min(BUCKETS - 1);
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.total_ms += frame_ms as f64;
+        self.max_ms = self.max_ms.max(frame_ms);
+    }
+
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    pub fn mean(&self) -> f32 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        (self.total_ms / self.count as f64) as f32
+    }
+
+    pub fn max(&self) -> f32 {
+        self.max_ms
+    }
+
+    /// Frame time `percentile` (0-100) of frames are at or under, to the bucket
+    pub fn percentile(&self, percentile: f32) -> f32 {
+        if self.count == 0 {
+            return 0.0;
+        }
+
+        let rank = ((percentile / 100.0) * self.count as f32).ceil().max(1.0) as u32;
+        let mut seen = 0;
+        for (index, &frames) in self.buckets.iter().enumerate() {
+            seen += frames;
+            if seen >= rank {
+                // Upper edge of the bucket, but never past the slowest frame
+                return ((index + 1) as f32 * BUCKET_MS).min(self.max_ms);
+            }
+        }
+        self.max_ms
+    }
+}
+
+/// What was measured during one phase
+#[derive(Debug, Clone, Default)]
+pub struct PhaseSamples {
+    pub frame_times: FrameTimeHistogram,
+    pub hitches: u32,
+    pub entities_max: usize,
+    /// Quality tiers frames were rendered at, in the order they were used
+    pub tiers: Vec<QualityPreset>,
+    entities_total: u64,
+}
+
+impl PhaseSamples {
+    pub fn entities_mean(&self) -> f32 {
+        match self.frame_times.count() {
+            0 => 0.0,
+            frames => (self.entities_total as f64 / frames as f64) as f32,
+        }
+    }
+
+    /// Tier names joined with `->`, e.g. `high->medium`
+    pub fn quality(&self) -> String {
+        let names: Vec<_> = self.tiers.iter().map(QualityPreset::name).collect();
+        names.join("->")
+    }
+}
+
+/// Samples for each phase of the current run, in the order they were reached
+#[derive(Resource, Debug, Clone, Default)]
+pub struct PhasePerformance {
+    phases: Vec<(Phase, PhaseSamples)>,
+}
+
+impl PhasePerformance {
+    pub fn record(
+        &mut self,
+        phase: Phase,
+        tier: QualityPreset,
+        frame_ms: f32,
+        hitch_ms: f32,
+        entities: usize,
+    ) {
+        let index = match self.phases.iter().position(|(p, _)| *p == phase) {
+            Some(index) => index,
+            None => {
+                self.phases.push((phase, PhaseSamples::default()));
+                self.phases.len() - 1
+            }
+        };
+        let samples = &mut self.phases[index].1;
+
+        samples.frame_times.record(frame_ms);
+        if samples.tiers.last() != Some(&tier) {
+            samples.tiers.push(tier);
+        }
+        if frame_ms > hitch_ms {
+            samples.hitches += 1;
+        }
+        samples.entities_max = samples.entities_max.max(entities);
+        samples.entities_total += entities as u64;
+    }
+
+    pub fn phase(&self, phase: Phase) -> Option<&PhaseSamples> {
+        self.phases
+            .iter()
+            .find(|(p, _)| *p == phase)
+            .map(|(_, samples)| samples)
+    }
+
+    /// Summarize the run so far
+    pub fn report(&self, hitch_ms: f32) -> PerformanceReport {
+        PerformanceReport {
+            version: BuildInfo::VERSION.to_string(),
+            profile: BuildInfo::PROFILE.to_string(),
+            hitch_ms,
+            phases: self
+                .phases
+                .iter()
+                .map(|(phase, samples)| PhaseReport {
+                    phase: phase.name().to_string(),
+                    quality: samples.quality(),
+                    frames: samples.frame_times.count(),
+                    mean_ms: samples.frame_times.mean(),
+                    p50_ms: samples.frame_times.percentile(50.0),
+                    p95_ms: samples.frame_times.percentile(95.0),
+                    p99_ms: samples.frame_times.percentile(99.0),
+                    max_ms: samples.frame_times.max(),
+                    hitches: samples.hitches,
+                    entities_mean: samples.entities_mean(),
+                    entities_max: samples.entities_max,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// One phase's line in the report
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PhaseReport {
+    /// `Phase::name`
+    pub phase: String,
+    /// `PhaseSamples::quality`, the tiers actually in effect
+    pub quality: String,
+    pub frames: u32,
+    pub mean_ms: f32,
+    pub p50_ms: f32,
+    pub p95_ms: f32,
+    pub p99_ms: f32,
+    pub max_ms: f32,
+    /// Frames over `PerformanceReport::hitch_ms`
+    pub hitches: u32,
+    pub entities_mean: f32,
+    pub entities_max: usize,
+}
+
+/// Per-phase performance of one run
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PerformanceReport {
+    pub version: String,
+    pub profile: String,
+    pub hitch_ms: f32,
+    pub phases: Vec<PhaseReport>,
+}
+
+impl PerformanceReport {
+    pub fn phase(&self, phase: Phase) -> Option<&PhaseReport> {
+        self.phases.iter().find(|report| report.phase == phase.name())
+    }
+
+    /// One row per phase with a header line
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(concat!(
+            "phase,quality,frames,mean_ms,p50_ms,p95_ms,p99_ms,max_ms,",
+            "hitches,entities_mean,entities_max\n",
+        ));
+        for phase in &self.phases {
+            csv.push_str(&format!(
+                "{},{},{},{:.3},{:.3},{:.3},{:.3},{:.3},{},{:.1},{}\n",
+                phase.phase,
+                phase.quality,
+                phase.frames,
+                phase.mean_ms,
+                phase.p50_ms,
+                phase.p95_ms,
+                phase.p99_ms,
+                phase.max_ms,
+                phase.hitches,
+                phase.entities_mean,
+                phase.entities_max
+            ));
+        }
+        csv
+    }
+
+    /// Write CSV for a `.csv` path, pretty JSON otherwise
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut writer = BufWriter::new(File::create(path)?);
+        if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("csv")) {
+            writer.write_all(self.to_csv().as_bytes())?;
+        } else {
+            serde_json::to_writer_pretty(&mut writer, self)?;
+            writeln!(writer)?;
+        }
+        writer.flush()
+    }
+}
+
+/// Frame time above which a frame counts as a hitch
+pub fn hitch_threshold(config: &PerformanceConfig) -> f32 {
+    config.target_frame_time * HITCH_FACTOR
+}
+
+/// Bin the last frame under the current phase
+pub fn record_phase_performance(
+    mut last_frame: Local<Option<Instant>>,
+    clock: Res<ExperienceClock>,
+    state: Res<State<ExperienceState>>,
+    metrics: Res<PerformanceMetrics>,
+    config: Res<PerformanceConfig>,
+    adaptive: Res<AdaptiveQuality>,
+    mut performance: ResMut<PhasePerformance>,
+) {
+    let now = Instant::now();
+    let Some(previous) = last_frame.replace(now) else {
+        return;
+    };
+
+    // Pauses are left out; the clock has stopped by the ending fade
+    let measuring = match state.get() {
+        ExperienceState::Running => clock.is_running(),
+        ExperienceState::Ending => true,
+        _ => false,
+    };
+    if !measuring {
+        return;
+    }
+
+    let frame_ms = now.duration_since(previous).as_secs_f32() * 1000.0;
+    performance.record(
+        clock.phase(),
+        adaptive.tier,
+        frame_ms,
+        hitch_threshold(&config),
+        metrics.entity_count,
+    );
+}
+
+/// Log the report and write it out when the experience ends
+pub fn write_performance_report(
+    performance: Res<PhasePerformance>,
+    config: Res<PerformanceConfig>,
+    report_config: Res<PerfReportConfig>,
+) {
+    let report = performance.report(hitch_threshold(&config));
+
+    for phase in &report.phases {
+        info!(
+            target: "lightwatch::performance",
+            "{} ({}): {} frames, p50/p95/p99/max {:.1}/{:.1}/{:.1}/{:.1}ms, {} hitches, \
+             {} entities",
+            phase.phase,
+            phase.quality,
+            phase.frames,
+            phase.p50_ms,
+            phase.p95_ms,
+            phase.p99_ms,
+            phase.max_ms,
+            phase.hitches,
+            phase.entities_max
+        );
+    }
+
+    if let Some(path) = &report_config.path {
+        match report.write(path) {
+            Ok(()) => info!(
+                target: "lightwatch::performance",
+                "Performance report written to {}",
+                path.display()
+            ),
+            Err(err) => warn!(
+                target: "lightwatch::performance",
+                "Cannot write performance report {}: {}",
+                path.display(),
+                err
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_use_the_bucket_upper_edge() {
+        let mut histogram = FrameTimeHistogram::default();
+        // 98 frames at 10ms, one at 20ms, one at 30ms
+        for _ in 0..98 {
+            histogram.record(10.05);
+        }
+        histogram.record(20.05);
+        histogram.record(30.05);
+
+        assert_eq!(histogram.count(), 100);
+        assert!((histogram.percentile(50.0) - 10.1).abs() < 1e-3);
+        assert!((histogram.percentile(99.0) - 20.1).abs() < 1e-3);
+        assert!((histogram.percentile(100.0) - 30.05).abs() < 1e-3);
+        assert!((histogram.mean() - 10.35).abs() < 1e-3);
+    }
+
+    #[test]
+    fn percentiles_never_exceed_the_slowest_frame() {
+        let mut histogram = FrameTimeHistogram::default();
+        histogram.record(16.62);
+
+        // The bucket's upper edge is 16.7ms, but no frame took that long
+        assert_eq!(histogram.percentile(50.0), 16.62);
+        assert_eq!(histogram.percentile(99.0), 16.62);
+        assert_eq!(FrameTimeHistogram::default().percentile(99.0), 0.0);
+    }
+
+    #[test]
+    fn long_frames_share_the_overflow_bucket() {
+        let mut histogram = FrameTimeHistogram::default();
+        histogram.record(10.0);
+        histogram.record(400.0);
+        histogram.record(1200.0);
+
+        assert_eq!(histogram.count(), 3);
+        assert_eq!(histogram.max(), 1200.0);
+        // Both land in the last bucket, whose upper edge is 250ms
+        assert!((histogram.percentile(50.0) - 250.0).abs() < 1e-3);
+        assert!((histogram.percentile(99.0) - 250.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn phases_count_hitches_and_the_tiers_in_effect() {
+        let mut performance = PhasePerformance::default();
+        let hitch_ms = 33.3;
+        performance.record(Phase::Signal, QualityPreset::High, 16.0, hitch_ms, 10);
+        performance.record(Phase::Signal, QualityPreset::High, 40.0, hitch_ms, 20);
+        performance.record(Phase::Signal, QualityPreset::Medium, 33.3, hitch_ms, 30);
+        performance.record(Phase::Bang, QualityPreset::Medium, 50.0, hitch_ms, 40);
+
+        let signal = performance.phase(Phase::Signal).unwrap();
+        assert_eq!(signal.hitches, 1);
+        assert_eq!(signal.entities_max, 30);
+        assert_eq!(signal.entities_mean(), 20.0);
+
+        let report = performance.report(hitch_ms);
+        assert_eq!(report.phases.len(), 2);
+        assert_eq!(report.phase(Phase::Signal).unwrap().quality, "high->medium");
+        assert_eq!(report.phase(Phase::Bang).unwrap().quality, "medium");
+        assert_eq!(report.phase(Phase::Bang).unwrap().hitches, 1);
+
+        let csv = report.to_csv();
+        assert!(csv.starts_with("phase,quality,frames,"));
+        assert!(csv.contains("\nsignal,high->medium,3,"));
+    }
+}
@@ -1,21 +1,55 @@
 //! Performance monitoring and adaptive quality
+//!
+//! Quality tiers are the launch presets. `adaptive_quality` drops a tier when
+//! frames stay slow and climbs back (up to the user's preset) once they have
+//! been fast for a while; the thresholds and hold times keep it from
+//! oscillating. A tier change waits for the next phase boundary, where the
+//! picture changes anyway, unless frames stay slow for too long.
 
-use bevy::core_pipeline::bloom::BloomSettings;
+use std::collections::VecDeque;
+
+use bevy::core_pipeline::dof::DepthOfFieldMode;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::clock::{ExperienceClock, Phase};
+use super::logging::WideEventLog;
+use super::settings::UserSettings;
+use crate::post::PostProcessConfig;
+use crate::wide_event;
+
+/// Average frame time over `target_frame_time` × this counts as slow
+const DEGRADE_FACTOR: f32 = 1.5;
+
+/// Average frame time under `target_frame_time` × this counts as fast
+const RECOVER_FACTOR: f32 = 1.1;
+
+/// Seconds of slow frames before dropping a tier
+const DEGRADE_AFTER: f32 = 2.0;
 
-use crate::camera::ExperienceCamera;
+/// Seconds of fast frames before climbing a tier
+const RECOVER_AFTER: f32 = 10.0;
+
+/// Seconds a drop waits for a phase boundary before it is applied anyway
+const MAX_DEFER: f32 = 5.0;
 
 /// Performance configuration
 #[derive(Resource)]
 pub struct PerformanceConfig {
     /// Target frame time (ms) - 60 FPS
     pub target_frame_time: f32,
-    /// Maximum particles
-    #[allow(dead_code)]
+    /// Most dust motes shown at once
     pub max_particles: usize,
-    /// LOD distance thresholds
-    #[allow(dead_code)]
+    /// Camera distances where traveler particles drop to half, a quarter and none
     pub lod_distances: [f32; 3],
+    /// Stars shown (of the spawned `StarfieldConfig::star_count`)
+    pub star_count: usize,
+    /// Share of aura particles shown and of trail particle lifetime
+    pub particle_density: f32,
+    /// Most god-ray samples per pixel
+    pub god_ray_samples: i32,
+    /// Depth of field, None for off
+    pub dof_mode: Option<DepthOfFieldMode>,
 }
 
 impl Default for PerformanceConfig {
@@ -24,54 +58,292 @@ impl Default for PerformanceConfig {
             target_frame_time: 16.67, // 60 FPS
             max_particles: 10000,
             lod_distances: [20.0, 50.0, 100.0],
+            star_count: 2000,
+            particle_density: 1.0,
+            god_ray_samples: 50,
+            dof_mode: Some(DepthOfFieldMode::Gaussian),
+        }
+    }
+}
+
+impl PerformanceConfig {
+    /// Share of traveler particle detail kept at `distance` from the camera
+    pub fn lod_detail(&self, distance: f32) -> f32 {
+        let [near, mid, far] = self.lod_distances;
+        if distance < near {
+            1.0
+        } else if distance < mid {
+            0.5
+        } else if distance < far {
+            0.25
+        } else {
+            0.0
         }
     }
 }
 
+/// Quality preset, also the tiers adaptive quality moves between (Low < High)
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum QualityPreset {
+    /// No MSAA, grain or chromatic aberration, fewer particles
+    Low,
+    /// 2x MSAA, no grain, half the particles
+    Medium,
+    /// Everything on (the default)
+    #[default]
+    High,
+}
+
+impl QualityPreset {
+    pub fn name(&self) -> &'static str {
+        match self {
+            QualityPreset::Low => "low",
+            QualityPreset::Medium => "medium",
+            QualityPreset::High => "high",
+        }
+    }
+
+    /// Next tier down, None at Low
+    pub fn lower(&self) -> Option<QualityPreset> {
+        match self {
+            QualityPreset::Low => None,
+            QualityPreset::Medium => Some(QualityPreset::Low),
+            QualityPreset::High => Some(QualityPreset::Medium),
+        }
+    }
+
+    /// Next tier up, None at High
+    pub fn higher(&self) -> Option<QualityPreset> {
+        match self {
+            QualityPreset::Low => Some(QualityPreset::Medium),
+            QualityPreset::Medium => Some(QualityPreset::High),
+            QualityPreset::High => None,
+        }
+    }
+
+    /// Anti-aliasing for this preset
+    ///
+    /// Applied from the settings only: changing `Msaa` recompiles every
+    /// pipeline, so adaptive tiers keep whatever the user's preset set.
+    pub fn msaa(&self) -> Msaa {
+        match self {
+            QualityPreset::Low => Msaa::Off,
+            QualityPreset::Medium => Msaa::Sample2,
+            QualityPreset::High => Msaa::Sample4,
+        }
+    }
+
+    /// Scale down the default performance and post-processing settings
+    pub fn apply(&self, performance: &mut PerformanceConfig, post: &mut PostProcessConfig) {
+        match self {
+            QualityPreset::Low => {
+                performance.max_particles = 2500;
+                performance.lod_distances = [10.0, 25.0, 50.0];
+                performance.star_count = 600;
+                performance.particle_density = 0.35;
+                performance.god_ray_samples = 16;
+                performance.dof_mode = None;
+                post.grain.enabled = false;
+                post.chromatic_aberration.enabled = false;
+            }
+            QualityPreset::Medium => {
+                performance.max_particles = 5000;
+                performance.lod_distances = [15.0, 35.0, 75.0];
+                performance.star_count = 1200;
+                performance.particle_density = 0.6;
+                performance.god_ray_samples = 32;
+                post.grain.enabled = false;
+            }
+            QualityPreset::High => {}
+        }
+    }
+}
+
+impl std::str::FromStr for QualityPreset {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "low" => Ok(QualityPreset::Low),
+            "medium" => Ok(QualityPreset::Medium),
+            "high" => Ok(QualityPreset::High),
+            _ => Err(format!("unknown quality preset '{}' (low, medium, high)", value)),
+        }
+    }
+}
+
+/// Frames kept in `PerformanceMetrics::frame_times`
+pub const FRAME_WINDOW: usize = 60;
+
 /// Performance metrics
 #[derive(Resource, Default)]
 pub struct PerformanceMetrics {
-    /// Recent frame times in milliseconds
-    pub frame_times: Vec<f32>,
+    /// Recent frame times in milliseconds, oldest first
+    pub frame_times: VecDeque<f32>,
     /// Current entity count
-    #[allow(dead_code)]
     pub entity_count: usize,
 }
 
-/// Update performance metrics
+/// Update performance metrics (wall-clock frame times, whatever the clock speed)
 pub fn update_metrics(
-    time: Res<Time>,
+    time: Res<Time<Real>>,
     mut metrics: ResMut<PerformanceMetrics>,
     entities: Query<Entity>,
 ) {
     let frame_time = time.delta_seconds() * 1000.0;
-    metrics.frame_times.push(frame_time);
-
-    // Keep last 60 frames
-    if metrics.frame_times.len() > 60 {
-        metrics.frame_times.remove(0);
+    if metrics.frame_times.len() == FRAME_WINDOW {
+        metrics.frame_times.pop_front();
     }
+    metrics.frame_times.push_back(frame_time);
 
     metrics.entity_count = entities.iter().count();
 }
 
-/// Adaptive quality based on performance
+/// Adaptive quality tier state
+#[derive(Resource, Debug)]
+pub struct AdaptiveQuality {
+    /// Off for headless runs and `--no-adaptive-quality`
+    pub enabled: bool,
+    /// Tier in effect
+    pub tier: QualityPreset,
+    /// Highest tier to climb back to (the user's preset)
+    pub ceiling: QualityPreset,
+    /// Tier waiting for a phase boundary, and how long it has waited
+    pending: Option<(QualityPreset, f32)>,
+    slow_for: f32,
+    fast_for: f32,
+}
+
+impl Default for AdaptiveQuality {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            tier: QualityPreset::default(),
+            ceiling: QualityPreset::default(),
+            pending: None,
+            slow_for: 0.0,
+            fast_for: 0.0,
+        }
+    }
+}
+
+impl AdaptiveQuality {
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..default()
+        }
+    }
+
+    /// Tier a change is waiting to apply
+    pub fn pending(&self) -> Option<QualityPreset> {
+        self.pending.map(|(tier, _)| tier)
+    }
+
+    /// Track slow and fast frames for `frame_ms` (window average) and pick the
+    /// next tier once either has lasted long enough
+    fn observe(&mut self, frame_ms: f32, target_ms: f32, delta: f32) {
+        if frame_ms > target_ms * DEGRADE_FACTOR {
+            self.slow_for += delta;
+            self.fast_for = 0.0;
+        } else if frame_ms < target_ms * RECOVER_FACTOR {
+            self.fast_for += delta;
+            self.slow_for = 0.0;
+        } else {
+            self.slow_for = 0.0;
+            self.fast_for = 0.0;
+        }
+
+        match self.pending {
+            // Drop cancelled once frames stop being slow, climb once they stop being fast
+            Some((tier, _)) if tier < self.tier && self.slow_for == 0.0 => self.pending = None,
+            Some((tier, _)) if tier > self.tier && self.fast_for == 0.0 => self.pending = None,
+            Some(_) => {}
+            None if self.slow_for >= DEGRADE_AFTER => {
+                self.pending = self.tier.lower().map(|tier| (tier, 0.0));
+            }
+            None if self.fast_for >= RECOVER_AFTER && self.tier < self.ceiling => {
+                self.pending = self.tier.higher().map(|tier| (tier, 0.0));
+            }
+            None => {}
+        }
+    }
+
+    /// Take the pending tier if it can apply now
+    fn take_ready(&mut self, at_boundary: bool, delta: f32) -> Option<QualityPreset> {
+        let (tier, waited) = self.pending.as_mut()?;
+        *waited += delta;
+        let overdue = *tier < self.tier && *waited >= MAX_DEFER;
+        if !at_boundary && !overdue {
+            return None;
+        }
+
+        let tier = *tier;
+        self.pending = None;
+        self.slow_for = 0.0;
+        self.fast_for = 0.0;
+        Some(tier)
+    }
+}
+
+/// Move between quality tiers as frame times allow
+#[allow(clippy::too_many_arguments)]
 pub fn adaptive_quality(
-    metrics: Res<PerformanceMetrics>,
-    config: Res<PerformanceConfig>,
-    mut bloom: Query<&mut BloomSettings, With<ExperienceCamera>>,
+    time: Res<Time<Real>>,
+    clock: Res<ExperienceClock>,
+    log: Res<WideEventLog>,
+    settings: Res<UserSettings>,
+    mut metrics: ResMut<PerformanceMetrics>,
+    mut adaptive: ResMut<AdaptiveQuality>,
+    mut config: ResMut<PerformanceConfig>,
+    mut post: ResMut<PostProcessConfig>,
+    mut last_phase: Local<Option<Phase>>,
 ) {
-    if metrics.frame_times.len() < 30 {
+    let phase = clock.phase();
+    let at_boundary = !clock.is_running() || last_phase.replace(phase) != Some(phase);
+
+    // A new preset from the settings menu starts over from that tier
+    if settings.is_changed() {
+        adaptive.ceiling = settings.quality;
+        adaptive.tier = settings.quality;
+        adaptive.pending = None;
+        adaptive.slow_for = 0.0;
+        adaptive.fast_for = 0.0;
         return;
     }
 
-    let avg_frame_time: f32 =
-        metrics.frame_times.iter().sum::<f32>() / metrics.frame_times.len() as f32;
-
-    // If running slow (below 40 FPS), reduce bloom quality
-    if avg_frame_time > config.target_frame_time * 1.5 {
-        for mut bloom_settings in bloom.iter_mut() {
-            bloom_settings.intensity = (bloom_settings.intensity * 0.95).max(0.05);
-        }
+    if !adaptive.enabled || metrics.frame_times.len() < FRAME_WINDOW {
+        return;
     }
+
+    let frame_ms = metrics.frame_times.iter().sum::<f32>() / metrics.frame_times.len() as f32;
+    let delta = time.delta_seconds();
+    adaptive.observe(frame_ms, config.target_frame_time, delta);
+
+    let Some(tier) = adaptive.take_ready(at_boundary, delta) else {
+        return;
+    };
+    let from = adaptive.tier;
+    adaptive.tier = tier;
+
+    (*config, *post) = settings.configs_for(tier);
+    // Judge the new tier on its own frames
+    metrics.frame_times.clear();
+
+    info!(
+        target: "lightwatch::performance",
+        "Quality {} -> {} ({:.1}ms average frame, {})",
+        from.name(),
+        tier.name(),
+        frame_ms,
+        phase.name()
+    );
+    wide_event!("quality_tier_changed")
+        .with_str("from", from.name())
+        .with_str("to", tier.name())
+        .with_f32("frame_ms", frame_ms)
+        .with_str("phase", phase.name())
+        .emit(&log, clock.elapsed());
 }
@@ -1,28 +1,104 @@
 //! Phase controller - orchestrates moment-by-moment actions
+//!
+//! A moment fires at an absolute time, or `time` seconds after an anchor
+//! (another moment, a traveler fading, a bang stage). Guards are checked when
+//! the moment comes due; if one fails the moment is skipped, and anything
+//! anchored to it never fires.
 
 #![allow(dead_code)]
 
+use bevy::ecs::system::{SystemParam, SystemState};
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use super::events::*;
+use super::logging::WideEventLog;
+use super::moment_actions::{dispatch_moment_action, report_unhandled_moment_actions, MomentActionRegistry};
+use super::replay::ReplayLog;
+use super::timeline_data::{load_timeline, TimelineConfig};
+use super::phase_table::PhaseTable;
 use super::ExperienceClock;
+use crate::text::{Transmission, TransmissionQueue, TransmissionState};
+use crate::travelers::{Traveler, TravelerState, TravelerVisibility};
 use crate::wide_event;
 
 /// A scheduled moment in the experience
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Moment {
-    /// Time in seconds when this moment triggers
+    /// Time in seconds when this moment triggers, or the delay after `after`
     pub time: f32,
     /// Name for logging
-    pub name: &'static str,
+    pub name: String,
     /// The action to take
     pub action: MomentAction,
+    /// Fire `time` seconds after this happens instead of at an absolute time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<MomentAnchor>,
+    /// Skip the moment unless all of these hold when it comes due
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub only_if: Vec<MomentGuard>,
     /// Has this moment been triggered?
+    #[serde(skip)]
     pub triggered: bool,
+    /// When the anchor happened, once it has
+    #[serde(skip)]
+    pub anchored_at: Option<f32>,
+}
+
+/// Something a moment can be scheduled relative to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MomentAnchor {
+    /// Another moment fired (at its scheduled time)
+    Moment(String),
+    /// A `SpawnTraveler` moment fired for this traveler
+    TravelerSpawned(TravelerId),
+    /// A `FadeTraveler` moment fired for this traveler
+    TravelerFading(TravelerId),
+    /// The traveler finished fading (`TravelerFadedEvent`)
+    TravelerFaded(TravelerId),
+    /// A fading traveler's opacity dropped below `below`
+    TravelerDimmed { traveler: TravelerId, below: f32 },
+    /// A `BangStage` moment fired for this stage
+    BangStage(BangStage),
+}
+
+impl MomentAnchor {
+    /// Does firing `moment` set this anchor?
+    pub fn is_set_by(&self, moment: &Moment) -> bool {
+        match (self, &moment.action) {
+            (MomentAnchor::Moment(name), _) => *name == moment.name,
+            (MomentAnchor::TravelerSpawned(id), MomentAction::SpawnTraveler(spawned)) => {
+                id == spawned
+            }
+            (MomentAnchor::TravelerFading(id), MomentAction::FadeTraveler(fading)) => {
+                id == fading
+            }
+            (MomentAnchor::BangStage(stage), MomentAction::BangStage(reached)) => stage == reached,
+            _ => false,
+        }
+    }
+
+    /// Traveler whose fade this anchor waits on, for anchors set by the simulation
+    pub fn fading_traveler(&self) -> Option<TravelerId> {
+        match self {
+            MomentAnchor::TravelerFaded(id)
+            | MomentAnchor::TravelerDimmed { traveler: id, .. } => Some(*id),
+            _ => None,
+        }
+    }
+}
+
+/// Condition checked when a moment comes due
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MomentGuard {
+    /// The traveler has spawned and is in this state
+    TravelerIs(TravelerId, TravelerState),
+    /// No narrative text is on screen or waiting to show
+    NoText,
 }
 
 /// Actions that can happen at a moment
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MomentAction {
     // Traveler actions
     SpawnTraveler(TravelerId),
@@ -52,20 +128,84 @@ pub enum MomentAction {
     // Meta actions
     TriggerGrief { mourner: TravelerId, deceased: TravelerId },
     SyncTravelers(Vec<TravelerId>),
+
+    /// Action registered by another crate under `kind`; `data` is passed through as written
+    Custom {
+        kind: String,
+        #[serde(default = "unit_value")]
+        data: ron::Value,
+    },
+}
+
+fn unit_value() -> ron::Value {
+    ron::Value::Unit
+}
+
+impl MomentAction {
+    /// Registry key: the variant name, or the kind of a custom action
+    pub fn kind(&self) -> &str {
+        match self {
+            MomentAction::SpawnTraveler(_) => "SpawnTraveler",
+            MomentAction::FadeTraveler(_) => "FadeTraveler",
+            MomentAction::SetCameraBehavior(_) => "SetCameraBehavior",
+            MomentAction::CameraShake { .. } => "CameraShake",
+            MomentAction::FocusOn(_) => "FocusOn",
+            MomentAction::PlayNote { .. } => "PlayNote",
+            MomentAction::StartLayer(_) => "StartLayer",
+            MomentAction::StopLayer(_) => "StopLayer",
+            MomentAction::PlayLeitmotif(_) => "PlayLeitmotif",
+            MomentAction::ShowText { .. } => "ShowText",
+            MomentAction::HideText => "HideText",
+            MomentAction::SignalTypewriter(_) => "SignalTypewriter",
+            MomentAction::BangStage(_) => "BangStage",
+            MomentAction::Glitch { .. } => "Glitch",
+            MomentAction::EnvironmentChange { .. } => "EnvironmentChange",
+            MomentAction::TriggerGrief { .. } => "TriggerGrief",
+            MomentAction::SyncTravelers(_) => "SyncTravelers",
+            MomentAction::Custom { kind, .. } => kind,
+        }
+    }
 }
 
 impl Moment {
-    pub fn new(time: f32, name: &'static str, action: MomentAction) -> Self {
+    pub fn new(time: f32, name: impl Into<String>, action: MomentAction) -> Self {
         Self {
             time,
-            name,
+            name: name.into(),
             action,
+            after: None,
+            only_if: Vec::new(),
             triggered: false,
+            anchored_at: None,
+        }
+    }
+
+    /// Fire `time` seconds after `anchor` instead
+    pub fn relative_to(mut self, anchor: MomentAnchor) -> Self {
+        self.after = Some(anchor);
+        self
+    }
+
+    /// Skip the moment unless `guard` holds when it comes due
+    pub fn when(mut self, guard: MomentGuard) -> Self {
+        self.only_if.push(guard);
+        self
+    }
+
+    /// Clock time the moment is due on the cut described by `table`, if known yet
+    ///
+    /// Absolute times are written against the standard cut and keep their
+    /// place within their phase (`PhaseTable::rescale`); delays after an
+    /// anchor are plain seconds.
+    pub fn due(&self, table: &PhaseTable) -> Option<f32> {
+        match self.after {
+            None => Some(table.rescale(self.time)),
+            Some(_) => self.anchored_at.map(|at| at + self.time),
         }
     }
 }
 
-/// All scheduled moments for the experience
+/// Built-in moment schedule, used when the timeline file cannot be loaded
 pub fn create_moment_schedule() -> Vec<Moment> {
     vec![
         // ====================================================================
@@ -76,7 +216,7 @@ pub fn create_moment_schedule() -> Vec<Moment> {
         Moment::new(1.0, "age_info", MomentAction::SignalTypewriter("age: 14.3 billion years".into())),
 
         // ====================================================================
-        // BANG PHASE (2-12s) - moments staggered by 0.05s to prevent frame spikes
+        // BANG PHASE (2-12s) - moments staggered by 0.05s (pipelines are warmed up in Loading)
         // ====================================================================
         Moment::new(2.0, "bang_start", MomentAction::BangStage(BangStage::Start)),
         Moment::new(2.05, "hide_signal", MomentAction::HideText),
@@ -87,7 +227,8 @@ pub fn create_moment_schedule() -> Vec<Moment> {
         Moment::new(4.10, "peak_shake", MomentAction::CameraShake { intensity: 0.4, duration: 0.5 }),
         Moment::new(6.0, "settling", MomentAction::BangStage(BangStage::Settling)),
         Moment::new(9.5, "bang_complete", MomentAction::BangStage(BangStage::Complete)),
-        Moment::new(10.0, "start_radiation", MomentAction::StartLayer("radiation".into())),
+        Moment::new(0.5, "start_radiation", MomentAction::StartLayer("radiation".into()))
+            .relative_to(MomentAnchor::BangStage(BangStage::Complete)),
 
         // ====================================================================
         // AWAKENING PHASE (12-27s) - staggered moments
@@ -150,11 +291,16 @@ pub fn create_moment_schedule() -> Vec<Moment> {
         Moment::new(87.0, "camera_pullback", MomentAction::SetCameraBehavior(CameraBehavior::Pullback)),
         Moment::new(90.0, "stop_radiation", MomentAction::StopLayer("radiation".into())),
         Moment::new(95.0, "child_fades", MomentAction::FadeTraveler(TravelerId::Child)),
-        Moment::new(95.6, "grief_shake", MomentAction::CameraShake { intensity: 0.2, duration: 1.0 }),
-        Moment::new(96.1, "archivist_grief", MomentAction::TriggerGrief {
+        Moment::new(0.6, "grief_shake", MomentAction::CameraShake { intensity: 0.2, duration: 1.0 })
+            .relative_to(MomentAnchor::TravelerFading(TravelerId::Child)),
+        // Grief follows the child's fade rather than a fixed time, so it stays
+        // in step with TravelerVisibility::speed
+        Moment::new(0.0, "archivist_grief", MomentAction::TriggerGrief {
             mourner: TravelerId::Archivist,
             deceased: TravelerId::Child,
-        }),
+        })
+        .relative_to(MomentAnchor::TravelerDimmed { traveler: TravelerId::Child, below: 0.9 })
+        .when(MomentGuard::TravelerIs(TravelerId::Archivist, TravelerState::Active)),
         Moment::new(105.0, "wanderer_fades", MomentAction::FadeTraveler(TravelerId::Wanderer)),
         Moment::new(112.0, "keeper_fades", MomentAction::FadeTraveler(TravelerId::Keeper)),
         Moment::new(120.0, "archivist_fades", MomentAction::FadeTraveler(TravelerId::Archivist)),
@@ -169,241 +315,269 @@ pub fn create_moment_schedule() -> Vec<Moment> {
     ]
 }
 
+/// Where the active moment schedule came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MomentSource {
+    /// Compiled-in schedule from `create_moment_schedule()`
+    Builtin,
+    /// Loaded from a timeline data file
+    File(String),
+    /// Rebuilt from a recorded wide-event log
+    Replay(String),
+}
+
 /// The phase controller resource
 #[derive(Resource)]
 pub struct PhaseController {
     pub moments: Vec<Moment>,
+    pub source: MomentSource,
 }
 
 impl Default for PhaseController {
     fn default() -> Self {
         Self {
             moments: create_moment_schedule(),
+            source: MomentSource::Builtin,
         }
     }
 }
 
-/// Check and trigger scheduled moments
-#[allow(clippy::too_many_arguments)]
-pub fn process_moments(
-    clock: Res<ExperienceClock>,
-    mut controller: ResMut<PhaseController>,
-    mut moment_events: EventWriter<MomentEvent>,
-    mut traveler_spawn_events: EventWriter<TravelerSpawnedEvent>,
-    mut traveler_fade_events: EventWriter<TravelerFadingEvent>,
-    mut camera_behavior_events: EventWriter<CameraBehaviorChangedEvent>,
-    mut camera_shake_events: EventWriter<CameraShakeEvent>,
-    mut bang_events: EventWriter<BangEvent>,
-    mut glitch_events: EventWriter<GlitchEvent>,
-    mut text_events: EventWriter<DisplayTextEvent>,
-    mut hide_text_events: EventWriter<HideTextEvent>,
-    mut signal_events: EventWriter<SignalOverlayEvent>,
-    mut audio_events: EventWriter<AudioLayerEvent>,
-    mut leitmotif_events: EventWriter<PlayLeitmotifEvent>,
-    mut grief_events: EventWriter<TravelerGriefEvent>,
-    mut sync_events: EventWriter<TravelersSyncedEvent>,
-) {
-    let elapsed = clock.elapsed();
-
-    for moment in controller.moments.iter_mut() {
-        // Skip already triggered
-        if moment.triggered {
-            continue;
+impl PhaseController {
+    /// Cut the schedule's times are mapped onto: the clock's, except when
+    /// replaying, since recorded moments carry the clock times they fired at
+    pub fn schedule_table(&self, clock: &ExperienceClock) -> PhaseTable {
+        match self.source {
+            MomentSource::Replay(_) => PhaseTable::default(),
+            _ => clock.phase_table().clone(),
         }
+    }
 
-        // Check if it's time
-        if elapsed >= moment.time {
-            moment.triggered = true;
-
-            // Log the moment
-            wide_event!("moment_triggered")
-                .with_str("name", moment.name)
-                .with_f32("scheduled_at", moment.time)
-                .emit(elapsed);
-
-            // Fire MomentEvent
-            moment_events.send(MomentEvent {
-                name: moment.name.to_string(),
-                elapsed,
-                phase: clock.phase(),
-            });
-
-            // Dispatch to appropriate event based on action
-            dispatch_moment_action(
-                &moment.action,
-                elapsed,
-                &mut traveler_spawn_events,
-                &mut traveler_fade_events,
-                &mut camera_behavior_events,
-                &mut camera_shake_events,
-                &mut bang_events,
-                &mut glitch_events,
-                &mut text_events,
-                &mut hide_text_events,
-                &mut signal_events,
-                &mut audio_events,
-                &mut leitmotif_events,
-                &mut grief_events,
-                &mut sync_events,
-            );
+    /// Load the schedule from a timeline file, falling back to the built-in schedule
+    pub fn load_or_builtin(path: &str) -> Self {
+        match load_timeline(path) {
+            Ok(moments) => {
+                info!(
+                    target: "lightwatch::timeline",
+                    "Loaded {} moments from {}",
+                    moments.len(),
+                    path
+                );
+                Self {
+                    moments,
+                    source: MomentSource::File(path.to_string()),
+                }
+            }
+            Err(err) => {
+                error!(
+                    target: "lightwatch::timeline",
+                    "Failed to load timeline, using built-in schedule: {}",
+                    err
+                );
+                Self::default()
+            }
         }
     }
 }
 
-/// Dispatch a moment action to the appropriate event
-#[allow(clippy::too_many_arguments)]
-fn dispatch_moment_action(
-    action: &MomentAction,
-    elapsed: f32,
-    traveler_spawn_events: &mut EventWriter<TravelerSpawnedEvent>,
-    traveler_fade_events: &mut EventWriter<TravelerFadingEvent>,
-    camera_behavior_events: &mut EventWriter<CameraBehaviorChangedEvent>,
-    camera_shake_events: &mut EventWriter<CameraShakeEvent>,
-    bang_events: &mut EventWriter<BangEvent>,
-    glitch_events: &mut EventWriter<GlitchEvent>,
-    text_events: &mut EventWriter<DisplayTextEvent>,
-    hide_text_events: &mut EventWriter<HideTextEvent>,
-    signal_events: &mut EventWriter<SignalOverlayEvent>,
-    audio_events: &mut EventWriter<AudioLayerEvent>,
-    leitmotif_events: &mut EventWriter<PlayLeitmotifEvent>,
-    grief_events: &mut EventWriter<TravelerGriefEvent>,
-    sync_events: &mut EventWriter<TravelersSyncedEvent>,
+/// Replace the built-in schedule with the recording being replayed or the timeline file
+pub fn load_moment_schedule(
+    config: Res<TimelineConfig>,
+    replay: Option<Res<ReplayLog>>,
+    log: Res<WideEventLog>,
+    mut controller: ResMut<PhaseController>,
 ) {
-    match action {
-        MomentAction::SpawnTraveler(id) => {
-            traveler_spawn_events.send(TravelerSpawnedEvent {
-                id: *id,
-                position: Vec3::ZERO, // Position determined by spawn system
-                elapsed,
-            });
-        }
-
-        MomentAction::FadeTraveler(id) => {
-            traveler_fade_events.send(TravelerFadingEvent {
-                id: *id,
-                elapsed,
-            });
-        }
-
-        MomentAction::SetCameraBehavior(behavior) => {
-            camera_behavior_events.send(CameraBehaviorChangedEvent {
-                from: CameraBehavior::Static, // Will be tracked by camera system
-                to: *behavior,
-                elapsed,
-            });
-        }
-
-        MomentAction::CameraShake { intensity, duration } => {
-            camera_shake_events.send(CameraShakeEvent {
-                intensity: *intensity,
-                duration: *duration,
-                elapsed,
-            });
-        }
-
-        MomentAction::FocusOn(target) => {
-            // CameraFocusEvent - not yet wired, but action exists
-            let _ = target; // Placeholder until camera system implements
-        }
-
-        MomentAction::PlayNote { freq, dur, vol } => {
-            // PlayNoteEvent - not yet wired
-            let _ = (freq, dur, vol); // Placeholder until audio system implements
-        }
-
-        MomentAction::BangStage(stage) => {
-            bang_events.send(BangEvent {
-                stage: *stage,
-                elapsed,
-            });
-        }
-
-        MomentAction::Glitch { intensity, duration } => {
-            glitch_events.send(GlitchEvent {
-                intensity: *intensity,
-                duration: *duration,
-                elapsed,
-            });
+    *controller = match replay.map(|log| (log.path.display().to_string(), log.moments())) {
+        Some((path, Ok(moments))) => PhaseController {
+            moments,
+            source: MomentSource::Replay(path),
+        },
+        Some((path, Err(err))) => {
+            error!(
+                target: "lightwatch::replay",
+                "Cannot replay {}, using the timeline instead: {}",
+                path,
+                err
+            );
+            PhaseController::load_or_builtin(&config.path)
         }
+        None => PhaseController::load_or_builtin(&config.path),
+    };
+
+    let source = match &controller.source {
+        MomentSource::Builtin => "builtin".to_string(),
+        MomentSource::File(path) => path.clone(),
+        MomentSource::Replay(path) => format!("replay:{}", path),
+    };
+
+    wide_event!("timeline_loaded")
+        .with_str("source", source)
+        .with_i64("moments", controller.moments.len() as i64)
+        .emit(&log, 0.0);
+}
 
-        MomentAction::ShowText { text, traveler } => {
-            text_events.send(DisplayTextEvent {
-                text: text.clone(),
-                traveler: *traveler,
-                style: NarrativeTextStyle::Normal,
-                elapsed,
-            });
-        }
+/// Let every moment fire again (on `ExperienceReset`)
+pub fn rearm_moments(mut controller: ResMut<PhaseController>) {
+    for moment in controller.moments.iter_mut() {
+        moment.triggered = false;
+        moment.anchored_at = None;
+    }
+}
 
-        MomentAction::HideText => {
-            hide_text_events.send(HideTextEvent);
-        }
+/// What moments read besides their schedule: simulation anchors and guard state
+#[derive(SystemParam)]
+pub struct MomentContext<'w, 's> {
+    clock: Res<'w, ExperienceClock>,
+    faded_events: EventReader<'w, 's, TravelerFadedEvent>,
+    travelers: Query<
+        'w,
+        's,
+        (
+            &'static Traveler,
+            &'static TravelerState,
+            &'static TravelerVisibility,
+        ),
+    >,
+    transmissions: Query<'w, 's, &'static Transmission>,
+    text_queue: Option<Res<'w, TransmissionQueue>>,
+}
 
-        MomentAction::SignalTypewriter(text) => {
-            signal_events.send(SignalOverlayEvent {
-                action: SignalAction::Typewriter(text.clone()),
-                elapsed,
-            });
-        }
+impl MomentContext<'_, '_> {
+    fn traveler(&self, id: TravelerId) -> Option<(&TravelerState, &TravelerVisibility)> {
+        self.travelers
+            .iter()
+            .find(|(traveler, _, _)| traveler.id == id)
+            .map(|(_, state, visibility)| (state, visibility))
+    }
 
-        MomentAction::StartLayer(layer) => {
-            audio_events.send(AudioLayerEvent {
-                layer: layer.clone(),
-                action: AudioAction::Start,
-                elapsed,
-            });
+    /// When the simulation set `anchor`, if it has; `faded` holds this step's faded travelers
+    fn simulated_anchor(&self, anchor: &MomentAnchor, faded: &[(TravelerId, f32)]) -> Option<f32> {
+        match anchor {
+            MomentAnchor::TravelerFaded(id) => faded
+                .iter()
+                .find(|(faded_id, _)| faded_id == id)
+                .map(|(_, at)| *at),
+            MomentAnchor::TravelerDimmed { traveler, below } => {
+                let (state, visibility) = self.traveler(*traveler)?;
+                let fading = matches!(state, TravelerState::Fading | TravelerState::Gone);
+                (fading && visibility.opacity < *below).then(|| self.clock.elapsed())
+            }
+            _ => None,
         }
+    }
 
-        MomentAction::StopLayer(layer) => {
-            // Use FadeOut instead of Stop for smooth crossfades
-            audio_events.send(AudioLayerEvent {
-                layer: layer.clone(),
-                action: AudioAction::FadeOut,
-                elapsed,
-            });
+    fn guard_holds(&self, guard: &MomentGuard) -> bool {
+        match guard {
+            MomentGuard::TravelerIs(id, wanted) => {
+                self.traveler(*id).is_some_and(|(state, _)| state == wanted)
+            }
+            MomentGuard::NoText => {
+                let queued = self.text_queue.as_ref().is_some_and(|queue| !queue.is_empty());
+                let showing = self
+                    .transmissions
+                    .iter()
+                    .any(|transmission| transmission.state != TransmissionState::Complete);
+                !queued && !showing
+            }
         }
+    }
+}
 
-        MomentAction::PlayLeitmotif(motif) => {
-            leitmotif_events.send(PlayLeitmotifEvent {
-                motif: motif.clone(),
-                traveler: None,
-                elapsed,
-            });
+/// Check and trigger scheduled moments (exclusive, so handlers can send any event)
+pub fn process_moments(
+    world: &mut World,
+    state: &mut SystemState<(
+        MomentContext,
+        ResMut<PhaseController>,
+        EventWriter<MomentEvent>,
+    )>,
+) {
+    let log = world.resource::<WideEventLog>().clone();
+    let (mut context, mut controller, mut moment_events) = state.get_mut(world);
+    let elapsed = context.clock.elapsed();
+    let table = controller.schedule_table(&context.clock);
+
+    // Anchors set by the simulation rather than by other moments
+    let faded: Vec<(TravelerId, f32)> = context
+        .faded_events
+        .read()
+        .map(|event| (event.id, event.elapsed))
+        .collect();
+    for moment in controller.moments.iter_mut() {
+        if moment.triggered || moment.anchored_at.is_some() {
+            continue;
         }
-
-        MomentAction::TriggerGrief { mourner, deceased } => {
-            grief_events.send(TravelerGriefEvent {
-                mourner: *mourner,
-                deceased: *deceased,
-                elapsed,
-            });
+        if let Some(anchor) = &moment.after {
+            moment.anchored_at = context.simulated_anchor(anchor, &faded);
         }
+    }
 
-        MomentAction::SyncTravelers(travelers) => {
-            sync_events.send(TravelersSyncedEvent {
-                participants: travelers.clone(),
-                elapsed,
-            });
+    // A fired moment can bring others due in the same step, so look again after each
+    let mut fired = Vec::new();
+    while let Some(index) = controller
+        .moments
+        .iter()
+        .position(|moment| {
+            !moment.triggered && moment.due(&table).is_some_and(|due| elapsed >= due)
+        })
+    {
+        let moment = &mut controller.moments[index];
+        moment.triggered = true;
+        let due = moment.due(&table).unwrap_or(elapsed);
+
+        if let Some(guard) = moment.only_if.iter().find(|guard| !context.guard_holds(guard)) {
+            debug!(
+                target: "lightwatch::timeline",
+                "Skipped moment '{}': {:?} does not hold",
+                moment.name,
+                guard
+            );
+            wide_event!("moment_skipped")
+                .with_str("name", moment.name.clone())
+                .with_str("guard", ron::to_string(guard).unwrap_or_default())
+                .emit(&log, elapsed);
+            continue;
         }
 
-        MomentAction::EnvironmentChange { layer, action } => {
-            // EnvironmentEvent - not yet wired
-            let _ = (layer, action); // Placeholder
+        // Log the moment
+        // The action is recorded so a replay can rebuild the schedule
+        wide_event!("moment_triggered")
+            .with_str("name", moment.name.clone())
+            .with_str("action", ron::to_string(&moment.action).unwrap_or_default())
+            .with_f32("scheduled_at", due)
+            .emit(&log, elapsed);
+
+        // Fire MomentEvent
+        moment_events.send(MomentEvent {
+            name: moment.name.clone(),
+            elapsed,
+            phase: context.clock.phase(),
+        });
+        fired.push((moment.name.clone(), moment.action.clone()));
+
+        // Anchor moments waiting on this one to its scheduled time
+        let source = &controller.moments[index];
+        let anchored: Vec<usize> = controller
+            .moments
+            .iter()
+            .enumerate()
+            .filter(|(_, moment)| !moment.triggered && moment.anchored_at.is_none())
+            .filter(|(_, moment)| {
+                moment
+                    .after
+                    .as_ref()
+                    .is_some_and(|anchor| anchor.is_set_by(source))
+            })
+            .map(|(waiting, _)| waiting)
+            .collect();
+        for waiting in anchored {
+            controller.moments[waiting].anchored_at = Some(due);
         }
     }
-}
+    state.apply(world);
 
-/// Reset controller when experience restarts (dev only)
-#[cfg(debug_assertions)]
-pub fn reset_controller_on_restart(
-    mut controller: ResMut<PhaseController>,
-    clock: Res<ExperienceClock>,
-) {
-    // If clock jumped backwards, reset triggered moments
-    for moment in controller.moments.iter_mut() {
-        if moment.triggered && clock.elapsed() < moment.time {
-            moment.triggered = false;
-        }
+    // Hand each action to the plugin that owns it
+    for (name, action) in fired {
+        dispatch_moment_action(world, &name, &action, elapsed);
     }
 }
 
@@ -412,10 +586,19 @@ pub struct PhaseControllerPlugin;
 
 impl Plugin for PhaseControllerPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<PhaseController>()
-            .add_systems(Update, process_moments.in_set(super::RunningSet));
-
-        #[cfg(debug_assertions)]
-        app.add_systems(Update, reset_controller_on_restart);
+        app.init_resource::<TimelineConfig>()
+            .init_resource::<PhaseController>()
+            .init_resource::<MomentActionRegistry>()
+            .add_systems(
+                Startup,
+                (load_moment_schedule, report_unhandled_moment_actions).chain(),
+            )
+            .add_systems(super::ExperienceReset, rearm_moments)
+            .add_systems(
+                FixedUpdate,
+                process_moments
+                    .after(super::update_clock)
+                    .in_set(super::RunningSet),
+            );
     }
 }
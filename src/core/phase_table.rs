@@ -0,0 +1,343 @@
+//! Phase table - phase boundaries, total duration and per-phase exposure
+//!
+//! The clock, timeline verification, dev phase jumps and exposure all read
+//! phase timing from the `PhaseTable` resource. The standard 143-second cut
+//! is built in; other cuts are authored in RON (`assets/timeline/phases.ron`)
+//! and loaded at startup, falling back to the standard table on any error.
+
+use std::path::Path;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::clock::{Phase, EXPERIENCE_DURATION, PHASE_TRANSITION_BUFFER};
+use super::easing::smooth_step;
+use super::logging::WideEventLog;
+use super::timeline_data::{TimelineError, TimelineIssue};
+use crate::wide_event;
+
+/// Default location of the phase table file
+pub const DEFAULT_PHASE_TABLE_PATH: &str = "assets/timeline/phases.ron";
+
+/// Phases a table must list, in order (`Ended` starts at the table's duration)
+pub const TIMED_PHASES: [Phase; 6] = [
+    Phase::Signal,
+    Phase::Bang,
+    Phase::Awakening,
+    Phase::Discovery,
+    Phase::Connection,
+    Phase::Acceptance,
+];
+
+/// One phase's entry in the table; it lasts until the next phase starts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhaseSpan {
+    pub phase: Phase,
+    /// Start time in seconds
+    pub start: f32,
+    /// Target exposure (EV100) while the phase is active
+    pub exposure: f32,
+}
+
+/// Phase boundaries and total duration of one cut of the experience
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhaseTable {
+    /// Total length in seconds; `Phase::Ended` begins here
+    pub duration: f32,
+    pub phases: Vec<PhaseSpan>,
+}
+
+impl Default for PhaseTable {
+    /// The standard 143-second cut
+    fn default() -> Self {
+        let span = |phase, start, exposure| PhaseSpan {
+            phase,
+            start,
+            exposure,
+        };
+
+        Self {
+            duration: EXPERIENCE_DURATION,
+            phases: vec![
+                span(Phase::Signal, 0.0, 0.5),       // Dark, waiting
+                span(Phase::Bang, 2.0, 15.0),        // Blindingly bright at peak
+                span(Phase::Awakening, 12.0, 8.0),   // Settling down
+                span(Phase::Discovery, 27.0, 10.0),  // Normal exposure
+                span(Phase::Connection, 57.0, 12.0), // Warm, bright
+                span(Phase::Acceptance, 87.0, 6.0),  // Dimming toward darkness
+            ],
+        }
+    }
+}
+
+impl PhaseTable {
+    /// Total length in seconds
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    /// Phase active at `elapsed` seconds
+    pub fn phase_at(&self, elapsed: f32) -> Phase {
+        if elapsed >= self.duration {
+            return Phase::Ended;
+        }
+
+        self.phases
+            .iter()
+            .rev()
+            .find(|span| elapsed >= span.start)
+            .map(|span| span.phase)
+            .unwrap_or(Phase::Signal)
+    }
+
+    fn index_of(&self, phase: Phase) -> Option<usize> {
+        self.phases.iter().position(|span| span.phase == phase)
+    }
+
+    /// Start time of a phase
+    pub fn start(&self, phase: Phase) -> f32 {
+        match self.index_of(phase) {
+            Some(index) => self.phases[index].start,
+            None => self.duration,
+        }
+    }
+
+    /// End time of a phase (the next phase's start)
+    pub fn end(&self, phase: Phase) -> f32 {
+        match self.index_of(phase) {
+            Some(index) => self
+                .phases
+                .get(index + 1)
+                .map(|next| next.start)
+                .unwrap_or(self.duration),
+            None => f32::INFINITY,
+        }
+    }
+
+    /// Length of a phase in seconds
+    pub fn phase_duration(&self, phase: Phase) -> f32 {
+        self.end(phase) - self.start(phase)
+    }
+
+    /// Progress within `phase` at `elapsed` (0.0 to 1.0)
+    pub fn progress(&self, phase: Phase, elapsed: f32) -> f32 {
+        ((elapsed - self.start(phase)) / self.phase_duration(phase)).clamp(0.0, 1.0)
+    }
+
+    /// Smooth entry factor (0.0 at phase start, 1.0 after the transition buffer)
+    pub fn entry_factor(&self, phase: Phase, elapsed: f32) -> f32 {
+        let time_in_phase = elapsed - self.start(phase);
+        if time_in_phase < 0.0 {
+            return 0.0;
+        }
+        smooth_step((time_in_phase / PHASE_TRANSITION_BUFFER).clamp(0.0, 1.0))
+    }
+
+    /// Smooth exit factor (1.0 normally, 0.0 at phase end)
+    pub fn exit_factor(&self, phase: Phase, elapsed: f32) -> f32 {
+        let time_until_end = self.end(phase) - elapsed;
+        if time_until_end < 0.0 {
+            return 0.0;
+        }
+        smooth_step((time_until_end / PHASE_TRANSITION_BUFFER).clamp(0.0, 1.0))
+    }
+
+    /// Map a time authored against the standard cut onto this table
+    ///
+    /// The result sits at the same point within the same phase, so cues keep
+    /// their place when phases are stretched or shortened. Times past the
+    /// standard end keep their distance from the end.
+    pub fn rescale(&self, standard_time: f32) -> f32 {
+        let standard = PhaseTable::default();
+        let phase = standard.phase_at(standard_time);
+        if phase == Phase::Ended {
+            return self.duration + (standard_time - standard.duration);
+        }
+
+        let ratio = self.phase_duration(phase) / standard.phase_duration(phase);
+        self.start(phase) + (standard_time - standard.start(phase)) * ratio
+    }
+
+    /// Is `elapsed` within the entry buffer of `phase`?
+    pub fn is_entering(&self, phase: Phase, elapsed: f32) -> bool {
+        (0.0..PHASE_TRANSITION_BUFFER).contains(&(elapsed - self.start(phase)))
+    }
+
+    /// Is `elapsed` within the exit buffer of `phase`?
+    pub fn is_exiting(&self, phase: Phase, elapsed: f32) -> bool {
+        (0.0..PHASE_TRANSITION_BUFFER).contains(&(self.end(phase) - elapsed))
+    }
+
+    /// Target exposure for a phase (EV100)
+    pub fn exposure(&self, phase: Phase) -> f32 {
+        self.index_of(phase)
+            .map(|index| self.phases[index].exposure)
+            .unwrap_or(10.0)
+    }
+
+    /// Structural problems: missing or out-of-order phases, bad times
+    pub fn validate(&self) -> Vec<TimelineIssue> {
+        let mut issues = Vec::new();
+        let mut report = |phase: &str, message: String| {
+            issues.push(TimelineIssue {
+                line: None,
+                moment: phase.to_string(),
+                message,
+            });
+        };
+
+        let listed: Vec<Phase> = self.phases.iter().map(|span| span.phase).collect();
+        if listed != TIMED_PHASES {
+            let expected: Vec<&str> = TIMED_PHASES.iter().map(|p| p.name()).collect();
+            report(
+                "phases",
+                format!(
+                    "phases must be listed once each, in order: {}",
+                    expected.join(", ")
+                ),
+            );
+        }
+
+        if !self.duration.is_finite() || self.duration <= 0.0 {
+            report(
+                "duration",
+                format!("duration {} must be positive", self.duration),
+            );
+        }
+
+        let mut previous: Option<f32> = None;
+        for span in &self.phases {
+            let name = span.phase.name();
+            if !span.start.is_finite() {
+                report(name, format!("start {} is not a number", span.start));
+                continue;
+            }
+            if previous.is_none() && span.start != 0.0 {
+                report(
+                    name,
+                    format!("first phase must start at 0, not {}", span.start),
+                );
+            }
+            if let Some(previous) = previous {
+                if span.start <= previous {
+                    report(
+                        name,
+                        format!("starts at {}s, not after the previous phase", span.start),
+                    );
+                }
+            }
+            if span.start >= self.duration {
+                report(
+                    name,
+                    format!(
+                        "starts at {}s, after the {}s duration",
+                        span.start, self.duration
+                    ),
+                );
+            }
+            previous = Some(span.start);
+        }
+
+        issues
+    }
+}
+
+/// Which phase table file to load at startup
+#[derive(Resource, Debug, Clone)]
+pub struct PhaseTableConfig {
+    pub path: String,
+}
+
+impl Default for PhaseTableConfig {
+    fn default() -> Self {
+        Self {
+            path: DEFAULT_PHASE_TABLE_PATH.to_string(),
+        }
+    }
+}
+
+/// Read, parse and validate a phase table file
+pub fn load_phase_table(path: impl AsRef<Path>) -> Result<PhaseTable, TimelineError> {
+    let path = path.as_ref();
+    let source = std::fs::read_to_string(path).map_err(|err| TimelineError::Io {
+        path: path.display().to_string(),
+        message: err.to_string(),
+    })?;
+
+    parse_phase_table(&source)
+}
+
+/// Parse and validate phase table source text
+pub fn parse_phase_table(source: &str) -> Result<PhaseTable, TimelineError> {
+    let table: PhaseTable = ron::de::from_str(source).map_err(|err| TimelineError::Parse {
+        line: err.position.line,
+        column: err.position.col,
+        message: err.code.to_string(),
+    })?;
+
+    let issues = table.validate();
+    if !issues.is_empty() {
+        return Err(TimelineError::Invalid(issues));
+    }
+
+    Ok(table)
+}
+
+/// Replace the standard table with the configured file, if it loads
+pub fn load_phase_table_file(
+    config: Res<PhaseTableConfig>,
+    log: Res<WideEventLog>,
+    mut table: ResMut<PhaseTable>,
+) {
+    match load_phase_table(&config.path) {
+        Ok(loaded) => {
+            info!(
+                target: "lightwatch::timeline",
+                "Loaded phase table from {} ({:.0}s)",
+                config.path,
+                loaded.duration
+            );
+            *table = loaded;
+        }
+        Err(err) => {
+            error!(
+                target: "lightwatch::timeline",
+                "Failed to load phase table {}, using the standard cut: {}",
+                config.path,
+                err
+            );
+        }
+    }
+
+    wide_event!("phase_table_loaded")
+        .with_f32("duration", table.duration)
+        .emit(&log, 0.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rescale_keeps_times_on_the_standard_cut() {
+        let table = PhaseTable::default();
+        for time in [0.0, 2.0, 2.5, 10.0, 12.0, 57.0, 110.0, 135.0, 143.0] {
+            assert_eq!(table.rescale(time), time);
+        }
+    }
+
+    #[test]
+    fn rescale_follows_stretched_phases() {
+        // Acceptance runs 87..173 instead of 87..143
+        let table = PhaseTable {
+            duration: 173.0,
+            ..default()
+        };
+
+        assert_eq!(table.rescale(57.0), 57.0);
+        assert_eq!(table.rescale(87.0), 87.0);
+        assert_eq!(table.rescale(115.0), 130.0);
+        assert_eq!(table.rescale(143.0), 173.0);
+        assert_eq!(table.rescale(150.0), 180.0);
+    }
+}
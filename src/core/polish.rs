@@ -5,7 +5,7 @@ use bevy::render::camera::ClearColorConfig;
 
 use crate::camera::ExperienceCamera;
 
-use super::ExperienceClock;
+use super::{ExperienceClock, ResetExperience};
 
 /// Track fade state
 #[derive(Resource, Default)]
@@ -24,9 +24,10 @@ pub fn fade_visuals_at_end(
 ) {
     let elapsed = clock.elapsed();
 
-    // Start fade at 141 seconds
-    if elapsed >= 141.0 {
-        let fade = ((elapsed - 141.0) / 2.0).clamp(0.0, 1.0);
+    // Start fade 2 seconds before the end (141s in the standard cut)
+    let fade_start = clock.duration() - 2.0;
+    if elapsed >= fade_start {
+        let fade = ((elapsed - fade_start) / 2.0).clamp(0.0, 1.0);
         fade_state.fade_opacity = fade;
 
         if !fade_state.visual_fade_started && fade > 0.0 {
@@ -46,14 +47,26 @@ pub fn fade_visuals_at_end(
     }
 }
 
+/// Undo the fade to black (on `ExperienceReset`)
+pub fn clear_end_fade(mut cameras: Query<&mut Camera, With<ExperienceCamera>>) {
+    for mut camera in cameras.iter_mut() {
+        camera.clear_color = ClearColorConfig::Default;
+    }
+}
+
 /// Log when experience is about to end
 pub fn log_experience_ending(
     clock: Res<ExperienceClock>,
+    mut resets: EventReader<ResetExperience>,
     mut logged: Local<bool>,
 ) {
+    if resets.read().count() > 0 {
+        *logged = false;
+    }
+
     let elapsed = clock.elapsed();
 
-    if elapsed >= 140.0 && !*logged {
+    if elapsed >= clock.duration() - 3.0 && !*logged {
         *logged = true;
         info!(target: "lightwatch::polish", "Experience ending in 3 seconds...");
     }
@@ -1,21 +1,38 @@
-//! Ready screen - "click to begin" overlay
+//! Ready screen - "click to begin" overlay with content notes and settings
 
 use bevy::prelude::*;
 
+use super::loading::LoadingTracker;
+use super::settings_menu::spawn_settings_menu;
+use crate::text::TextConfig;
+
 /// Marker for the ready screen
 #[derive(Component)]
 pub struct ReadyScreen;
 
 /// Spawn the "click to begin" overlay
-pub fn spawn_ready_screen(mut commands: Commands) {
+pub fn spawn_ready_screen(
+    mut commands: Commands,
+    text: Option<Res<TextConfig>>,
+    loading: Option<Res<LoadingTracker>>,
+) {
+    let font = text.map(|text| text.font.clone()).unwrap_or_default();
+    let style = |font_size: f32, alpha: f32| TextStyle {
+        font: font.clone(),
+        font_size,
+        color: Color::srgba(0.9, 0.87, 0.82, alpha),
+    };
+
     commands
         .spawn((
             NodeBundle {
                 style: Style {
                     width: Val::Percent(100.0),
                     height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
+                    row_gap: Val::Px(12.0),
                     ..default()
                 },
                 background_color: BackgroundColor(Color::BLACK),
@@ -24,14 +41,37 @@ pub fn spawn_ready_screen(mut commands: Commands) {
             ReadyScreen,
         ))
         .with_children(|parent| {
-            parent.spawn(TextBundle::from_section(
-                "click to begin",
-                TextStyle {
-                    font_size: 18.0,
-                    color: Color::srgba(0.9, 0.87, 0.82, 0.6),
+            parent.spawn(TextBundle::from_section("click to begin", style(18.0, 0.6)));
+
+            // Shown before the bang, which is a bright full-screen flash
+            parent.spawn(
+                TextBundle::from_section(
+                    "contains bright flashing light",
+                    style(14.0, 0.45),
+                )
+                .with_style(Style {
+                    margin: UiRect::top(Val::Px(24.0)),
                     ..default()
-                },
+                }),
+            );
+            parent.spawn(TextBundle::from_section(
+                "best with headphones",
+                style(14.0, 0.45),
             ));
+            parent.spawn(TextBundle::from_section("S  settings", style(12.0, 0.3)));
+
+            // Optional parts that failed to load (the run goes ahead without them)
+            for (task, error) in loading.iter().flat_map(|loading| loading.failures()) {
+                parent.spawn(TextBundle::from_section(
+                    format!("{} unavailable: {}", task.name, error),
+                    TextStyle {
+                        color: Color::srgba(0.95, 0.45, 0.4, 0.6),
+                        ..style(12.0, 0.6)
+                    },
+                ));
+            }
+
+            spawn_settings_menu(parent, font.clone());
         });
 }
 
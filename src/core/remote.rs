@@ -0,0 +1,417 @@
+//! Remote control - OSC over UDP for show controllers and lighting desks
+//!
+//! With `RemoteConfig::listen` set, OSC messages sent to that address drive
+//! the piece:
+//!
+//! - `/lightwatch/start` - begin from the ready screen, or resume after stop
+//! - `/lightwatch/stop` - pause the clock
+//! - `/lightwatch/seek <seconds>` - seek through `ExperienceController`
+//! - `/lightwatch/speed <scale>` - set `TimeControl::speed`
+//! - `/lightwatch/reset` - reset the world and return to the ready screen
+//! - `/lightwatch/loop <0|1>` - turn installation loop mode off or on
+//!
+//! Each `TELEMETRY_EVENTS` wide event is sent as an OSC message to every
+//! `RemoteConfig::telemetry` address.
+
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+
+use bevy::ecs::system::SystemState;
+use bevy::prelude::*;
+
+use super::event_sinks::{WideEventSink, WideEventSinks};
+use super::input::{hide_cursor_for_run, InputConfig};
+use super::installation::{LoopConfig, LoopState};
+use super::logging::{WideEvent, WideEventLog, WideValue};
+use super::osc::{decode_packet, OscArg, OscError, OscMessage};
+use super::reset::{reset_experience, ResetReason};
+use super::state::request_transition_in;
+use super::{ExperienceClock, ExperienceController, ExperienceState, TimeControl};
+use crate::wide_event;
+
+/// Address prefix for every command and telemetry message
+pub const OSC_PREFIX: &str = "/lightwatch";
+
+/// Wide events forwarded as telemetry
+pub const TELEMETRY_EVENTS: &[&str] = &["phase_changed", "moment_triggered"];
+
+/// Largest packet read from the socket
+const MAX_PACKET_SIZE: usize = 1536;
+
+/// Remote control settings; insert before `LightwatchPlugin` to enable
+#[derive(Resource, Debug, Clone, Default)]
+pub struct RemoteConfig {
+    /// Receive commands on this address
+    pub listen: Option<SocketAddr>,
+    /// Send telemetry to these addresses
+    pub telemetry: Vec<SocketAddr>,
+}
+
+/// A command received from a show controller
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RemoteCommand {
+    Start,
+    Stop,
+    Seek(f32),
+    Speed(f32),
+    Reset,
+    Loop(bool),
+}
+
+impl RemoteCommand {
+    /// Parse a message addressed under `OSC_PREFIX`
+    pub fn from_message(message: &OscMessage) -> Result<Self, String> {
+        let Some(command) = message
+            .address
+            .strip_prefix(OSC_PREFIX)
+            .and_then(|rest| rest.strip_prefix('/'))
+        else {
+            return Err(format!("unknown address {}", message.address));
+        };
+
+        let number = || {
+            message
+                .args
+                .first()
+                .and_then(OscArg::as_f32)
+                .filter(|value| value.is_finite())
+                .ok_or_else(|| format!("{} needs a number", message.address))
+        };
+
+        match command {
+            "start" => Ok(RemoteCommand::Start),
+            "stop" => Ok(RemoteCommand::Stop),
+            "seek" => number().map(RemoteCommand::Seek),
+            "speed" => number().map(RemoteCommand::Speed),
+            "reset" => Ok(RemoteCommand::Reset),
+            // A bare /loop turns looping on
+            "loop" => Ok(RemoteCommand::Loop(
+                message
+                    .args
+                    .first()
+                    .map(|arg| arg.as_bool().unwrap_or(true))
+                    .unwrap_or(true),
+            )),
+            _ => Err(format!("unknown address {}", message.address)),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RemoteCommand::Start => "start",
+            RemoteCommand::Stop => "stop",
+            RemoteCommand::Seek(_) => "seek",
+            RemoteCommand::Speed(_) => "speed",
+            RemoteCommand::Reset => "reset",
+            RemoteCommand::Loop(_) => "loop",
+        }
+    }
+}
+
+/// Non-blocking UDP socket receiving OSC commands
+#[derive(Resource)]
+pub struct OscServer {
+    socket: UdpSocket,
+}
+
+impl OscServer {
+    /// Bind to `addr` (port 0 picks a free port)
+    pub fn bind(addr: SocketAddr) -> io::Result<Self> {
+        let socket = UdpSocket::bind(addr)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self { socket })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Every packet waiting on the socket, decoded
+    pub fn poll(&self) -> Vec<(SocketAddr, Result<Vec<OscMessage>, OscError>)> {
+        let mut packets = Vec::new();
+        let mut buffer = [0u8; MAX_PACKET_SIZE];
+
+        loop {
+            match self.socket.recv_from(&mut buffer) {
+                Ok((len, from)) => packets.push((from, decode_packet(&buffer[..len]))),
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => {
+                    warn!(target: "lightwatch::remote", "OSC receive failed: {}", err);
+                    break;
+                }
+            }
+        }
+
+        packets
+    }
+}
+
+/// Sends telemetry wide events as OSC messages
+pub struct OscTelemetrySink {
+    socket: UdpSocket,
+    targets: Vec<SocketAddr>,
+    failed: bool,
+}
+
+impl OscTelemetrySink {
+    pub fn new(targets: Vec<SocketAddr>) -> io::Result<Self> {
+        let socket = UdpSocket::bind(SocketAddr::from(([0, 0, 0, 0], 0)))?;
+        Ok(Self {
+            socket,
+            targets,
+            failed: false,
+        })
+    }
+
+    /// OSC form of a telemetry event: `/lightwatch/<event>`, fields first, elapsed last
+    pub fn message(event: &WideEvent) -> Option<OscMessage> {
+        if !TELEMETRY_EVENTS.contains(&event.name.as_str()) {
+            return None;
+        }
+
+        let string = |key: &str| match event.get(key) {
+            Some(WideValue::String(value)) => OscArg::String(value.clone()),
+            _ => OscArg::String(String::new()),
+        };
+        let mut args = match event.name.as_str() {
+            "phase_changed" => vec![string("from"), string("to")],
+            "moment_triggered" => vec![string("name")],
+            _ => Vec::new(),
+        };
+        args.push(OscArg::Float(event.elapsed_secs));
+
+        Some(OscMessage::new(
+            format!("{}/{}", OSC_PREFIX, event.name),
+            args,
+        ))
+    }
+}
+
+impl WideEventSink for OscTelemetrySink {
+    fn write(&mut self, event: &WideEvent) {
+        let Some(message) = Self::message(event) else {
+            return;
+        };
+
+        let packet = message.encode();
+        for target in &self.targets {
+            if let Err(err) = self.socket.send_to(&packet, target) {
+                // One error is enough; an unreachable desk would otherwise log every event
+                if !self.failed {
+                    self.failed = true;
+                    warn!(target: "lightwatch::remote", "OSC telemetry to {} failed: {}", target, err);
+                }
+            }
+        }
+    }
+}
+
+/// Apply waiting OSC commands (exclusive, runs before seeks are applied)
+pub fn receive_remote_commands(world: &mut World) {
+    let Some(server) = world.get_resource::<OscServer>() else {
+        return;
+    };
+    let packets = server.poll();
+
+    for (from, packet) in packets {
+        let messages = match packet {
+            Ok(messages) => messages,
+            Err(err) => {
+                warn!(target: "lightwatch::remote", "From {}: {}", from, err);
+                continue;
+            }
+        };
+
+        for message in messages {
+            match RemoteCommand::from_message(&message) {
+                Ok(command) => apply_remote_command(world, command, from),
+                Err(err) => warn!(target: "lightwatch::remote", "From {}: {}", from, err),
+            }
+        }
+    }
+}
+
+/// Carry out one command
+pub fn apply_remote_command(world: &mut World, command: RemoteCommand, from: SocketAddr) {
+    let elapsed = world.resource::<ExperienceClock>().elapsed();
+    let state = *world.resource::<State<ExperienceState>>().get();
+    info!(target: "lightwatch::remote", "{:?} from {}", command, from);
+
+    let mut event = wide_event!("remote_command")
+        .with_str("command", command.name())
+        .with_str("from", from.to_string());
+
+    match command {
+        RemoteCommand::Start => {
+            if world.resource::<TimeControl>().paused {
+                world.resource_mut::<TimeControl>().paused = false;
+            } else if state == ExperienceState::Ready {
+                start_run(world, "remote");
+            }
+        }
+        RemoteCommand::Stop => {
+            world.resource_mut::<TimeControl>().paused = true;
+        }
+        RemoteCommand::Seek(target) => {
+            world.resource_mut::<ExperienceController>().seek(target);
+            event = event.with_f32("value", target);
+        }
+        RemoteCommand::Speed(speed) => {
+            world.resource_mut::<TimeControl>().speed = speed.clamp(0.1, 10.0);
+            event = event.with_f32("value", speed);
+        }
+        RemoteCommand::Reset => return_to_ready(world, ResetReason::Remote),
+        RemoteCommand::Loop(enabled) => {
+            if let Some(mut config) = world.get_resource_mut::<LoopConfig>() {
+                config.enabled = enabled;
+            }
+            event = event.with_bool("value", enabled);
+        }
+    }
+
+    event.emit(world.resource::<WideEventLog>(), elapsed);
+}
+
+/// Leave the ready screen as if someone had clicked
+pub fn start_run(world: &mut World, trigger: &str) {
+    if !request_transition_in(world, ExperienceState::Running) {
+        return;
+    }
+    world.resource_mut::<ExperienceClock>().start();
+
+    let mut cursor: SystemState<(Res<InputConfig>, Query<&mut Window>)> = SystemState::new(world);
+    let (input, mut windows) = cursor.get_mut(world);
+    hide_cursor_for_run(&input, &mut windows);
+
+    wide_event!("experience_started")
+        .with_str("trigger", trigger)
+        .emit(world.resource::<WideEventLog>(), 0.0);
+}
+
+/// Reset the world, unpause and show the ready screen
+pub fn return_to_ready(world: &mut World, reason: ResetReason) {
+    reset_experience(world, reason);
+    world.resource_mut::<TimeControl>().paused = false;
+    if let Some(mut loop_state) = world.get_resource_mut::<LoopState>() {
+        loop_state.waited = 0.0;
+    }
+
+    // Already on the ready screen: the reset alone is enough
+    if *world.resource::<State<ExperienceState>>().get() != ExperienceState::Ready {
+        request_transition_in(world, ExperienceState::Ready);
+    }
+}
+
+/// Remote control plugin; does nothing unless a `RemoteConfig` is inserted before `LightwatchPlugin`
+pub struct RemotePlugin;
+
+impl Plugin for RemotePlugin {
+    fn build(&self, app: &mut App) {
+        let Some(config) = app.world().get_resource::<RemoteConfig>().cloned() else {
+            return;
+        };
+
+        if let Some(addr) = config.listen {
+            match OscServer::bind(addr) {
+                Ok(server) => {
+                    info!(
+                        target: "lightwatch::remote",
+                        "Listening for OSC on {}",
+                        server.local_addr().unwrap_or(addr)
+                    );
+                    app.insert_resource(server).add_systems(
+                        PreUpdate,
+                        receive_remote_commands.before(super::apply_pending_seek),
+                    );
+                }
+                Err(err) => error!(
+                    target: "lightwatch::remote",
+                    "Cannot listen for OSC on {}: {}",
+                    addr,
+                    err
+                ),
+            }
+        }
+
+        if !config.telemetry.is_empty() {
+            match OscTelemetrySink::new(config.telemetry.clone()) {
+                Ok(sink) => {
+                    if let Some(mut sinks) = app.world_mut().get_resource_mut::<WideEventSinks>() {
+                        sinks.add(sink);
+                    }
+                    info!(
+                        target: "lightwatch::remote",
+                        "Sending OSC telemetry to {:?}",
+                        config.telemetry
+                    );
+                }
+                Err(err) => error!(
+                    target: "lightwatch::remote",
+                    "Cannot open OSC telemetry socket: {}",
+                    err
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn server_parses_commands_from_the_network() {
+        let server = OscServer::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let to = server.local_addr().unwrap();
+
+        let sent = [
+            OscMessage::new("/lightwatch/start", Vec::new()),
+            OscMessage::new("/lightwatch/seek", vec![OscArg::Float(30.0)]),
+            OscMessage::new("/lightwatch/speed", vec![OscArg::Int(2)]),
+            OscMessage::new("/lightwatch/reset", Vec::new()),
+        ];
+        for message in &sent {
+            client.send_to(&message.encode(), to).unwrap();
+        }
+
+        // Loopback delivery is quick but not immediate
+        let mut commands = Vec::new();
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while commands.len() < sent.len() && Instant::now() < deadline {
+            for (from, packet) in server.poll() {
+                assert_eq!(from, client.local_addr().unwrap());
+                for message in packet.unwrap() {
+                    commands.push(RemoteCommand::from_message(&message).unwrap());
+                }
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        assert_eq!(
+            commands,
+            [
+                RemoteCommand::Start,
+                RemoteCommand::Seek(30.0),
+                RemoteCommand::Speed(2.0),
+                RemoteCommand::Reset,
+            ]
+        );
+    }
+
+    #[test]
+    fn commands_need_the_prefix_and_their_arguments() {
+        let parse =
+            |address: &str, args| RemoteCommand::from_message(&OscMessage::new(address, args));
+
+        assert!(parse("/other/start", Vec::new()).is_err());
+        assert!(parse("/lightwatch/seek", Vec::new()).is_err());
+        assert!(parse("/lightwatch/seek", vec![OscArg::Float(f32::NAN)]).is_err());
+        assert_eq!(parse("/lightwatch/loop", Vec::new()), Ok(RemoteCommand::Loop(true)));
+        assert_eq!(
+            parse("/lightwatch/loop", vec![OscArg::Int(0)]),
+            Ok(RemoteCommand::Loop(false))
+        );
+    }
+}
@@ -33,8 +33,3 @@ impl Default for CinematicCameraBundle {
         }
     }
 }
-
-/// Configure MSAA globally (4x for good balance)
-pub fn configure_msaa() -> Msaa {
-    Msaa::Sample4
-}
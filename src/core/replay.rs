@@ -0,0 +1,474 @@
+//! Record and replay - drive a run from a wide-event log and diff the result
+//!
+//! Record with `--events run.jsonl`. `--replay run.jsonl` then rebuilds the
+//! moment schedule from the recorded `moment_triggered` events (each carries
+//! its action), fires every moment at the time it fired in the recording,
+//! repeats recorded seeks and starts without a click. The replayed events are
+//! compared against the recording with `diff_events`.
+//!
+//! Moments replayed inside a seek are not logged, so a recording that seeks
+//! backwards only replays the moments it actually logged.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+
+use super::event_sinks::{read_json_lines, RingBufferSink, WideEventSinks};
+use super::logging::{WideEvent, WideEventLog, WideValue};
+use super::state::request_transition;
+use super::{
+    ExperienceClock, ExperienceController, ExperienceState, HeadlessMode, Moment, MomentAction,
+};
+use crate::wide_event;
+
+/// Events that describe how a run was set up rather than what happened in it
+pub const DIFF_IGNORED_EVENTS: &[&str] = &[
+    "timeline_loaded",
+    "phase_table_loaded",
+    "experience_started",
+    "remote_command",
+    "sync_correction",
+    "moment_skipped",
+];
+
+/// Fixed steps an event may drift before it counts as moved
+///
+/// Replayed moments fire at their recorded time rather than their scheduled
+/// one, which can move lifecycle events by a single step.
+pub const REPLAY_TOLERANCE_STEPS: f32 = 1.5;
+
+/// A recorded run, loaded from JSON Lines
+#[derive(Resource, Debug, Clone)]
+pub struct ReplayLog {
+    pub path: PathBuf,
+    pub events: Vec<WideEvent>,
+}
+
+impl ReplayLog {
+    /// Load a log written by `JsonLinesSink`
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let events = read_json_lines(&path)?;
+
+        Ok(Self { path, events })
+    }
+
+    /// Moment schedule as it played in the recording
+    ///
+    /// Each moment is scheduled at the time it fired, not the time it was
+    /// scheduled for, so the replay reproduces the recorded run.
+    pub fn moments(&self) -> Result<Vec<Moment>, String> {
+        self.events
+            .iter()
+            .filter(|event| event.name == "moment_triggered")
+            .map(|event| {
+                let name = match event.get("name") {
+                    Some(WideValue::String(name)) => name.clone(),
+                    _ => return Err(format!("moment at {:.2}s has no name", event.elapsed_secs)),
+                };
+                let action = match event.get("action") {
+                    Some(WideValue::String(action)) => ron::from_str::<MomentAction>(action)
+                        .map_err(|err| format!("moment '{}': bad action: {}", name, err))?,
+                    _ => return Err(format!("moment '{}' was recorded without its action", name)),
+                };
+
+                Ok(Moment::new(event.elapsed_secs, name, action))
+            })
+            .collect()
+    }
+
+    /// Recorded seeks as (clock time when requested, target)
+    pub fn seeks(&self) -> Vec<(f32, f32)> {
+        self.events
+            .iter()
+            .filter(|event| event.name == "seek")
+            .filter_map(|event| match (event.get("from"), event.get("to")) {
+                (Some(WideValue::Float(from)), Some(WideValue::Float(to))) => Some((*from, *to)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// One way a replayed run differs from its recording
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventDifference {
+    /// In the recording, not in the replay
+    Missing { signature: String, elapsed: f32 },
+    /// In the replay, not in the recording
+    Extra { signature: String, elapsed: f32 },
+    /// In both, at different times
+    Moved {
+        signature: String,
+        recorded: f32,
+        replayed: f32,
+    },
+}
+
+impl fmt::Display for EventDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventDifference::Missing { signature, elapsed } => {
+                write!(f, "missing [{:.2}s] {}", elapsed, signature)
+            }
+            EventDifference::Extra { signature, elapsed } => {
+                write!(f, "extra   [{:.2}s] {}", elapsed, signature)
+            }
+            EventDifference::Moved {
+                signature,
+                recorded,
+                replayed,
+            } => write!(
+                f,
+                "moved   [{:.2}s -> {:.2}s] {} ({:+.3}s)",
+                recorded,
+                replayed,
+                signature,
+                replayed - recorded
+            ),
+        }
+    }
+}
+
+/// Event name plus its discrete fields; floats and vectors are measurements, not identity
+fn signature(event: &WideEvent) -> String {
+    let fields: Vec<String> = event
+        .context
+        .iter()
+        .filter_map(|(key, value)| match value {
+            WideValue::String(v) => Some(format!("{}={}", key, v)),
+            WideValue::Int(v) => Some(format!("{}={}", key, v)),
+            WideValue::Bool(v) => Some(format!("{}={}", key, v)),
+            WideValue::Float(_) | WideValue::Vec3(_) => None,
+        })
+        .collect();
+
+    if fields.is_empty() {
+        event.name.clone()
+    } else {
+        format!("{} {}", event.name, fields.join(" "))
+    }
+}
+
+/// Compare two event logs
+///
+/// Events are matched by signature in order of occurrence; matched events more
+/// than `tolerance` seconds apart are reported as moved. Events named in
+/// `DIFF_IGNORED_EVENTS` are skipped. Differences are sorted by time.
+pub fn diff_events(
+    recorded: &[WideEvent],
+    replayed: &[WideEvent],
+    tolerance: f32,
+) -> Vec<EventDifference> {
+    let group = |events: &[WideEvent]| {
+        let mut groups: HashMap<String, Vec<f32>> = HashMap::new();
+        let mut order = Vec::new();
+        for event in events {
+            if DIFF_IGNORED_EVENTS.contains(&event.name.as_str()) {
+                continue;
+            }
+            let key = signature(event);
+            if !groups.contains_key(&key) {
+                order.push(key.clone());
+            }
+            groups.entry(key).or_default().push(event.elapsed_secs);
+        }
+        (groups, order)
+    };
+
+    let (recorded_groups, recorded_order) = group(recorded);
+    let (replayed_groups, replayed_order) = group(replayed);
+    let empty = Vec::new();
+    let mut differences = Vec::new();
+
+    for key in &recorded_order {
+        let before = &recorded_groups[key];
+        let after = replayed_groups.get(key).unwrap_or(&empty);
+
+        for (index, &recorded_at) in before.iter().enumerate() {
+            match after.get(index) {
+                Some(&replayed_at) if (replayed_at - recorded_at).abs() > tolerance => {
+                    differences.push(EventDifference::Moved {
+                        signature: key.clone(),
+                        recorded: recorded_at,
+                        replayed: replayed_at,
+                    });
+                }
+                Some(_) => {}
+                None => differences.push(EventDifference::Missing {
+                    signature: key.clone(),
+                    elapsed: recorded_at,
+                }),
+            }
+        }
+
+        for &replayed_at in after.iter().skip(before.len()) {
+            differences.push(EventDifference::Extra {
+                signature: key.clone(),
+                elapsed: replayed_at,
+            });
+        }
+    }
+
+    for key in replayed_order
+        .iter()
+        .filter(|key| !recorded_groups.contains_key(*key))
+    {
+        for &replayed_at in &replayed_groups[key] {
+            differences.push(EventDifference::Extra {
+                signature: key.clone(),
+                elapsed: replayed_at,
+            });
+        }
+    }
+
+    let time = |difference: &EventDifference| match difference {
+        EventDifference::Missing { elapsed, .. } | EventDifference::Extra { elapsed, .. } => {
+            *elapsed
+        }
+        EventDifference::Moved { recorded, .. } => *recorded,
+    };
+    differences.sort_by(|a, b| time(a).total_cmp(&time(b)));
+
+    differences
+}
+
+/// Recorded seeks not yet repeated
+#[derive(Resource, Debug, Default)]
+pub struct ReplaySeeks {
+    pending: Vec<(f32, f32)>,
+}
+
+/// Every event of the replayed run, for the end-of-run diff
+#[derive(Resource, Clone)]
+pub struct ReplayRun(pub RingBufferSink);
+
+/// Start the replay without waiting for a click (windowed runs)
+pub fn start_replay(
+    state: Res<State<ExperienceState>>,
+    mut next_state: ResMut<NextState<ExperienceState>>,
+    mut clock: ResMut<ExperienceClock>,
+    log: Res<WideEventLog>,
+) {
+    if *state.get() != ExperienceState::Ready {
+        return;
+    }
+
+    if !request_transition(*state.get(), ExperienceState::Running, &mut next_state) {
+        return;
+    }
+    clock.start();
+
+    wide_event!("experience_started")
+        .with_str("trigger", "replay")
+        .emit(&log, 0.0);
+}
+
+/// Repeat recorded seeks when the clock reaches the point they were made
+pub fn replay_seeks(
+    clock: Res<ExperienceClock>,
+    mut seeks: ResMut<ReplaySeeks>,
+    mut controller: ResMut<ExperienceController>,
+) {
+    if !clock.has_started() {
+        return;
+    }
+
+    if let Some(&(from, to)) = seeks.pending.first() {
+        if clock.elapsed() >= from {
+            seeks.pending.remove(0);
+            controller.seek(to);
+        }
+    }
+}
+
+/// Log how the finished replay differs from its recording
+pub fn report_replay_diff(log: Res<ReplayLog>, run: Res<ReplayRun>, time: Res<Time<Fixed>>) {
+    let tolerance = time.timestep().as_secs_f32() * REPLAY_TOLERANCE_STEPS;
+    let differences = diff_events(&log.events, &run.0.events(), tolerance);
+
+    if differences.is_empty() {
+        info!(
+            target: "lightwatch::replay",
+            "Replay matches {}",
+            log.path.display()
+        );
+        return;
+    }
+
+    warn!(
+        target: "lightwatch::replay",
+        "Replay differs from {} in {} events",
+        log.path.display(),
+        differences.len()
+    );
+    for difference in &differences {
+        warn!(target: "lightwatch::replay", "{}", difference);
+    }
+}
+
+/// Replay plugin; does nothing unless a `ReplayLog` is inserted before `LightwatchPlugin`
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        let Some(log) = app.world().get_resource::<ReplayLog>() else {
+            return;
+        };
+
+        info!(
+            target: "lightwatch::replay",
+            "Replaying {} ({} events)",
+            log.path.display(),
+            log.events.len()
+        );
+
+        let seeks = ReplaySeeks {
+            pending: log.seeks(),
+        };
+
+        // Keep the whole run; a replay is about as long as its recording
+        let run = RingBufferSink::new(log.events.len() * 2 + 1024);
+        if let Some(mut sinks) = app.world_mut().get_resource_mut::<WideEventSinks>() {
+            sinks.add(run.clone());
+        }
+
+        app.insert_resource(seeks)
+            .insert_resource(ReplayRun(run))
+            .add_systems(
+                PreUpdate,
+                replay_seeks.before(super::apply_pending_seek),
+            );
+
+        // Headless runs start themselves and diff in `main`
+        if !app.world().contains_resource::<HeadlessMode>() {
+            app.add_systems(Update, start_replay)
+                .add_systems(OnEnter(ExperienceState::Ended), report_replay_diff);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEP: f32 = 1.0 / 60.0;
+    const TOLERANCE: f32 = STEP * REPLAY_TOLERANCE_STEPS;
+
+    fn event(name: &str, id: &str, elapsed: f32) -> WideEvent {
+        let mut event = WideEvent::new(name).with_str("id", id);
+        event.elapsed_secs = elapsed;
+        event
+    }
+
+    /// A short run: two spawns, a fade and its finish
+    fn recording() -> Vec<WideEvent> {
+        vec![
+            event("traveler_spawned", "archivist", 12.05),
+            event("traveler_spawned", "wanderer", 20.0),
+            event("traveler_fading", "child", 95.0),
+            event("traveler_faded", "child", 103.0),
+        ]
+    }
+
+    #[test]
+    fn identical_logs_have_no_differences() {
+        assert!(diff_events(&recording(), &recording(), TOLERANCE).is_empty());
+    }
+
+    #[test]
+    fn shifts_within_the_tolerance_are_ignored() {
+        let mut replayed = recording();
+        replayed[1].elapsed_secs += STEP;
+        replayed[3].elapsed_secs -= STEP;
+
+        assert!(diff_events(&recording(), &replayed, TOLERANCE).is_empty());
+    }
+
+    #[test]
+    fn shifts_past_the_tolerance_are_moved() {
+        let mut replayed = recording();
+        replayed[1].elapsed_secs += 2.0 * STEP;
+
+        assert_eq!(
+            diff_events(&recording(), &replayed, TOLERANCE),
+            vec![EventDifference::Moved {
+                signature: "traveler_spawned id=wanderer".to_string(),
+                recorded: 20.0,
+                replayed: 20.0 + 2.0 * STEP,
+            }]
+        );
+    }
+
+    #[test]
+    fn missing_and_extra_events_are_reported() {
+        let mut replayed = recording();
+        replayed.remove(2);
+        replayed.push(event("traveler_spawned", "keeper", 30.0));
+
+        assert_eq!(
+            diff_events(&recording(), &replayed, TOLERANCE),
+            vec![
+                EventDifference::Extra {
+                    signature: "traveler_spawned id=keeper".to_string(),
+                    elapsed: 30.0,
+                },
+                EventDifference::Missing {
+                    signature: "traveler_fading id=child".to_string(),
+                    elapsed: 95.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn repeated_events_are_matched_in_order() {
+        let pulses = |times: &[f32]| {
+            times
+                .iter()
+                .map(|&elapsed| event("traveler_pulse", "keeper", elapsed))
+                .collect::<Vec<_>>()
+        };
+        let recorded = pulses(&[10.0, 20.0, 30.0]);
+
+        // Same count and times: each occurrence pairs with its counterpart
+        assert!(diff_events(&recorded, &pulses(&[10.0, 20.0, 30.0]), TOLERANCE).is_empty());
+
+        // One occurrence late, one dropped from the end
+        assert_eq!(
+            diff_events(&recorded, &pulses(&[10.0, 21.0]), TOLERANCE),
+            vec![
+                EventDifference::Moved {
+                    signature: "traveler_pulse id=keeper".to_string(),
+                    recorded: 20.0,
+                    replayed: 21.0,
+                },
+                EventDifference::Missing {
+                    signature: "traveler_pulse id=keeper".to_string(),
+                    elapsed: 30.0,
+                },
+            ]
+        );
+
+        // One more than recorded
+        assert_eq!(
+            diff_events(&recorded, &pulses(&[10.0, 20.0, 30.0, 40.0]), TOLERANCE),
+            vec![EventDifference::Extra {
+                signature: "traveler_pulse id=keeper".to_string(),
+                elapsed: 40.0,
+            }]
+        );
+    }
+
+    #[test]
+    fn setup_events_and_measurements_are_not_compared() {
+        let mut replayed = recording();
+        replayed.insert(0, event("experience_started", "replay", 0.0));
+        replayed[1] = replayed[1].clone().with_vec3("position", Vec3::X);
+
+        assert!(diff_events(&recording(), &replayed, TOLERANCE).is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+//! Experience reset - return every plugin to its start-of-show state
+//!
+//! `reset_experience` sends `ResetExperience` and runs the `ExperienceReset`
+//! schedule. Each plugin adds systems to that schedule for the state it owns:
+//! resources go back to their defaults, run entities are despawned, the
+//! experience RNG starts over from its seed and the audio thread restarts.
+//! Systems that keep `Local` state read the event instead. Seeks reset before
+//! replaying; installation loops reset between runs; show controllers reset
+//! on request.
+
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::prelude::*;
+
+use super::events::*;
+use super::{ExperienceClock, PhaseChangedEvent};
+use crate::camera::TriggerTransitionEvent;
+use crate::environment::FogPulseEvent;
+
+/// Sent whenever the experience is reset
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResetExperience {
+    pub reason: ResetReason,
+}
+
+/// Why the experience was reset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResetReason {
+    /// Rebuilding the world before replaying to a seek target
+    Seek,
+    /// Installation loop, between two runs
+    Loop,
+    /// `/lightwatch/reset` from a show controller
+    Remote,
+    /// Following a leader instance back to its ready screen
+    Sync,
+}
+
+impl ResetReason {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ResetReason::Seek => "seek",
+            ResetReason::Loop => "loop",
+            ResetReason::Remote => "remote",
+            ResetReason::Sync => "sync",
+        }
+    }
+}
+
+/// Schedule of per-plugin reset systems, run by `reset_experience`
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExperienceReset;
+
+/// Put every plugin back to where the show starts
+///
+/// The clock ends up stopped at 0 and not started; the caller decides
+/// whether to run again immediately (seek) or wait on the ready screen (loop).
+pub fn reset_experience(world: &mut World, reason: ResetReason) {
+    world.send_event(ResetExperience { reason });
+    world.run_schedule(ExperienceReset);
+
+    // Nothing sent before the reset should be read after it
+    clear_moment_events(world);
+}
+
+/// Reset system: restore a resource to its default value
+pub fn reset_resource<R: Resource + Default>(resource: Option<ResMut<R>>) {
+    if let Some(mut resource) = resource {
+        *resource = R::default();
+    }
+}
+
+/// Reset system: despawn every entity with component `C`
+pub fn despawn_all<C: Component>(mut commands: Commands, query: Query<Entity, With<C>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Stop the clock at 0, keeping its phase table
+pub fn reset_clock(mut clock: ResMut<ExperienceClock>) {
+    clock.reset();
+}
+
+fn clear_events<E: Event>(world: &mut World) {
+    if let Some(mut events) = world.get_resource_mut::<Events<E>>() {
+        events.clear();
+    }
+}
+
+/// Drop everything the moment dispatcher and simulation can send
+pub fn clear_moment_events(world: &mut World) {
+    clear_events::<MomentEvent>(world);
+    clear_events::<PhaseChangedEvent>(world);
+    clear_events::<TravelerSpawnedEvent>(world);
+    clear_events::<TravelerPulseEvent>(world);
+    clear_events::<TravelerFadingEvent>(world);
+    clear_events::<TravelerFadedEvent>(world);
+    clear_events::<TravelerGriefEvent>(world);
+    clear_events::<TravelersSyncedEvent>(world);
+    clear_events::<CameraBehaviorChangedEvent>(world);
+    clear_events::<CameraShakeEvent>(world);
+    clear_events::<CameraFocusEvent>(world);
+    clear_events::<TriggerTransitionEvent>(world);
+    clear_events::<PlayNoteEvent>(world);
+    clear_events::<PlayLeitmotifEvent>(world);
+    clear_events::<AudioLayerEvent>(world);
+    clear_events::<DisplayTextEvent>(world);
+    clear_events::<HideTextEvent>(world);
+    clear_events::<SignalOverlayEvent>(world);
+    clear_events::<BangEvent>(world);
+    clear_events::<GlitchEvent>(world);
+    clear_events::<EnvironmentEvent>(world);
+    clear_events::<FogPulseEvent>(world);
+}
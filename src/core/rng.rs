@@ -0,0 +1,82 @@
+//! Experience RNG - single seeded source of randomness for reproducible runs
+//!
+//! Scenery built at startup draws first. Every run then restarts the stream
+//! from the seed, so a run's draws don't depend on what came before it: the
+//! first run, a loop restart and a seek's replay all see the same numbers.
+//! Systems outside the fixed step take a `stream` of their own instead, since
+//! the number of frames between two steps would shift the shared sequence.
+
+use bevy::prelude::*;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Seeded random number generator shared by every system that needs randomness
+///
+/// Defaults to a random seed; insert `ExperienceRng::new(seed)` before adding
+/// the plugins to reproduce a run exactly.
+#[derive(Resource)]
+pub struct ExperienceRng {
+    seed: u64,
+    rng: ChaCha8Rng,
+}
+
+impl ExperienceRng {
+    /// Create a generator from a fixed seed
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Seed this generator was created with
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Start the shared sequence over from the seed
+    pub fn restart(&mut self) {
+        self.rng = ChaCha8Rng::seed_from_u64(self.seed);
+    }
+
+    /// Independent generator for `stream`, fixed by the seed alone
+    pub fn stream(&self, stream: u64) -> ChaCha8Rng {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        rng.set_stream(stream);
+        rng
+    }
+}
+
+impl Default for ExperienceRng {
+    fn default() -> Self {
+        Self::new(rand::random())
+    }
+}
+
+impl RngCore for ExperienceRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+/// Restart the shared sequence (on `ExperienceReset` and when a run starts)
+pub fn restart_rng(mut rng: ResMut<ExperienceRng>) {
+    rng.restart();
+}
+
+/// Log the seed so any run can be reproduced
+pub fn log_rng_seed(rng: Res<ExperienceRng>) {
+    info!(target: "lightwatch::rng", "Experience seed: {}", rng.seed());
+}
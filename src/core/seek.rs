@@ -0,0 +1,278 @@
+//! Seek - rebuild the whole experience at an arbitrary time
+//!
+//! A seek resets every piece of timeline-driven state to its start-of-show
+//! value (`reset_experience`, which also restarts the experience RNG), then
+//! replays the fixed simulation from 0 to the target without rendering.
+//! Moments, traveler lifecycles and drift, grief, the bang sequence and the
+//! camera rig end up where an uninterrupted run has them at the target.
+//! Presentation that only advances per frame (aura particles, text reveal,
+//! post effects) starts over from there instead of being replayed.
+//! Events age step by step during the replay, so text and one-shot sounds
+//! whose moment has passed are skipped rather than played in a burst, and
+//! the audio thread is resynced to the ambiance state.
+
+use std::time::Duration;
+
+use bevy::app::FixedMain;
+use bevy::ecs::component::Tick;
+use bevy::ecs::event::EventRegistry;
+use bevy::prelude::*;
+
+use super::fixed_step::InterpolatedTransform;
+use super::logging::WideEventLog;
+use super::reset::{reset_experience, ResetReason};
+use super::state::request_transition_in;
+use super::{
+    ExperienceClock, ExperienceState, MomentAction, Phase, PhaseController, TimeControl,
+    TimelineVerification,
+};
+use crate::audio::{
+    AmbianceFadeState, AudioTrigger, AudioTriggerQueue, AMBIANCE_FADE_LEAD, AUDIO_LAYERS,
+};
+use crate::text::{FinalMessageState, SignalConfig, SignalState, FINAL_MESSAGES_LEAD};
+use crate::wide_event;
+
+/// Public control over the running experience
+#[derive(Resource, Default)]
+pub struct ExperienceController {
+    pending_seek: Option<f32>,
+}
+
+impl ExperienceController {
+    /// Rebuild the experience at `time` seconds before the next simulation step
+    ///
+    /// Works from Ready, Running, Ending and Ended; a seek requested while
+    /// loading or between loop runs is held until the experience is ready.
+    /// Targets past the end of the cut land on the end.
+    pub fn seek(&mut self, time: f32) {
+        self.pending_seek = Some(time.max(0.0));
+    }
+
+    /// Seek target waiting to be applied, if any
+    pub fn pending_seek(&self) -> Option<f32> {
+        self.pending_seek
+    }
+}
+
+/// Apply a pending seek (exclusive, runs before the fixed-step loop)
+pub fn apply_pending_seek(world: &mut World) {
+    let Some(target) = world.resource::<ExperienceController>().pending_seek else {
+        return;
+    };
+
+    // Held while loading, or between loop runs until the ready screen is back
+    let state = *world.resource::<State<ExperienceState>>().get();
+    if state != ExperienceState::Running && !state.can_transition_to(&ExperienceState::Running) {
+        return;
+    }
+    world.resource_mut::<ExperienceController>().pending_seek = None;
+
+    let from = world.resource::<ExperienceClock>().elapsed();
+    let target = target.min(world.resource::<ExperienceClock>().duration());
+
+    // Forward seeks while running only need the missing steps; anything else
+    // replays from the start
+    let rebuild = state != ExperienceState::Running || target < from;
+
+    if state != ExperienceState::Running {
+        request_transition_in(world, ExperienceState::Running);
+        world.run_schedule(StateTransition);
+    }
+
+    if rebuild {
+        reset_experience(world, ResetReason::Seek);
+        world.resource_mut::<ExperienceClock>().restart();
+    }
+
+    let log = world.resource::<WideEventLog>().clone();
+    log.set_muted(true);
+    let steps = fast_forward(world, target);
+    log.set_muted(false);
+
+    let ambiance = finish_seek(world, target);
+
+    let elapsed = world.resource::<ExperienceClock>().elapsed();
+    info!(
+        target: "lightwatch::seek",
+        "Seeked from {:.2}s to {:.2}s ({} steps)",
+        from,
+        elapsed,
+        steps
+    );
+
+    wide_event!("seek")
+        .with_f32("from", from)
+        .with_f32("to", elapsed)
+        .with_i64("steps", steps as i64)
+        .with_bool("rebuilt", rebuild)
+        .with_bool("ambiance", ambiance)
+        .with_str("phase", world.resource::<ExperienceClock>().phase().name())
+        .emit(&log, elapsed);
+}
+
+/// Run the fixed simulation until the clock reaches `target`, returns the step count
+fn fast_forward(world: &mut World, target: f32) -> u32 {
+    // Pause and speed overrides would stall or skew the replay
+    let (paused, speed) = {
+        let mut control = world.resource_mut::<TimeControl>();
+        let saved = (control.paused, control.speed);
+        control.paused = false;
+        control.speed = 1.0;
+        saved
+    };
+
+    let timestep = world.resource::<Time<Fixed>>().timestep();
+    let max_steps = (target / timestep.as_secs_f32()).ceil() as u32 + 2;
+    let mut time = world.resource::<Time<Fixed>>().as_generic();
+    let mut steps = 0;
+
+    while steps < max_steps {
+        let remaining = target - world.resource::<ExperienceClock>().elapsed();
+        if remaining <= 1e-4 {
+            break;
+        }
+
+        // The last step is shortened so the clock lands on the target
+        time.advance_by(timestep.min(Duration::from_secs_f32(remaining)));
+        *world.resource_mut::<Time>() = time;
+        world.run_schedule(FixedMain);
+        update_events(world);
+        steps += 1;
+    }
+
+    *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
+
+    let mut control = world.resource_mut::<TimeControl>();
+    control.paused = paused;
+    control.speed = speed;
+
+    steps
+}
+
+/// Age every event by one frame, as the frame loop does after each fixed step
+///
+/// Fixed-step readers still get what the previous step sent, and by the end
+/// only the last two steps' events are left for the presentation systems.
+fn update_events(world: &mut World) {
+    if world.contains_resource::<EventRegistry>() {
+        world.resource_scope(|world, mut registry: Mut<EventRegistry>| {
+            registry.run_updates(world, Tick::new(0));
+        });
+    }
+}
+
+/// Should ambiance be playing at `at` seconds, given the moments triggered so far?
+///
+/// It is silent once any of its layers was stopped, or past the end fade.
+pub fn ambiance_playing(world: &World, at: f32) -> bool {
+    let ambiance_fade = world.resource::<ExperienceClock>().duration() - AMBIANCE_FADE_LEAD;
+    let stopped = world
+        .resource::<PhaseController>()
+        .moments
+        .iter()
+        .any(|moment| {
+            moment.triggered
+                && matches!(&moment.action, MomentAction::StopLayer(layer)
+                    if AUDIO_LAYERS.contains(&layer.as_str()))
+        });
+    !stopped && at < ambiance_fade
+}
+
+/// Settle presentation after the replay, returns whether ambiance is playing
+fn finish_seek(world: &mut World, target: f32) -> bool {
+    let duration = world.resource::<ExperienceClock>().duration();
+    let final_messages = duration - FINAL_MESSAGES_LEAD;
+    let ambiance_fade = duration - AMBIANCE_FADE_LEAD;
+    let bang = world.resource::<ExperienceClock>().phase_table().start(Phase::Bang);
+
+    let ambiance = ambiance_playing(world, target);
+
+    // The signal overlay has finished by the time the bang starts
+    if target > bang {
+        let revealed = world.resource::<SignalConfig>().reveal_order.len();
+        let mut signal = world.resource_mut::<SignalState>();
+        signal.detection_started = true;
+        signal.detection_complete = true;
+        signal.travelers_revealed = revealed;
+    }
+    world.resource_mut::<FinalMessageState>().messages_started = target > final_messages;
+    world.resource_mut::<AmbianceFadeState>().triggered = target >= ambiance_fade;
+
+    let phase = world.resource::<ExperienceClock>().phase();
+    world.resource_mut::<TimelineVerification>().skip_to(phase);
+
+    world
+        .resource::<AudioTriggerQueue>()
+        .send(AudioTrigger::Resync { ambiance });
+
+    // Render from the reconstructed state without blending from before the seek
+    let mut query = world.query::<(&mut Transform, &mut InterpolatedTransform)>();
+    for (mut transform, mut interpolated) in query.iter_mut(world) {
+        let current = interpolated.current;
+        interpolated.snap(current);
+        *transform = current;
+    }
+
+    ambiance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::headless::{run_until, running_app, summarize, HeadlessConfig};
+
+    #[test]
+    fn seek_back_matches_uninterrupted_run() {
+        let config = HeadlessConfig {
+            seed: 11,
+            timestep: 1.0 / 30.0,
+            ..default()
+        };
+
+        // Past the first spawns and the bang, with travelers drifting; the
+        // second run goes on further and seeks back to where the first stopped
+        let (target_tx, target_rx) = std::sync::mpsc::channel();
+        let config = &config;
+        let (expected, actual) = std::thread::scope(|scope| {
+            let uninterrupted = scope.spawn(move || {
+                let mut app = running_app(config);
+                run_until(&mut app, 45.0);
+                let target = app.world().resource::<ExperienceClock>().elapsed();
+                target_tx.send(target).unwrap();
+                app.update();
+                summarize(&mut app, config.seed, 0)
+            });
+            let seeked = scope.spawn(move || {
+                let mut app = running_app(config);
+                run_until(&mut app, 55.0);
+                let target = target_rx.recv().unwrap();
+                app.world_mut().resource_mut::<ExperienceController>().seek(target);
+                // Applied before this frame's step; the registry catches up in Update
+                app.update();
+                summarize(&mut app, config.seed, 0)
+            });
+            (uninterrupted.join().unwrap(), seeked.join().unwrap())
+        });
+
+        assert_eq!(actual.elapsed, expected.elapsed);
+        assert_eq!(actual.spawned, expected.spawned);
+        assert_eq!(actual.active, expected.active);
+        assert_eq!(actual.faded, expected.faded);
+        assert!(!expected.travelers.is_empty());
+        assert_eq!(actual.travelers.len(), expected.travelers.len());
+        for (actual, expected) in actual.travelers.iter().zip(&expected.travelers) {
+            assert_eq!(actual.id, expected.id);
+            assert_eq!(actual.state, expected.state);
+            assert!((actual.opacity - expected.opacity).abs() < 1e-4);
+            assert!(
+                actual.position.distance(expected.position) < 1e-3,
+                "{} at {:?}, expected {:?}",
+                actual.id.name(),
+                actual.position,
+                expected.position
+            );
+        }
+        let camera = actual.camera_position.unwrap();
+        assert!(camera.distance(expected.camera_position.unwrap()) < 1e-3);
+    }
+}
@@ -0,0 +1,250 @@
+//! User settings - viewer preferences kept between runs
+//!
+//! Volume, text size, quality, reduced motion, fullscreen and language are
+//! stored as RON in `$XDG_CONFIG_HOME/lightwatch/settings.ron` (falling back
+//! to `~/.config`). The binary loads the file at launch; the ready screen's
+//! settings menu edits the `UserSettings` resource and saves it on close.
+//! `apply_user_settings` copies every change into the existing config
+//! resources, so no system reads `UserSettings` directly.
+
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::performance::{PerformanceConfig, QualityPreset};
+use super::reset::ResetExperience;
+use crate::audio::{AudioTrigger, AudioTriggerQueue};
+use crate::camera::{BreathingConfig, CameraShake};
+use crate::post::PostProcessConfig;
+use crate::text::TextConfig;
+
+/// Settings file name inside the config directory
+pub const SETTINGS_FILE_NAME: &str = "settings.ron";
+
+/// Default text size in points (the `Medium` size)
+const BASE_FONT_SIZE: f32 = 24.0;
+
+/// Camera shake kept with reduced motion
+const REDUCED_MOTION_SHAKE: f32 = 0.25;
+
+/// Size of narrative text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TextSize {
+    Small,
+    #[default]
+    Medium,
+    Large,
+}
+
+impl TextSize {
+    pub const ALL: [TextSize; 3] = [TextSize::Small, TextSize::Medium, TextSize::Large];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TextSize::Small => "small",
+            TextSize::Medium => "medium",
+            TextSize::Large => "large",
+        }
+    }
+
+    /// Font size relative to the default
+    pub fn scale(&self) -> f32 {
+        match self {
+            TextSize::Small => 0.8,
+            TextSize::Medium => 1.0,
+            TextSize::Large => 1.3,
+        }
+    }
+}
+
+/// Language of the narrative text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+}
+
+impl Language {
+    /// Languages with a full set of transmissions
+    pub const ALL: [Language; 1] = [Language::English];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+        }
+    }
+}
+
+/// Viewer preferences, saved between runs
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserSettings {
+    /// Master volume, 0.0 to 1.0
+    pub volume: f32,
+    pub text_size: TextSize,
+    pub quality: QualityPreset,
+    /// No chromatic aberration, film grain or camera breathing, gentler shake
+    pub reduced_motion: bool,
+    pub fullscreen: bool,
+    pub language: Language,
+    /// Output device by name (`null` for none); the system default when unset
+    pub audio_device: Option<String>,
+}
+
+impl Default for UserSettings {
+    fn default() -> Self {
+        Self {
+            volume: 0.7,
+            text_size: TextSize::default(),
+            quality: QualityPreset::default(),
+            reduced_motion: false,
+            fullscreen: false,
+            language: Language::default(),
+            audio_device: None,
+        }
+    }
+}
+
+impl UserSettings {
+    /// Performance and post-processing settings for the quality tier and motion preference
+    pub fn quality_configs(&self) -> (PerformanceConfig, PostProcessConfig) {
+        self.configs_for(self.quality)
+    }
+
+    /// Like `quality_configs`, for another tier (adaptive quality)
+    pub fn configs_for(&self, tier: QualityPreset) -> (PerformanceConfig, PostProcessConfig) {
+        let mut performance = PerformanceConfig::default();
+        let mut post = PostProcessConfig::default();
+        tier.apply(&mut performance, &mut post);
+
+        if self.reduced_motion {
+            post.chromatic_aberration.enabled = false;
+            post.grain.enabled = false;
+        }
+
+        (performance, post)
+    }
+}
+
+/// Where `UserSettings` is saved; `None` keeps changes for this run only
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SettingsFile {
+    pub path: Option<PathBuf>,
+}
+
+/// `$XDG_CONFIG_HOME/lightwatch/settings.ron`, or under `~/.config`
+pub fn default_settings_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config_dir.join("lightwatch").join(SETTINGS_FILE_NAME))
+}
+
+/// Read settings; a missing file gives the defaults
+pub fn load_user_settings(path: impl AsRef<Path>) -> Result<UserSettings, String> {
+    let path = path.as_ref();
+    let source = match std::fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(UserSettings::default())
+        }
+        Err(err) => return Err(format!("{}: {}", path.display(), err)),
+    };
+
+    let mut settings: UserSettings = ron::de::from_str(&source).map_err(|err| {
+        format!(
+            "{}:{}:{}: {}",
+            path.display(),
+            err.position.line,
+            err.position.col,
+            err.code
+        )
+    })?;
+    settings.volume = settings.volume.clamp(0.0, 1.0);
+
+    Ok(settings)
+}
+
+/// Write settings, creating the config directory if needed
+pub fn save_user_settings(path: impl AsRef<Path>, settings: &UserSettings) -> Result<(), String> {
+    let path = path.as_ref();
+    let source = ron::ser::to_string_pretty(settings, ron::ser::PrettyConfig::default())
+        .map_err(|err| err.to_string())?;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|err| format!("{}: {}", dir.display(), err))?;
+    }
+    std::fs::write(path, source).map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+/// Copy settings into the config resources when they change
+///
+/// Also runs after a reset, which restores the camera shake defaults.
+#[allow(clippy::too_many_arguments)]
+pub fn apply_user_settings(
+    settings: Res<UserSettings>,
+    mut resets: EventReader<ResetExperience>,
+    mut performance: ResMut<PerformanceConfig>,
+    mut post: ResMut<PostProcessConfig>,
+    msaa: Option<ResMut<Msaa>>,
+    text: Option<ResMut<TextConfig>>,
+    breathing: Option<ResMut<BreathingConfig>>,
+    shake: Option<ResMut<CameraShake>>,
+    audio: Option<Res<AudioTriggerQueue>>,
+    mut windows: Query<&mut Window>,
+) {
+    let reset = resets.read().count() > 0;
+    if !settings.is_changed() && !reset {
+        return;
+    }
+
+    let motion = if settings.reduced_motion { 0.0 } else { 1.0 };
+    if let Some(mut breathing) = breathing {
+        let defaults = BreathingConfig::default();
+        breathing.amplitude_x = defaults.amplitude_x * motion;
+        breathing.amplitude_y = defaults.amplitude_y * motion;
+    }
+    if let Some(mut shake) = shake {
+        let defaults = CameraShake::default();
+        let scale = if settings.reduced_motion { REDUCED_MOTION_SHAKE } else { 1.0 };
+        shake.max_rotation = defaults.max_rotation * scale;
+        shake.max_offset = defaults.max_offset * scale;
+    }
+
+    // The rest only changes when the settings do
+    if !settings.is_changed() {
+        return;
+    }
+
+    (*performance, *post) = settings.quality_configs();
+    if let Some(mut msaa) = msaa {
+        *msaa = settings.quality.msaa();
+    }
+    if let Some(mut text) = text {
+        text.font_size = BASE_FONT_SIZE * settings.text_size.scale();
+    }
+    if let Some(audio) = audio {
+        audio.send(AudioTrigger::SetVolume(settings.volume));
+    }
+    if let Ok(mut window) = windows.get_single_mut() {
+        window.mode = if settings.fullscreen {
+            bevy::window::WindowMode::BorderlessFullscreen
+        } else {
+            bevy::window::WindowMode::Windowed
+        };
+    }
+
+    info!(
+        target: "lightwatch::settings",
+        "Settings: volume {:.0}%, text {}, quality {}, reduced motion {}, fullscreen {}, {}",
+        settings.volume * 100.0,
+        settings.text_size.name(),
+        settings.quality.name(),
+        settings.reduced_motion,
+        settings.fullscreen,
+        settings.language.name()
+    );
+}
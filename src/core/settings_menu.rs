@@ -0,0 +1,233 @@
+//! Settings menu - edit `UserSettings` from the ready screen
+//!
+//! S or Tab opens the menu; arrows choose a row and change its value. While
+//! the menu is open a click does not start the experience. Closing the menu,
+//! or starting the run, saves any changes to the settings file.
+
+use bevy::prelude::*;
+
+use super::input::InputConfig;
+use super::performance::QualityPreset;
+use super::settings::{save_user_settings, Language, SettingsFile, TextSize, UserSettings};
+
+/// Volume change per key press
+const VOLUME_STEP: f32 = 0.1;
+
+/// Marker for the menu panel (hidden while closed)
+#[derive(Component)]
+pub struct SettingsMenu;
+
+/// Marker for the menu's text
+#[derive(Component)]
+pub struct SettingsMenuText;
+
+/// Menu open state and selected row
+#[derive(Resource, Debug, Default)]
+pub struct SettingsMenuState {
+    pub open: bool,
+    pub selected: usize,
+    /// Settings changed since the menu opened
+    pub dirty: bool,
+}
+
+/// One editable row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsField {
+    Volume,
+    TextSize,
+    Quality,
+    ReducedMotion,
+    Fullscreen,
+    Language,
+}
+
+impl SettingsField {
+    pub const ALL: [SettingsField; 6] = [
+        SettingsField::Volume,
+        SettingsField::TextSize,
+        SettingsField::Quality,
+        SettingsField::ReducedMotion,
+        SettingsField::Fullscreen,
+        SettingsField::Language,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingsField::Volume => "volume",
+            SettingsField::TextSize => "text size",
+            SettingsField::Quality => "quality",
+            SettingsField::ReducedMotion => "reduced motion",
+            SettingsField::Fullscreen => "fullscreen",
+            SettingsField::Language => "language",
+        }
+    }
+
+    /// Current value as shown in the menu
+    pub fn value(&self, settings: &UserSettings) -> String {
+        let on_off = |on: bool| if on { "on" } else { "off" }.to_string();
+
+        match self {
+            SettingsField::Volume => format!("{:.0}%", settings.volume * 100.0),
+            SettingsField::TextSize => settings.text_size.name().to_string(),
+            SettingsField::Quality => settings.quality.name().to_string(),
+            SettingsField::ReducedMotion => on_off(settings.reduced_motion),
+            SettingsField::Fullscreen => on_off(settings.fullscreen),
+            SettingsField::Language => settings.language.name().to_string(),
+        }
+    }
+
+    /// Step the value forward (`1`) or back (`-1`)
+    pub fn adjust(&self, settings: &mut UserSettings, step: i32) {
+        fn cycle<T: Copy + PartialEq>(all: &[T], current: T, step: i32) -> T {
+            let index = all.iter().position(|v| *v == current).unwrap_or(0) as i32;
+            all[(index + step).rem_euclid(all.len() as i32) as usize]
+        }
+
+        match self {
+            SettingsField::Volume => {
+                let volume = settings.volume + VOLUME_STEP * step as f32;
+                settings.volume = ((volume / VOLUME_STEP).round() * VOLUME_STEP).clamp(0.0, 1.0);
+            }
+            SettingsField::TextSize => {
+                settings.text_size = cycle(&TextSize::ALL, settings.text_size, step);
+            }
+            SettingsField::Quality => {
+                let all = [QualityPreset::Low, QualityPreset::Medium, QualityPreset::High];
+                settings.quality = cycle(&all, settings.quality, step);
+            }
+            SettingsField::ReducedMotion => settings.reduced_motion = !settings.reduced_motion,
+            SettingsField::Fullscreen => settings.fullscreen = !settings.fullscreen,
+            SettingsField::Language => {
+                settings.language = cycle(&Language::ALL, settings.language, step);
+            }
+        }
+    }
+}
+
+/// Spawn the (hidden) menu panel; called from the ready screen
+pub fn spawn_settings_menu(parent: &mut ChildBuilder, font: Handle<Font>) {
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    margin: UiRect::top(Val::Px(32.0)),
+                    ..default()
+                },
+                ..default()
+            },
+            SettingsMenu,
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font,
+                        font_size: 16.0,
+                        color: Color::srgba(0.9, 0.87, 0.82, 0.8),
+                    },
+                ),
+                SettingsMenuText,
+            ));
+        });
+}
+
+/// Save settings edited in the menu
+fn save_if_dirty(menu: &mut SettingsMenuState, settings: &UserSettings, file: &SettingsFile) {
+    if !menu.dirty {
+        return;
+    }
+    menu.dirty = false;
+
+    let Some(path) = &file.path else {
+        return;
+    };
+    match save_user_settings(path, settings) {
+        Ok(()) => info!(target: "lightwatch::settings", "Saved settings to {}", path.display()),
+        Err(err) => warn!(target: "lightwatch::settings", "Failed to save settings: {}", err),
+    }
+}
+
+/// Open, close and edit the menu (Ready state only)
+pub fn handle_settings_menu_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut menu: ResMut<SettingsMenuState>,
+    mut settings: ResMut<UserSettings>,
+    mut input: ResMut<InputConfig>,
+    file: Res<SettingsFile>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyS) || keyboard.just_pressed(KeyCode::Tab) {
+        menu.open = !menu.open;
+        input.enabled = !menu.open;
+        if !menu.open {
+            save_if_dirty(&mut menu, &settings, &file);
+        }
+        return;
+    }
+
+    if !menu.open {
+        return;
+    }
+
+    let rows = SettingsField::ALL.len();
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        menu.selected = (menu.selected + rows - 1) % rows;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        menu.selected = (menu.selected + 1) % rows;
+    }
+
+    let step = if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        -1
+    } else if keyboard.just_pressed(KeyCode::ArrowRight) || keyboard.just_pressed(KeyCode::Enter)
+    {
+        1
+    } else {
+        return;
+    };
+
+    SettingsField::ALL[menu.selected].adjust(&mut settings, step);
+    menu.dirty = true;
+}
+
+/// Close the menu when the run starts, saving any changes
+pub fn close_settings_menu(
+    mut menu: ResMut<SettingsMenuState>,
+    settings: Res<UserSettings>,
+    file: Res<SettingsFile>,
+) {
+    save_if_dirty(&mut menu, &settings, &file);
+    menu.open = false;
+}
+
+/// Show or hide the panel and refresh its rows
+pub fn update_settings_menu(
+    menu: Res<SettingsMenuState>,
+    settings: Res<UserSettings>,
+    mut panels: Query<&mut Style, With<SettingsMenu>>,
+    mut texts: Query<&mut Text, With<SettingsMenuText>>,
+) {
+    if !menu.is_changed() && !settings.is_changed() {
+        return;
+    }
+
+    for mut style in panels.iter_mut() {
+        style.display = if menu.open { Display::Flex } else { Display::None };
+    }
+
+    let mut rows: Vec<String> = SettingsField::ALL
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let cursor = if index == menu.selected { ">" } else { " " };
+            format!("{} {:<16}< {} >", cursor, field.label(), field.value(&settings))
+        })
+        .collect();
+    rows.push(String::new());
+    rows.push("arrows change   S close".to_string());
+
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = rows.join("\n");
+    }
+}
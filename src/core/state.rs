@@ -1,8 +1,13 @@
 //! Experience state machine - Loading → Ready → Running → Ending → Ended
+//!
+//! In installation loop mode, Ended → Idle → Ready starts the next run.
+//! Loop, remote and sync changes go through `request_transition`, which
+//! refuses any move `can_transition_to` does not list.
 
 #![allow(dead_code)]
 
 use super::clock::{ExperienceClock, Phase};
+use super::logging::WideEventLog;
 use bevy::prelude::*;
 
 /// High-level experience states
@@ -19,6 +24,8 @@ pub enum ExperienceState {
     Ending,
     /// Fully ended
     Ended,
+    /// Between loops: the world is reset before returning to Ready
+    Idle,
 }
 
 impl ExperienceState {
@@ -29,6 +36,7 @@ impl ExperienceState {
             ExperienceState::Running => "running",
             ExperienceState::Ending => "ending",
             ExperienceState::Ended => "ended",
+            ExperienceState::Idle => "idle",
         }
     }
 
@@ -40,10 +48,49 @@ impl ExperienceState {
                 | (ExperienceState::Ready, ExperienceState::Running)
                 | (ExperienceState::Running, ExperienceState::Ending)
                 | (ExperienceState::Ending, ExperienceState::Ended)
+                | (ExperienceState::Ended, ExperienceState::Idle)
+                | (ExperienceState::Idle, ExperienceState::Ready)
+                // Seeks run again after the end
+                | (
+                    ExperienceState::Ending | ExperienceState::Ended,
+                    ExperienceState::Running
+                )
+                // Remote and sync resets go back to the ready screen
+                | (
+                    ExperienceState::Running | ExperienceState::Ending | ExperienceState::Ended,
+                    ExperienceState::Ready
+                )
         )
     }
 }
 
+/// Queue a move from `from` to `to`, or warn and stay put if the state
+/// machine does not allow it; returns whether the move was queued
+pub fn request_transition(
+    from: ExperienceState,
+    to: ExperienceState,
+    next_state: &mut NextState<ExperienceState>,
+) -> bool {
+    if !from.can_transition_to(&to) {
+        warn!(
+            target: "lightwatch::state",
+            "Refused state change {} -> {}",
+            from.name(),
+            to.name()
+        );
+        return false;
+    }
+
+    next_state.set(to);
+    true
+}
+
+/// `request_transition` from the current state (exclusive systems)
+pub fn request_transition_in(world: &mut World, to: ExperienceState) -> bool {
+    let from = *world.resource::<State<ExperienceState>>().get();
+    request_transition(from, to, &mut world.resource_mut::<NextState<ExperienceState>>())
+}
+
 /// Event for state changes
 #[derive(Event, Debug, Clone)]
 pub struct StateChangedEvent {
@@ -51,26 +98,12 @@ pub struct StateChangedEvent {
     pub to: ExperienceState,
 }
 
-/// Check if loading is complete and transition to Ready
-pub fn check_loading_complete(
-    state: Res<State<ExperienceState>>,
-    mut next_state: ResMut<NextState<ExperienceState>>,
-) {
-    if *state.get() != ExperienceState::Loading {
-        return;
-    }
-
-    // For now, immediately transition (assets are embedded)
-    next_state.set(ExperienceState::Ready);
-
-    crate::wide_event!("loading_complete").emit(0.0);
-}
-
 /// Transition to Ending when clock finishes
 pub fn check_experience_end(
     state: Res<State<ExperienceState>>,
     mut next_state: ResMut<NextState<ExperienceState>>,
     clock: Res<ExperienceClock>,
+    log: Res<WideEventLog>,
 ) {
     if *state.get() != ExperienceState::Running {
         return;
@@ -79,7 +112,7 @@ pub fn check_experience_end(
     if clock.phase() == Phase::Ended {
         next_state.set(ExperienceState::Ending);
 
-        crate::wide_event!("experience_ending").emit(clock.elapsed());
+        crate::wide_event!("experience_ending").emit(&log, clock.elapsed());
     }
 }
 
@@ -99,6 +132,8 @@ pub fn handle_ending_phase(
     mut next_state: ResMut<NextState<ExperienceState>>,
     mut timer: ResMut<EndingTimer>,
     time: Res<Time>,
+    clock: Res<ExperienceClock>,
+    log: Res<WideEventLog>,
 ) {
     if *state.get() != ExperienceState::Ending {
         return;
@@ -109,7 +144,7 @@ pub fn handle_ending_phase(
     if timer.0.finished() {
         next_state.set(ExperienceState::Ended);
 
-        crate::wide_event!("experience_ended").emit(143.0);
+        crate::wide_event!("experience_ended").emit(&log, clock.duration());
     }
 }
 
@@ -117,6 +152,7 @@ pub fn handle_ending_phase(
 pub fn log_state_transitions(
     state: Res<State<ExperienceState>>,
     mut events: EventWriter<StateChangedEvent>,
+    log: Res<WideEventLog>,
     mut last_state: Local<Option<ExperienceState>>,
 ) {
     let current = *state.get();
@@ -128,7 +164,7 @@ pub fn log_state_transitions(
             crate::wide_event!("state_changed")
                 .with_str("from", from.name())
                 .with_str("to", current.name())
-                .emit(0.0);
+                .emit(&log, 0.0);
         }
         *last_state = Some(current);
     }
@@ -171,3 +207,28 @@ pub fn experience_started(state: Res<State<ExperienceState>>) -> bool {
         ExperienceState::Loading | ExperienceState::Ready
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_transition_refuses_unlisted_moves() {
+        let mut next = NextState::<ExperienceState>::default();
+
+        assert!(!request_transition(
+            ExperienceState::Loading,
+            ExperienceState::Running,
+            &mut next
+        ));
+        assert!(matches!(next, NextState::Unchanged));
+
+        // A remote reset mid-run goes straight back to the ready screen
+        assert!(request_transition(
+            ExperienceState::Running,
+            ExperienceState::Ready,
+            &mut next
+        ));
+        assert!(matches!(next, NextState::Pending(ExperienceState::Ready)));
+    }
+}
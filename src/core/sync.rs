@@ -0,0 +1,609 @@
+//! Multi-screen sync - one leader instance, any number of followers
+//!
+//! The leader sends its clock as `/lightwatch/sync` OSC messages over UDP:
+//!
+//! `/lightwatch/sync <seed:s> <seq:i> <elapsed:f> <state:s> <speed:f> <paused:T|F>`
+//!
+//! The seed is sent as a decimal string, since OSC ints are 32 bits.
+//! Followers take the leader's seed for their `ExperienceRng`, so every
+//! screen draws the same numbers whatever `--seed` it was started with.
+//! Followers estimate where the leader is now from the latest message and
+//! lock to it. Small drift is taken out by nudging `TimeControl::speed`;
+//! drift past `SyncConfig::seek_threshold` seeks straight to the leader.
+//! Followers leave the ready screen when the leader starts, and return to it
+//! when the leader does. Loop mode belongs on the leader only.
+//!
+//! Pair with `CameraViewOffset` to turn each screen into one slice of a
+//! panorama.
+
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+
+use bevy::prelude::*;
+
+use super::osc::{decode_packet, OscArg, OscMessage};
+use super::remote::{return_to_ready, start_run, OSC_PREFIX};
+use super::logging::WideEventLog;
+use super::reset::ResetReason;
+use super::{ExperienceClock, ExperienceController, ExperienceRng, ExperienceState, TimeControl};
+use crate::wide_event;
+
+/// Largest packet read from the socket
+const MAX_PACKET_SIZE: usize = 512;
+
+/// Which side of the sync this instance is on
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncRole {
+    /// Send the clock to these addresses (broadcast addresses work)
+    Leader { targets: Vec<SocketAddr> },
+    /// Lock to a leader sending to this address
+    Follower { listen: SocketAddr },
+}
+
+/// Sync settings; insert before `LightwatchPlugin` to enable
+#[derive(Resource, Debug, Clone)]
+pub struct SyncConfig {
+    pub role: SyncRole,
+    /// Seconds between leader messages (state changes are sent at once)
+    pub interval: f32,
+    /// Drift in seconds past which a follower seeks instead of nudging
+    pub seek_threshold: f32,
+    /// Largest speed change used to take out drift (0.05 = ±5%)
+    pub max_nudge: f32,
+    /// Fraction of the drift taken out per second while nudging
+    pub correction_rate: f32,
+    /// Seconds without a message before a follower runs free
+    pub timeout: f32,
+}
+
+impl SyncConfig {
+    pub fn leader(targets: Vec<SocketAddr>) -> Self {
+        Self {
+            role: SyncRole::Leader { targets },
+            ..Self::follower(SocketAddr::from(([0, 0, 0, 0], 0)))
+        }
+    }
+
+    pub fn follower(listen: SocketAddr) -> Self {
+        Self {
+            role: SyncRole::Follower { listen },
+            interval: 0.1,
+            seek_threshold: 0.5,
+            max_nudge: 0.05,
+            correction_rate: 0.5,
+            timeout: 2.0,
+        }
+    }
+
+    /// How a follower at `elapsed` catches up with a leader estimated at `estimate`
+    fn correction(&self, elapsed: f32, estimate: f32, leader_speed: f32) -> SyncCorrection {
+        let drift = elapsed - estimate;
+        if drift.abs() > self.seek_threshold {
+            return SyncCorrection::Seek(estimate);
+        }
+
+        let nudge = (-drift * self.correction_rate).clamp(-self.max_nudge, self.max_nudge);
+        SyncCorrection::Speed(leader_speed * (1.0 + nudge))
+    }
+}
+
+/// A follower's answer to drift
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SyncCorrection {
+    /// Too far out: jump to the leader's position
+    Seek(f32),
+    /// Close: run slightly faster or slower until the next sample
+    Speed(f32),
+}
+
+/// Is `seq` after `than`? Sequence numbers wrap, so anything up to half the
+/// range ahead counts as newer
+fn seq_newer(seq: i32, than: i32) -> bool {
+    seq.wrapping_sub(than) > 0
+}
+
+/// One leader clock sample
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncMessage {
+    /// Leader's RNG seed; a leader restarted with another seed is followed
+    /// at once (one with the same seed after the timeout)
+    pub seed: u64,
+    pub seq: i32,
+    pub elapsed: f32,
+    pub state: ExperienceState,
+    pub speed: f32,
+    pub paused: bool,
+}
+
+impl SyncMessage {
+    pub fn address() -> String {
+        format!("{}/sync", OSC_PREFIX)
+    }
+
+    pub fn to_osc(&self) -> OscMessage {
+        OscMessage::new(
+            Self::address(),
+            vec![
+                OscArg::String(self.seed.to_string()),
+                OscArg::Int(self.seq),
+                OscArg::Float(self.elapsed),
+                OscArg::String(self.state.name().to_string()),
+                OscArg::Float(self.speed),
+                OscArg::Bool(self.paused),
+            ],
+        )
+    }
+
+    /// Parse a `/lightwatch/sync` message; `None` for anything else
+    pub fn from_osc(message: &OscMessage) -> Option<Self> {
+        if message.address != Self::address() {
+            return None;
+        }
+
+        match message.args.as_slice() {
+            [OscArg::String(seed), OscArg::Int(seq), elapsed, OscArg::String(state), speed, paused] => {
+                Some(Self {
+                    seed: seed.parse().ok()?,
+                    seq: *seq,
+                    elapsed: elapsed.as_f32()?,
+                    state: parse_state(state)?,
+                    speed: speed.as_f32()?,
+                    paused: paused.as_bool()?,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+fn parse_state(name: &str) -> Option<ExperienceState> {
+    [
+        ExperienceState::Loading,
+        ExperienceState::Ready,
+        ExperienceState::Running,
+        ExperienceState::Ending,
+        ExperienceState::Ended,
+        ExperienceState::Idle,
+    ]
+    .into_iter()
+    .find(|state| state.name() == name)
+}
+
+/// Is the timeline playing (or just finished) in this state?
+fn is_playing(state: ExperienceState) -> bool {
+    matches!(
+        state,
+        ExperienceState::Running | ExperienceState::Ending | ExperienceState::Ended
+    )
+}
+
+/// Leader side: socket and send bookkeeping
+#[derive(Resource)]
+pub struct SyncLeader {
+    socket: UdpSocket,
+    targets: Vec<SocketAddr>,
+    seed: u64,
+    seq: i32,
+    since_send: f32,
+    last_state: Option<ExperienceState>,
+    failed: bool,
+}
+
+impl SyncLeader {
+    pub fn new(targets: Vec<SocketAddr>, seed: u64) -> io::Result<Self> {
+        let socket = UdpSocket::bind(SocketAddr::from(([0, 0, 0, 0], 0)))?;
+        socket.set_broadcast(true)?;
+        Ok(Self {
+            socket,
+            targets,
+            seed,
+            seq: 0,
+            since_send: 0.0,
+            last_state: None,
+            failed: false,
+        })
+    }
+
+    fn send(&mut self, message: &SyncMessage) {
+        let packet = message.to_osc().encode();
+        for target in &self.targets {
+            if let Err(err) = self.socket.send_to(&packet, target) {
+                // One error is enough; the leader keeps running without followers
+                if !self.failed {
+                    self.failed = true;
+                    warn!(target: "lightwatch::sync", "Sync to {} failed: {}", target, err);
+                }
+            }
+        }
+    }
+}
+
+/// Follower side: socket and the latest leader sample
+#[derive(Resource)]
+pub struct SyncFollower {
+    socket: UdpSocket,
+    latest: Option<SyncMessage>,
+    /// Real time the latest sample arrived
+    received_at: f64,
+    /// No sample within `SyncConfig::timeout`
+    lost: bool,
+}
+
+impl SyncFollower {
+    /// Bind to `addr` (port 0 picks a free port)
+    pub fn bind(addr: SocketAddr) -> io::Result<Self> {
+        let socket = UdpSocket::bind(addr)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket,
+            latest: None,
+            received_at: 0.0,
+            lost: true,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Take in every waiting packet; true if a newer sample arrived
+    fn poll(&mut self, now: f64) -> bool {
+        let mut buffer = [0u8; MAX_PACKET_SIZE];
+        let mut updated = false;
+
+        loop {
+            let len = match self.socket.recv_from(&mut buffer) {
+                Ok((len, _)) => len,
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => {
+                    warn!(target: "lightwatch::sync", "Sync receive failed: {}", err);
+                    break;
+                }
+            };
+            let Ok(messages) = decode_packet(&buffer[..len]) else {
+                continue;
+            };
+
+            for sample in messages.iter().filter_map(SyncMessage::from_osc) {
+                // After losing the leader any sample will do, since a leader
+                // restarted with the same seed starts its sequence again
+                let newer = match &self.latest {
+                    Some(latest) if !self.lost => {
+                        sample.seed != latest.seed || seq_newer(sample.seq, latest.seq)
+                    }
+                    _ => true,
+                };
+                if newer {
+                    self.latest = Some(sample);
+                    self.received_at = now;
+                    updated = true;
+                }
+            }
+        }
+
+        updated
+    }
+
+    /// Where the leader's clock is now, allowing for time since the sample
+    fn estimate(&self, now: f64) -> Option<f32> {
+        let latest = self.latest.as_ref()?;
+        if latest.paused || latest.state != ExperienceState::Running {
+            return Some(latest.elapsed);
+        }
+        Some(latest.elapsed + (now - self.received_at) as f32 * latest.speed)
+    }
+}
+
+/// Send the clock every `SyncConfig::interval` and on every state change
+pub fn broadcast_sync(
+    config: Res<SyncConfig>,
+    mut leader: ResMut<SyncLeader>,
+    real_time: Res<Time<Real>>,
+    clock: Res<ExperienceClock>,
+    control: Res<TimeControl>,
+    state: Res<State<ExperienceState>>,
+) {
+    leader.since_send += real_time.delta_seconds();
+
+    let state = *state.get();
+    let state_changed = leader.last_state != Some(state);
+    if !state_changed && leader.since_send < config.interval {
+        return;
+    }
+
+    leader.since_send = 0.0;
+    leader.last_state = Some(state);
+    leader.seq = leader.seq.wrapping_add(1);
+
+    let message = SyncMessage {
+        seed: leader.seed,
+        seq: leader.seq,
+        elapsed: clock.elapsed(),
+        state,
+        speed: control.speed,
+        paused: control.paused,
+    };
+    leader.send(&message);
+}
+
+/// Lock to the leader (exclusive, runs before seeks are applied)
+pub fn follow_leader(world: &mut World) {
+    let config = world.resource::<SyncConfig>().clone();
+    let now = world.resource::<Time<Real>>().elapsed_seconds_f64();
+
+    let (sample, estimate) = {
+        let Some(mut follower) = world.get_resource_mut::<SyncFollower>() else {
+            return;
+        };
+        let updated = follower.poll(now);
+
+        if updated && follower.lost {
+            follower.lost = false;
+            info!(target: "lightwatch::sync", "Following leader");
+        }
+        if !follower.lost && now - follower.received_at > config.timeout as f64 {
+            follower.lost = true;
+            warn!(target: "lightwatch::sync", "Lost the leader, running free");
+        }
+        if follower.lost {
+            return;
+        }
+
+        let Some(sample) = follower.latest.clone() else {
+            return;
+        };
+        let estimate = follower.estimate(now).unwrap_or(sample.elapsed);
+        (sample, estimate)
+    };
+
+    let state = *world.resource::<State<ExperienceState>>().get();
+    if world.resource::<ExperienceRng>().seed() != sample.seed {
+        adopt_leader_seed(world, sample.seed, state);
+        return;
+    }
+    if state == ExperienceState::Loading {
+        return;
+    }
+
+    // Follow the leader's state first
+    match (is_playing(sample.state), is_playing(state)) {
+        // The leader's loop timer decides when every screen resets
+        (false, true) => {
+            if sample.state == ExperienceState::Ready {
+                return_to_ready(world, ResetReason::Sync);
+            }
+            return;
+        }
+        (true, false) if state == ExperienceState::Ready => {
+            start_run(world, "sync");
+            if estimate > config.seek_threshold {
+                world.resource_mut::<ExperienceController>().seek(estimate);
+            }
+            return;
+        }
+        (true, true) => {}
+        _ => return,
+    }
+
+    {
+        let mut control = world.resource_mut::<TimeControl>();
+        if control.paused != sample.paused {
+            control.paused = sample.paused;
+        }
+    }
+
+    // Past the end both clocks stop; nothing left to correct
+    let elapsed = world.resource::<ExperienceClock>().elapsed();
+    let duration = world.resource::<ExperienceClock>().duration();
+    if elapsed >= duration && estimate >= duration {
+        return;
+    }
+
+    match config.correction(elapsed, estimate, sample.speed) {
+        SyncCorrection::Seek(target) => {
+            world.resource_mut::<ExperienceController>().seek(target);
+            world.resource_mut::<TimeControl>().speed = sample.speed;
+
+            let drift = elapsed - estimate;
+            info!(
+                target: "lightwatch::sync",
+                "Drifted {:.2}s from the leader, seeking to {:.2}s",
+                drift,
+                target
+            );
+            wide_event!("sync_correction")
+                .with_f32("drift", drift)
+                .with_f32("to", target)
+                .emit(world.resource::<WideEventLog>(), elapsed);
+        }
+        SyncCorrection::Speed(speed) => {
+            let mut control = world.resource_mut::<TimeControl>();
+            if control.speed != speed {
+                control.speed = speed;
+            }
+        }
+    }
+}
+
+/// Draw from the leader's seed from now on; a run in progress was drawn from
+/// the old one, so it goes back to the ready screen and starts again in step
+fn adopt_leader_seed(world: &mut World, seed: u64, state: ExperienceState) {
+    let previous = world.resource::<ExperienceRng>().seed();
+    world.insert_resource(ExperienceRng::new(seed));
+    info!(
+        target: "lightwatch::sync",
+        "Using the leader's seed {} instead of {}",
+        seed,
+        previous
+    );
+
+    let elapsed = world.resource::<ExperienceClock>().elapsed();
+    wide_event!("sync_seed")
+        .with_str("seed", seed.to_string())
+        .with_str("previous", previous.to_string())
+        .emit(world.resource::<WideEventLog>(), elapsed);
+
+    if is_playing(state) {
+        return_to_ready(world, ResetReason::Sync);
+    }
+}
+
+/// Sync plugin; does nothing unless a `SyncConfig` is inserted before `LightwatchPlugin`
+pub struct SyncPlugin;
+
+impl Plugin for SyncPlugin {
+    fn build(&self, app: &mut App) {
+        let Some(config) = app.world().get_resource::<SyncConfig>().cloned() else {
+            return;
+        };
+
+        let seed = app.init_resource::<ExperienceRng>().world().resource::<ExperienceRng>().seed();
+
+        match config.role {
+            SyncRole::Leader { targets } => match SyncLeader::new(targets.clone(), seed) {
+                Ok(leader) => {
+                    info!(target: "lightwatch::sync", "Leading followers at {:?}", targets);
+                    app.insert_resource(leader)
+                        .add_systems(PostUpdate, broadcast_sync);
+                }
+                Err(err) => error!(
+                    target: "lightwatch::sync",
+                    "Cannot open sync socket: {}",
+                    err
+                ),
+            },
+            SyncRole::Follower { listen } => match SyncFollower::bind(listen) {
+                Ok(follower) => {
+                    info!(
+                        target: "lightwatch::sync",
+                        "Waiting for a leader on {}",
+                        follower.local_addr().unwrap_or(listen)
+                    );
+                    app.insert_resource(follower).add_systems(
+                        PreUpdate,
+                        follow_leader.before(super::apply_pending_seek),
+                    );
+                }
+                Err(err) => error!(
+                    target: "lightwatch::sync",
+                    "Cannot listen for a leader on {}: {}",
+                    listen,
+                    err
+                ),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::RngCore;
+    use std::time::{Duration, Instant};
+
+    fn sample(seq: i32, elapsed: f32) -> SyncMessage {
+        SyncMessage {
+            seed: 1,
+            seq,
+            elapsed,
+            state: ExperienceState::Running,
+            speed: 1.0,
+            paused: false,
+        }
+    }
+
+    /// Poll until a newer sample arrives (loopback is quick but not immediate)
+    fn receive(follower: &mut SyncFollower, now: f64) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while Instant::now() < deadline {
+            if follower.poll(now) {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        false
+    }
+
+    #[test]
+    fn follower_corrects_drift_from_a_leader_over_loopback() {
+        let mut follower = SyncFollower::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let addr = follower.local_addr().unwrap();
+        let mut leader = SyncLeader::new(vec![addr], 1).unwrap();
+        let config = SyncConfig::follower(addr);
+
+        leader.send(&sample(1, 30.0));
+        assert!(receive(&mut follower, 10.0));
+        assert_eq!(follower.estimate(10.0), Some(30.0));
+        assert_eq!(follower.estimate(10.5), Some(30.5));
+
+        let estimate = follower.estimate(10.0).unwrap();
+        let speed = |elapsed| match config.correction(elapsed, estimate, 1.0) {
+            SyncCorrection::Speed(speed) => speed,
+            other => panic!("expected a nudge, got {:?}", other),
+        };
+
+        // Small drifts are taken out gradually, never faster than ±5%
+        assert!((speed(29.98) - 1.01).abs() < 1e-5);
+        assert!((speed(30.4) - (1.0 - config.max_nudge)).abs() < 1e-6);
+        assert!((speed(29.6) - (1.0 + config.max_nudge)).abs() < 1e-6);
+
+        // Past the threshold the follower jumps to the leader
+        assert_eq!(
+            config.correction(estimate + config.seek_threshold + 0.1, estimate, 1.0),
+            SyncCorrection::Seek(30.0)
+        );
+        assert_eq!(
+            config.correction(estimate - config.seek_threshold - 0.1, estimate, 1.0),
+            SyncCorrection::Seek(30.0)
+        );
+    }
+
+    #[test]
+    fn follower_ignores_stale_samples_across_wraparound() {
+        let mut follower = SyncFollower::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let mut leader = SyncLeader::new(vec![follower.local_addr().unwrap()], 1).unwrap();
+
+        leader.send(&sample(i32::MAX, 40.0));
+        assert!(receive(&mut follower, 0.0));
+        follower.lost = false;
+
+        // The sequence wraps to i32::MIN, which is still newer
+        leader.send(&sample(i32::MIN, 40.1));
+        assert!(receive(&mut follower, 0.0));
+        assert_eq!(follower.latest.as_ref().map(|latest| latest.seq), Some(i32::MIN));
+
+        // A sample from before the wrap arrives late and is dropped
+        leader.send(&sample(i32::MAX - 1, 39.9));
+        assert!(!receive(&mut follower, 0.0));
+        assert_eq!(follower.estimate(0.0), Some(40.1));
+
+        assert!(seq_newer(1, 0));
+        assert!(seq_newer(i32::MIN, i32::MAX));
+        assert!(!seq_newer(i32::MAX, i32::MIN));
+        assert!(!seq_newer(5, 5));
+    }
+
+    #[test]
+    fn seed_survives_the_osc_round_trip() {
+        let message = SyncMessage {
+            seed: u64::MAX,
+            ..sample(3, 12.5)
+        };
+        let decoded = decode_packet(&message.to_osc().encode()).unwrap();
+        assert_eq!(SyncMessage::from_osc(&decoded[0]), Some(message));
+    }
+
+    #[test]
+    fn follower_adopts_the_leader_seed() {
+        let mut world = World::new();
+        world.insert_resource(ExperienceRng::new(3));
+        world.init_resource::<ExperienceClock>();
+        world.init_resource::<WideEventLog>();
+
+        adopt_leader_seed(&mut world, 42, ExperienceState::Ready);
+
+        let mut rng = world.resource_mut::<ExperienceRng>();
+        assert_eq!(rng.seed(), 42);
+        assert_eq!(rng.next_u64(), ExperienceRng::new(42).next_u64());
+    }
+}
@@ -1,15 +1,21 @@
 //! Development time control - pause, speed, phase jumping
+//!
+//! The clock follows `TimeControl` in all builds, so launch flags and remote
+//! control can set it too; the keys are dev only. Phase jumps go through `ExperienceController::seek`, so the world is
+//! rebuilt at the target time.
 
 #![allow(dead_code)]
 
 use bevy::prelude::*;
 
-/// Development time control
+#[cfg(debug_assertions)]
+use super::{ExperienceController, Phase, PhaseTable};
+
+/// Pause and speed applied to the experience clock
 #[derive(Resource)]
 pub struct TimeControl {
     pub paused: bool,
     pub speed: f32,
-    pub scrub_position: Option<f32>,
 }
 
 impl Default for TimeControl {
@@ -17,14 +23,18 @@ impl Default for TimeControl {
         Self {
             paused: false,
             speed: 1.0,
-            scrub_position: None,
         }
     }
 }
 
 /// Handle time control inputs (development only)
 #[cfg(debug_assertions)]
-pub fn handle_time_control(keyboard: Res<ButtonInput<KeyCode>>, mut control: ResMut<TimeControl>) {
+pub fn handle_time_control(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut control: ResMut<TimeControl>,
+    mut controller: ResMut<ExperienceController>,
+    table: Res<PhaseTable>,
+) {
     // Space: pause/resume
     if keyboard.just_pressed(KeyCode::Space) {
         control.paused = !control.paused;
@@ -47,18 +57,18 @@ pub fn handle_time_control(keyboard: Res<ButtonInput<KeyCode>>, mut control: Res
 
     // Number keys: jump to phase
     let jumps = [
-        (KeyCode::Digit1, 0.0, "signal"),
-        (KeyCode::Digit2, 2.0, "bang"),
-        (KeyCode::Digit3, 12.0, "awakening"),
-        (KeyCode::Digit4, 27.0, "discovery"),
-        (KeyCode::Digit5, 57.0, "connection"),
-        (KeyCode::Digit6, 87.0, "acceptance"),
-        (KeyCode::Digit0, 140.0, "end"),
+        (KeyCode::Digit1, table.start(Phase::Signal), "signal"),
+        (KeyCode::Digit2, table.start(Phase::Bang), "bang"),
+        (KeyCode::Digit3, table.start(Phase::Awakening), "awakening"),
+        (KeyCode::Digit4, table.start(Phase::Discovery), "discovery"),
+        (KeyCode::Digit5, table.start(Phase::Connection), "connection"),
+        (KeyCode::Digit6, table.start(Phase::Acceptance), "acceptance"),
+        (KeyCode::Digit0, table.duration() - 3.0, "end"),
     ];
 
     for (key, time, phase) in jumps {
         if keyboard.just_pressed(key) {
-            control.scrub_position = Some(time);
+            controller.seek(time);
             info!(
                 target: "lightwatch::debug",
                 "Jumping to {} ({:.0}s)", phase, time
@@ -0,0 +1,464 @@
+//! Timeline data file - moment schedule authored in RON
+//!
+//! Writers edit `assets/timeline/lightwatch.ron` instead of Rust. The file is
+//! parsed and validated at startup; any error falls back to the built-in
+//! schedule in `create_moment_schedule()`.
+//!
+//! Absolute times are written against the standard cut; a cut that stretches
+//! or shortens a phase moves its moments with it (`PhaseTable::rescale`).
+//! Moments anchored to other moments or travelers have no fixed time;
+//! `estimate_moment_times` places them after the moment that sets their
+//! anchor, which is as early as they can fire.
+
+use std::fmt;
+use std::path::Path;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::phase_controller::{Moment, MomentAction, MomentAnchor};
+use super::phase_table::PhaseTable;
+
+/// Default location of the timeline data file
+pub const DEFAULT_TIMELINE_PATH: &str = "assets/timeline/lightwatch.ron";
+
+/// Which timeline file to load at startup
+#[derive(Resource, Debug, Clone)]
+pub struct TimelineConfig {
+    pub path: String,
+}
+
+impl Default for TimelineConfig {
+    fn default() -> Self {
+        Self {
+            path: DEFAULT_TIMELINE_PATH.to_string(),
+        }
+    }
+}
+
+/// On-disk layout of a timeline file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineFile {
+    pub moments: Vec<Moment>,
+}
+
+/// A single problem found while validating a timeline
+#[derive(Debug, Clone)]
+pub struct TimelineIssue {
+    /// 1-based line in the source file, if it could be located
+    pub line: Option<usize>,
+    /// Moment name the issue belongs to
+    pub moment: String,
+    pub message: String,
+}
+
+impl fmt::Display for TimelineIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: '{}': {}", line, self.moment, self.message),
+            None => write!(f, "'{}': {}", self.moment, self.message),
+        }
+    }
+}
+
+/// Errors produced while loading a timeline file
+#[derive(Debug)]
+pub enum TimelineError {
+    /// File could not be read
+    Io { path: String, message: String },
+    /// RON syntax or schema error
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// File parsed but failed validation
+    Invalid(Vec<TimelineIssue>),
+}
+
+impl fmt::Display for TimelineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimelineError::Io { path, message } => write!(f, "{}: {}", path, message),
+            TimelineError::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            TimelineError::Invalid(issues) => {
+                write!(f, "{} invalid moment(s)", issues.len())?;
+                for issue in issues {
+                    write!(f, "\n  {}", issue)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for TimelineError {}
+
+/// Read, parse and validate a timeline file
+pub fn load_timeline(path: impl AsRef<Path>) -> Result<Vec<Moment>, TimelineError> {
+    let path = path.as_ref();
+    let source = std::fs::read_to_string(path).map_err(|err| TimelineError::Io {
+        path: path.display().to_string(),
+        message: err.to_string(),
+    })?;
+
+    parse_timeline(&source)
+}
+
+/// Parse and validate timeline source text
+pub fn parse_timeline(source: &str) -> Result<Vec<Moment>, TimelineError> {
+    let file: TimelineFile = ron::de::from_str(source).map_err(|err| TimelineError::Parse {
+        line: err.position.line,
+        column: err.position.col,
+        message: err.code.to_string(),
+    })?;
+
+    let issues = validate_moments(&file.moments, Some(source));
+    if !issues.is_empty() {
+        return Err(TimelineError::Invalid(issues));
+    }
+
+    // Anchored moments sort by when they can first fire; ones that never can go last
+    let estimates = estimate_moment_times(&file.moments, &PhaseTable::default());
+    let mut order: Vec<(f32, Moment)> = estimates
+        .into_iter()
+        .map(|estimate| estimate.unwrap_or(f32::INFINITY))
+        .zip(file.moments)
+        .collect();
+    order.sort_by(|a, b| a.0.total_cmp(&b.0));
+    Ok(order.into_iter().map(|(_, moment)| moment).collect())
+}
+
+/// Earliest clock time each moment can fire on the cut described by `table`
+///
+/// Anchored moments are placed after the first moment that sets their anchor;
+/// a traveler's fade finishing is placed at the start of the fade. `None`
+/// when nothing sets the anchor or the anchors form a loop.
+pub fn estimate_moment_times(moments: &[Moment], table: &PhaseTable) -> Vec<Option<f32>> {
+    (0..moments.len())
+        .map(|index| resolve_time(moments, table, index, &mut Vec::new()).unwrap_or(None))
+        .collect()
+}
+
+/// Moments that set `anchor` when they fire
+fn anchor_setters<'a>(
+    moments: &'a [Moment],
+    anchor: &'a MomentAnchor,
+) -> impl Iterator<Item = usize> + 'a {
+    // Fade ends are set by the simulation, some time after the fade starts
+    let anchor = match anchor.fading_traveler() {
+        Some(id) => MomentAnchor::TravelerFading(id),
+        None => anchor.clone(),
+    };
+    moments
+        .iter()
+        .enumerate()
+        .filter(move |(_, moment)| anchor.is_set_by(moment))
+        .map(|(index, _)| index)
+}
+
+/// `Err` when the anchor chain loops back on itself
+fn resolve_time(
+    moments: &[Moment],
+    table: &PhaseTable,
+    index: usize,
+    visiting: &mut Vec<usize>,
+) -> Result<Option<f32>, ()> {
+    let moment = &moments[index];
+    let Some(anchor) = &moment.after else {
+        return Ok(Some(table.rescale(moment.time)));
+    };
+    if visiting.contains(&index) {
+        return Err(());
+    }
+
+    visiting.push(index);
+    let mut earliest: Option<f32> = None;
+    for setter in anchor_setters(moments, anchor) {
+        if let Some(time) = resolve_time(moments, table, setter, visiting)? {
+            earliest = Some(earliest.map_or(time, |best| best.min(time)));
+        }
+    }
+    visiting.pop();
+
+    Ok(earliest.map(|at| at + moment.time))
+}
+
+/// Structural checks on a moment list
+///
+/// When `source` is given, issues are located by searching for the moment's
+/// quoted name in the text.
+pub fn validate_moments(moments: &[Moment], source: Option<&str>) -> Vec<TimelineIssue> {
+    let mut issues = Vec::new();
+
+    if moments.is_empty() {
+        issues.push(TimelineIssue {
+            line: None,
+            moment: String::new(),
+            message: "timeline contains no moments".into(),
+        });
+        return issues;
+    }
+
+    // Loops don't depend on the cut
+    let standard = PhaseTable::default();
+    for (index, moment) in moments.iter().enumerate() {
+        // Count earlier moments with the same name so duplicates point at the right line
+        let occurrence = moments[..index]
+            .iter()
+            .filter(|m| m.name == moment.name)
+            .count();
+        let line = source.and_then(|s| line_of_name(s, &moment.name, occurrence));
+        let mut report = |message: String| {
+            issues.push(TimelineIssue {
+                line,
+                moment: moment.name.clone(),
+                message,
+            });
+        };
+
+        if moment.name.trim().is_empty() {
+            report("moment name is empty".into());
+        }
+        if occurrence > 0 {
+            report("duplicate moment name".into());
+        }
+        if !moment.time.is_finite() || moment.time < 0.0 {
+            report(format!("time {} must be a non-negative number", moment.time));
+        }
+
+        match &moment.after {
+            Some(MomentAnchor::Moment(name)) if *name == moment.name => {
+                report("anchored to itself".into());
+            }
+            Some(MomentAnchor::Moment(name)) if !moments.iter().any(|m| m.name == *name) => {
+                report(format!("anchored to unknown moment '{}'", name));
+            }
+            Some(MomentAnchor::TravelerDimmed { below, .. })
+                if !(*below > 0.0 && *below <= 1.0) =>
+            {
+                report(format!("dimmed threshold {} must be above 0 and at most 1", below));
+            }
+            Some(_) if resolve_time(moments, &standard, index, &mut Vec::new()).is_err() => {
+                report("anchor chain contains a loop".into());
+            }
+            _ => {}
+        }
+
+        match &moment.action {
+            MomentAction::ShowText { text, .. } | MomentAction::SignalTypewriter(text) => {
+                if text.trim().is_empty() {
+                    report("text is empty".into());
+                }
+            }
+            MomentAction::StartLayer(layer) | MomentAction::StopLayer(layer) => {
+                if layer.trim().is_empty() {
+                    report("layer name is empty".into());
+                }
+            }
+            MomentAction::PlayLeitmotif(motif) => {
+                if motif.trim().is_empty() {
+                    report("leitmotif name is empty".into());
+                }
+            }
+            MomentAction::CameraShake {
+                intensity,
+                duration,
+            }
+            | MomentAction::Glitch {
+                intensity,
+                duration,
+            } => {
+                if *intensity < 0.0 {
+                    report(format!("intensity {} must not be negative", intensity));
+                }
+                if *duration <= 0.0 {
+                    report(format!("duration {} must be positive", duration));
+                }
+            }
+            MomentAction::PlayNote { freq, dur, vol } => {
+                if *freq <= 0.0 {
+                    report(format!("frequency {} must be positive", freq));
+                }
+                if *dur <= 0.0 {
+                    report(format!("duration {} must be positive", dur));
+                }
+                if !(0.0..=1.0).contains(vol) {
+                    report(format!("volume {} must be between 0 and 1", vol));
+                }
+            }
+            MomentAction::EnvironmentChange { layer, action } => {
+                if layer.trim().is_empty() || action.trim().is_empty() {
+                    report("environment layer and action must be set".into());
+                }
+            }
+            MomentAction::TriggerGrief { mourner, deceased } => {
+                if mourner == deceased {
+                    report(format!("{} cannot grieve for itself", mourner.name()));
+                }
+            }
+            MomentAction::SyncTravelers(travelers) => {
+                if travelers.len() < 2 {
+                    report("sync needs at least two travelers".into());
+                }
+            }
+            MomentAction::Custom { kind, .. } => {
+                if kind.trim().is_empty() {
+                    report("custom action kind is empty".into());
+                }
+            }
+            MomentAction::SpawnTraveler(_)
+            | MomentAction::FadeTraveler(_)
+            | MomentAction::SetCameraBehavior(_)
+            | MomentAction::FocusOn(_)
+            | MomentAction::HideText
+            | MomentAction::BangStage(_) => {}
+        }
+    }
+
+    issues
+}
+
+/// Find the 1-based line of the nth `name: "name"` field in the source
+///
+/// Only `name` fields count, so anchors, text or other fields quoting the same
+/// string are passed over.
+pub(crate) fn line_of_name(source: &str, name: &str, occurrence: usize) -> Option<usize> {
+    let needle = format!("\"{}\"", name);
+    let (offset, _) = source
+        .match_indices(&needle)
+        .filter(|(offset, _)| is_name_field(&source[..*offset]))
+        .nth(occurrence)?;
+    Some(source[..offset].matches('\n').count() + 1)
+}
+
+/// Does `before` end in a `name:` field key (not `some_name:`)?
+fn is_name_field(before: &str) -> bool {
+    let Some(key) = before.trim_end().strip_suffix(':') else {
+        return false;
+    };
+    let Some(rest) = key.trim_end().strip_suffix("name") else {
+        return false;
+    };
+    !rest.ends_with(|c: char| c.is_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::phase_controller::{create_moment_schedule, MomentSource, PhaseController};
+    use crate::core::timeline_verify::{check_timeline, TimelineCheckConfig};
+
+    const VALID: &str = r#"(moments: [
+        (time: 1.0, name: "first", action: HideText),
+        (time: 0.5, name: "second", after: Some(Moment("first")), action: HideText),
+    ])"#;
+
+    #[test]
+    fn parses_and_orders_anchored_moments() {
+        let moments = parse_timeline(VALID).expect("valid timeline");
+        let names: Vec<&str> = moments.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["first", "second"]);
+        assert_eq!(
+            estimate_moment_times(&moments, &PhaseTable::default()),
+            [Some(1.0), Some(1.5)]
+        );
+    }
+
+    #[test]
+    fn moment_times_follow_a_stretched_cut() {
+        // Acceptance runs 87..173 instead of 87..143
+        let table = PhaseTable {
+            duration: 173.0,
+            ..default()
+        };
+        let source = r#"(moments: [
+        (time: 115.0, name: "first", action: HideText),
+        (time: 0.5, name: "second", after: Some(Moment("first")), action: HideText),
+        (time: 140.0, name: "late", action: HideText),
+    ])"#;
+        let moments = parse_timeline(source).expect("valid timeline");
+
+        assert_eq!(moments[0].due(&table), Some(130.0));
+        let estimates = estimate_moment_times(&moments, &table);
+        assert_eq!(estimates[..2], [Some(130.0), Some(130.5)]);
+        assert!(estimates[2].is_some_and(|late| late > 143.0 && late < 173.0));
+
+        // 140s stays inside the cut, now in its stretched Acceptance
+        let config = TimelineCheckConfig::for_table(&table);
+        let issues = check_timeline(&moments, Some(source), &config);
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn unknown_anchor_is_reported_on_its_line() {
+        let source = r#"(moments: [
+        (time: 1.0, name: "first", action: HideText),
+        (time: 0.5, name: "second", after: Some(Moment("missing")), action: HideText),
+    ])"#;
+
+        let Err(TimelineError::Invalid(issues)) = parse_timeline(source) else {
+            panic!("unknown anchor should fail validation");
+        };
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].moment, "second");
+        assert_eq!(issues[0].line, Some(3));
+        assert!(issues[0].message.contains("unknown moment 'missing'"));
+    }
+
+    #[test]
+    fn moment_past_the_end_is_reported() {
+        let source = r#"(moments: [
+        (time: 1.0, name: "first", action: HideText),
+        (time: 150.0, name: "late", action: HideText),
+    ])"#;
+        let moments = parse_timeline(source).expect("structurally valid");
+        let config = TimelineCheckConfig::default();
+
+        let issues = check_timeline(&moments, Some(source), &config);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].moment, "late");
+        assert_eq!(issues[0].line, Some(3));
+        assert!(issues[0].message.contains("outside the 143s experience"));
+    }
+
+    #[test]
+    fn parse_error_falls_back_to_builtin_schedule() {
+        let path = std::env::temp_dir().join(format!(
+            "lightwatch-broken-timeline-{}.ron",
+            std::process::id()
+        ));
+        std::fs::write(&path, "(moments: [ (time: 1.0, name: ").unwrap();
+
+        let path_str = path.display().to_string();
+        assert!(matches!(
+            load_timeline(&path_str),
+            Err(TimelineError::Parse { line: 1, .. })
+        ));
+        let controller = PhaseController::load_or_builtin(&path_str);
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(controller.source, MomentSource::Builtin);
+        let builtin: Vec<String> = create_moment_schedule().into_iter().map(|m| m.name).collect();
+        let loaded: Vec<String> = controller.moments.into_iter().map(|m| m.name).collect();
+        assert_eq!(loaded, builtin);
+    }
+
+    #[test]
+    fn line_of_name_skips_anchors_and_other_fields() {
+        let source = r#"(moments: [
+        (time: 0.5, name: "second", after: Some(Moment("first")), action: HideText),
+        (time: 1.0, motif_name: "first", name: "first", action: HideText),
+        (time: 2.0, name:"first", action: HideText),
+    ])"#;
+
+        assert_eq!(line_of_name(source, "first", 0), Some(3));
+        assert_eq!(line_of_name(source, "first", 1), Some(4));
+        assert_eq!(line_of_name(source, "first", 2), None);
+        assert_eq!(line_of_name(source, "second", 0), Some(2));
+    }
+}
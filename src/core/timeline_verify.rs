@@ -1,8 +1,18 @@
 //! Timeline verification - Ensures phases transition at correct times
+//!
+//! `verify_timeline` watches a live run; `check_timeline` inspects a moment
+//! schedule offline, before a show (`lightwatch --check-timeline`).
+
+use std::collections::HashMap;
 
 use bevy::prelude::*;
 
+use super::timeline_data::{estimate_moment_times, line_of_name, TimelineError, TimelineIssue};
+use super::phase_table::{load_phase_table, PhaseTable};
+use super::{Moment, MomentAction, TravelerId};
 use super::{ExperienceClock, Phase};
+use crate::audio::AUDIO_LAYERS;
+use crate::text::TextConfig;
 
 /// Timeline verification resource
 #[derive(Resource, Default)]
@@ -15,6 +25,13 @@ pub struct TimelineVerification {
     logged: bool,
 }
 
+impl TimelineVerification {
+    /// Treat `phase` as already entered, so a seek into it is not reported as late
+    pub fn skip_to(&mut self, phase: Phase) {
+        self.last_phase = Some(phase);
+    }
+}
+
 /// Verify timeline events occur at correct times
 pub fn verify_timeline(
     clock: Res<ExperienceClock>,
@@ -29,15 +46,7 @@ pub fn verify_timeline(
         verify.phases_entered.push((phase, elapsed));
 
         // Verify timing
-        let expected = match phase {
-            Phase::Signal => 0.0,
-            Phase::Bang => 2.0,
-            Phase::Awakening => 12.0,
-            Phase::Discovery => 27.0,
-            Phase::Connection => 57.0,
-            Phase::Acceptance => 87.0,
-            Phase::Ended => 143.0,
-        };
+        let expected = clock.phase_table().start(phase);
 
         let delta = (elapsed - expected).abs();
         if delta > 0.5 {
@@ -61,7 +70,7 @@ pub fn log_timeline_verification(
     clock: Res<ExperienceClock>,
     mut verify: ResMut<TimelineVerification>,
 ) {
-    if clock.elapsed() >= 143.0 && !verify.logged {
+    if clock.elapsed() >= clock.duration() && !verify.logged {
         verify.logged = true;
 
         debug!(target: "lightwatch::timeline", "=== Timeline Verification ===");
@@ -71,3 +80,199 @@ pub fn log_timeline_verification(
         info!(target: "lightwatch::timeline", "Experience complete at {:.1}s", clock.elapsed());
     }
 }
+
+/// Limits used by the offline timeline check
+#[derive(Debug, Clone)]
+pub struct TimelineCheckConfig {
+    /// Cut the schedule is checked on; moment times are rescaled onto it
+    pub table: PhaseTable,
+    /// Typewriter speed used for `ShowText`
+    pub chars_per_second: f32,
+    /// Hold time after a line finishes typing
+    pub hold_duration: f32,
+}
+
+impl Default for TimelineCheckConfig {
+    fn default() -> Self {
+        Self::for_table(&PhaseTable::default())
+    }
+}
+
+impl TimelineCheckConfig {
+    /// Limits for the cut described by `table`
+    pub fn for_table(table: &PhaseTable) -> Self {
+        let text = TextConfig::new(Handle::default());
+        Self {
+            table: table.clone(),
+            chars_per_second: text.chars_per_second,
+            hold_duration: text.hold_duration,
+        }
+    }
+}
+
+/// Check a moment schedule for narrative problems without running it
+///
+/// Moments are checked where they land on `config.table`; anchored ones at
+/// the earliest time they can fire. When `source` is given, issues carry the
+/// line of the moment in the file.
+pub fn check_timeline(
+    moments: &[Moment],
+    source: Option<&str>,
+    config: &TimelineCheckConfig,
+) -> Vec<TimelineIssue> {
+    let mut issues = Vec::new();
+
+    let mut order: Vec<(f32, &Moment)> = Vec::with_capacity(moments.len());
+    let duration = config.table.duration();
+    for (moment, estimate) in moments.iter().zip(estimate_moment_times(moments, &config.table)) {
+        match (estimate, &moment.after) {
+            (Some(time), _) => order.push((time, moment)),
+            (None, Some(anchor)) => issues.push(TimelineIssue {
+                line: source.and_then(|s| line_of_name(s, &moment.name, 0)),
+                moment: moment.name.clone(),
+                message: format!("never fires: nothing in the timeline sets {:?}", anchor),
+            }),
+            (None, None) => {}
+        }
+    }
+    order.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut spawned: HashMap<TravelerId, f32> = HashMap::new();
+    let mut faded: HashMap<TravelerId, f32> = HashMap::new();
+
+    for (index, &(time, moment)) in order.iter().enumerate() {
+        let line = source.and_then(|s| line_of_name(s, &moment.name, 0));
+        let mut report = |message: String| {
+            issues.push(TimelineIssue {
+                line,
+                moment: moment.name.clone(),
+                message,
+            });
+        };
+
+        if time < 0.0 || time > duration {
+            report(format!(
+                "scheduled at {:.2}s, outside the {:.0}s experience",
+                time, duration
+            ));
+        }
+
+        match &moment.action {
+            MomentAction::SpawnTraveler(id) => {
+                if let Some(at) = spawned.get(id) {
+                    report(format!("{} already spawned at {:.2}s", id.name(), at));
+                } else {
+                    spawned.insert(*id, time);
+                }
+            }
+            MomentAction::FadeTraveler(id) => {
+                if !spawned.contains_key(id) {
+                    report(format!("fades {} before it spawns", id.name()));
+                }
+                if let Some(at) = faded.get(id) {
+                    report(format!("{} already faded at {:.2}s", id.name(), at));
+                } else {
+                    faded.insert(*id, time);
+                }
+            }
+            MomentAction::TriggerGrief { mourner, deceased } => {
+                if !spawned.contains_key(mourner) {
+                    report(format!("mourner {} has not spawned yet", mourner.name()));
+                }
+                if let Some(at) = faded.get(mourner) {
+                    report(format!(
+                        "mourner {} has already faded at {:.2}s",
+                        mourner.name(),
+                        at
+                    ));
+                }
+                if !spawned.contains_key(deceased) {
+                    report(format!("{} is mourned but never spawned", deceased.name()));
+                }
+            }
+            MomentAction::ShowText { text, .. } => {
+                let needed =
+                    text.chars().count() as f32 / config.chars_per_second + config.hold_duration;
+                let next_text = order[index + 1..]
+                    .iter()
+                    .find(|(_, m)| matches!(m.action, MomentAction::ShowText { .. }));
+
+                match next_text {
+                    Some((next_time, next)) if next_time - time < needed => {
+                        report(format!(
+                            "needs {:.1}s on screen but '{}' starts {:.1}s later",
+                            needed,
+                            next.name,
+                            next_time - time
+                        ));
+                    }
+                    None if time + needed > duration => {
+                        report(format!(
+                            "needs {:.1}s on screen but the experience ends {:.1}s later",
+                            needed,
+                            duration - time
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+            MomentAction::StartLayer(layer) | MomentAction::StopLayer(layer)
+                if !AUDIO_LAYERS.contains(&layer.as_str()) =>
+            {
+                report(format!(
+                    "audio layer '{}' is not handled (known: {})",
+                    layer,
+                    AUDIO_LAYERS.join(", ")
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    issues
+}
+
+/// Load a timeline file and check it
+pub fn check_timeline_file(
+    path: &str,
+    config: &TimelineCheckConfig,
+) -> Result<Vec<TimelineIssue>, TimelineError> {
+    let moments = super::timeline_data::load_timeline(path)?;
+    let source = std::fs::read_to_string(path).ok();
+    Ok(check_timeline(&moments, source.as_deref(), config))
+}
+
+/// Print a timeline check report against the phase table the app would load;
+/// returns true when both files are clean
+pub fn run_timeline_check(path: &str, phase_table: &str) -> bool {
+    println!("Checking timeline {} against {}", path, phase_table);
+
+    let (table, table_ok) = match load_phase_table(phase_table) {
+        Ok(table) => (table, true),
+        Err(err) => {
+            println!("error: {}", err);
+            println!("Checking against the standard cut instead");
+            (PhaseTable::default(), false)
+        }
+    };
+
+    match check_timeline_file(path, &TimelineCheckConfig::for_table(&table)) {
+        Ok(issues) if issues.is_empty() => {
+            if table_ok {
+                println!("OK: no problems found");
+            }
+            table_ok
+        }
+        Ok(issues) => {
+            for issue in &issues {
+                println!("  {}", issue);
+            }
+            println!("{} problem(s) found", issues.len());
+            false
+        }
+        Err(err) => {
+            println!("error: {}", err);
+            false
+        }
+    }
+}
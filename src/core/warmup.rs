@@ -0,0 +1,359 @@
+//! Pipeline warm-up - compile render pipelines during Loading
+//!
+//! The first time a material and mesh combination is drawn, `PipelineCache`
+//! compiles a pipeline for it, which stalls that frame. Plugins register a
+//! spawner for each combination they draw during the run; during Loading the
+//! spawners put a tiny copy of each in front of the camera (under the loading
+//! screen) and keep them there until the render world has no pipelines left
+//! to compile. Pipelines compiled after Loading are logged as
+//! `pipelines_compiled`, so anything the warm-up missed shows up in the log.
+
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use bevy::prelude::*;
+use bevy::render::render_resource::{CachedPipelineState, PipelineCache};
+use bevy::render::{Render, RenderApp, RenderSet};
+
+use super::clock::ExperienceClock;
+use super::loading::LoadingTracker;
+use super::logging::WideEventLog;
+use super::state::ExperienceState;
+use crate::camera::ExperienceCamera;
+use crate::wide_event;
+
+/// Loading task name
+const WARMUP_TASK: &str = "pipeline warm-up";
+
+/// Frames the warm-up entities stay even if nothing seems to compile; covers
+/// the render world running a frame behind
+const MIN_WARMUP_FRAMES: u32 = 3;
+
+/// Frames after which the warm-up gives up waiting and lets Loading finish
+const MAX_WARMUP_FRAMES: u32 = 600;
+
+/// Spawns one warm-up copy of something drawn during the run at `transform`;
+/// returns the root entity, or None if there is nothing to copy
+pub type WarmupSpawner = Arc<dyn Fn(&mut World, Transform) -> Option<Entity> + Send + Sync>;
+
+/// Pipeline counts from the render world's `PipelineCache`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PipelineCounts {
+    pub total: usize,
+    pub compiled: usize,
+    pub failed: usize,
+    /// Queued or still compiling
+    pub waiting: usize,
+}
+
+/// Pipeline counts shared between the render world and the main world
+#[derive(Resource, Clone, Default)]
+pub struct PipelineStats {
+    counts: Arc<Mutex<Option<PipelineCounts>>>,
+}
+
+impl PipelineStats {
+    /// Latest counts, None before the render world has reported
+    pub fn counts(&self) -> Option<PipelineCounts> {
+        self.counts.lock().ok().and_then(|counts| *counts)
+    }
+}
+
+#[derive(Default)]
+enum WarmupProgress {
+    #[default]
+    Waiting,
+    Running {
+        entities: Vec<Entity>,
+        frames: u32,
+        started: Instant,
+        compiled_before: usize,
+    },
+    Done,
+}
+
+/// Registered warm-up spawners and the warm-up's progress
+#[derive(Resource, Default)]
+pub struct PipelineWarmup {
+    spawners: Vec<(String, WarmupSpawner)>,
+    progress: WarmupProgress,
+    /// Compiled count last logged by `report_new_pipelines`
+    last_compiled: usize,
+}
+
+impl PipelineWarmup {
+    pub fn register(&mut self, name: impl Into<String>, spawner: WarmupSpawner) {
+        self.spawners.push((name.into(), spawner));
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.spawners.iter().map(|(name, _)| name.as_str())
+    }
+}
+
+/// Marker for warm-up entities
+#[derive(Component)]
+pub struct WarmupEntity;
+
+/// Registering warm-up spawners from a plugin
+pub trait PipelineWarmupAppExt {
+    /// Warm up the pipelines for whatever `spawner` spawns
+    fn add_pipeline_warmup<F>(&mut self, name: &str, spawner: F) -> &mut Self
+    where
+        F: Fn(&mut World, Transform) -> Option<Entity> + Send + Sync + 'static;
+}
+
+impl PipelineWarmupAppExt for App {
+    fn add_pipeline_warmup<F>(&mut self, name: &str, spawner: F) -> &mut Self
+    where
+        F: Fn(&mut World, Transform) -> Option<Entity> + Send + Sync + 'static,
+    {
+        self.world_mut()
+            .get_resource_or_insert_with(PipelineWarmup::default)
+            .register(name, Arc::new(spawner));
+        self
+    }
+}
+
+/// Spawn `mesh` with `material` for the warm-up
+pub fn spawn_warmup_mesh<M: Material>(
+    world: &mut World,
+    transform: Transform,
+    mesh: Mesh,
+    material: M,
+) -> Entity {
+    let mesh = world.resource_mut::<Assets<Mesh>>().add(mesh);
+    let material = world.resource_mut::<Assets<M>>().add(material);
+    world
+        .spawn(MaterialMeshBundle {
+            mesh,
+            material,
+            transform,
+            ..default()
+        })
+        .id()
+}
+
+/// Spawn a visible copy of the first entity with `C` (for things spawned hidden
+/// at startup)
+pub fn spawn_warmup_copy<C: Component, M: Material>(
+    world: &mut World,
+    transform: Transform,
+) -> Option<Entity> {
+    let (mesh, material) = world
+        .query_filtered::<(&Handle<Mesh>, &Handle<M>), With<C>>()
+        .iter(world)
+        .next()
+        .map(|(mesh, material)| (mesh.clone(), material.clone()))?;
+    Some(
+        world
+            .spawn(MaterialMeshBundle {
+                mesh,
+                material,
+                transform,
+                ..default()
+            })
+            .id(),
+    )
+}
+
+/// Count pipelines by state (render world)
+fn count_pipelines(pipeline_cache: Res<PipelineCache>, stats: Res<PipelineStats>) {
+    let mut counts = PipelineCounts::default();
+    for pipeline in pipeline_cache.pipelines() {
+        counts.total += 1;
+        match pipeline.state {
+            CachedPipelineState::Ok(_) => counts.compiled += 1,
+            CachedPipelineState::Err(_) => counts.failed += 1,
+            _ => counts.waiting += 1,
+        }
+    }
+
+    if let Ok(mut shared) = stats.counts.lock() {
+        *shared = Some(counts);
+    }
+}
+
+/// Spawn the warm-up set in front of the camera, then remove it once nothing
+/// is left to compile
+pub fn run_pipeline_warmup(world: &mut World) {
+    let Some(counts) = world.resource::<PipelineStats>().counts() else {
+        return;
+    };
+
+    let progress = std::mem::take(&mut world.resource_mut::<PipelineWarmup>().progress);
+    let progress = match progress {
+        WarmupProgress::Waiting => start_warmup(world, counts),
+        WarmupProgress::Running {
+            entities,
+            frames,
+            started,
+            compiled_before,
+        } => {
+            let frames = frames + 1;
+            let settled = frames >= MIN_WARMUP_FRAMES && counts.waiting == 0;
+            if settled || frames >= MAX_WARMUP_FRAMES {
+                finish_warmup(world, entities, frames, started, compiled_before, counts);
+                WarmupProgress::Done
+            } else {
+                WarmupProgress::Running {
+                    entities,
+                    frames,
+                    started,
+                    compiled_before,
+                }
+            }
+        }
+        WarmupProgress::Done => WarmupProgress::Done,
+    };
+    world.resource_mut::<PipelineWarmup>().progress = progress;
+}
+
+fn start_warmup(world: &mut World, counts: PipelineCounts) -> WarmupProgress {
+    let Some(camera) = world
+        .query_filtered::<&GlobalTransform, With<ExperienceCamera>>()
+        .iter(world)
+        .next()
+        .map(|transform| transform.compute_transform())
+    else {
+        return WarmupProgress::Waiting;
+    };
+
+    // Tiny, just in front of the near plane: inside the frustum, so every
+    // pipeline gets specialized, but under the loading screen
+    let transform = Transform::from_translation(camera.translation + camera.forward() * 1.0)
+        .with_scale(Vec3::splat(0.001));
+
+    let spawners = world.resource::<PipelineWarmup>().spawners.clone();
+    let mut entities = Vec::new();
+    for (name, spawner) in &spawners {
+        match spawner(world, transform) {
+            Some(entity) => {
+                world
+                    .entity_mut(entity)
+                    .insert((WarmupEntity, Name::new("Warm-up")));
+                entities.push(entity);
+            }
+            None => debug!(target: "lightwatch::render", "Nothing to warm up for {}", name),
+        }
+    }
+
+    info!(
+        target: "lightwatch::render",
+        "Warming up {} pipelines ({} already compiled)",
+        entities.len(),
+        counts.compiled
+    );
+
+    WarmupProgress::Running {
+        entities,
+        frames: 0,
+        started: Instant::now(),
+        compiled_before: counts.compiled,
+    }
+}
+
+fn finish_warmup(
+    world: &mut World,
+    entities: Vec<Entity>,
+    frames: u32,
+    started: Instant,
+    compiled_before: usize,
+    counts: PipelineCounts,
+) {
+    let spawned = entities.len();
+    for entity in entities {
+        if let Some(entity) = world.get_entity_mut(entity) {
+            entity.despawn_recursive();
+        }
+    }
+
+    if counts.waiting > 0 {
+        warn!(
+            target: "lightwatch::render",
+            "Warm-up gave up after {} frames with {} pipelines still compiling",
+            frames,
+            counts.waiting
+        );
+    }
+
+    let seconds = started.elapsed().as_secs_f32();
+    info!(
+        target: "lightwatch::render",
+        "Warm-up compiled {} pipelines in {:.2}s ({} frames)",
+        counts.compiled.saturating_sub(compiled_before),
+        seconds,
+        frames
+    );
+    wide_event!("pipeline_warmup")
+        .with_i64("spawned", spawned as i64)
+        .with_i64(
+            "compiled",
+            counts.compiled.saturating_sub(compiled_before) as i64,
+        )
+        .with_i64("total", counts.total as i64)
+        .with_i64("failed", counts.failed as i64)
+        .with_i64("waiting", counts.waiting as i64)
+        .with_i64("frames", frames as i64)
+        .with_f32("seconds", seconds)
+        .emit(world.resource::<WideEventLog>(), 0.0);
+
+    world.resource_mut::<PipelineWarmup>().last_compiled = counts.compiled;
+    if let Some(mut loading) = world.get_resource_mut::<LoadingTracker>() {
+        loading.finish(WARMUP_TASK);
+    }
+}
+
+/// Log pipelines compiled after Loading (each is a likely frame hitch)
+pub fn report_new_pipelines(
+    stats: Res<PipelineStats>,
+    clock: Res<ExperienceClock>,
+    log: Res<WideEventLog>,
+    mut warmup: ResMut<PipelineWarmup>,
+) {
+    let Some(counts) = stats.counts() else {
+        return;
+    };
+    if counts.compiled <= warmup.last_compiled {
+        return;
+    }
+
+    let new = counts.compiled - warmup.last_compiled;
+    warmup.last_compiled = counts.compiled;
+    debug!(target: "lightwatch::render", "{} pipelines compiled after warm-up", new);
+    wide_event!("pipelines_compiled")
+        .with_i64("new", new as i64)
+        .with_i64("total", counts.compiled as i64)
+        .emit(&log, clock.elapsed());
+}
+
+/// Pipeline warm-up plugin (inert without a render world, e.g. headless)
+pub struct WarmupPlugin;
+
+impl Plugin for WarmupPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PipelineWarmup>()
+            .init_resource::<PipelineStats>()
+            .add_systems(
+                Update,
+                (
+                    run_pipeline_warmup.run_if(in_state(ExperienceState::Loading)),
+                    report_new_pipelines.run_if(not(in_state(ExperienceState::Loading))),
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let stats = app.world().resource::<PipelineStats>().clone();
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .insert_resource(stats)
+            .add_systems(Render, count_pipelines.in_set(RenderSet::Cleanup));
+
+        if let Some(mut loading) = app.world_mut().get_resource_mut::<LoadingTracker>() {
+            loading.add_task(WARMUP_TASK);
+        }
+    }
+}
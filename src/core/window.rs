@@ -1,16 +1,51 @@
 //! Window configuration and handling for LIGHTWATCH
 
 use bevy::prelude::*;
-use bevy::window::{PresentMode, WindowMode, WindowResolution};
+use bevy::window::{
+    MonitorSelection, PresentMode, WindowMode, WindowPosition, WindowResolution,
+};
+
+/// Launch-time window options
+#[derive(Debug, Clone)]
+pub struct WindowSettings {
+    /// Borderless fullscreen instead of a window
+    pub fullscreen: bool,
+    /// Window size in logical pixels (ignored when fullscreen)
+    pub width: f32,
+    pub height: f32,
+    /// Monitor to open on; the primary monitor when `None`
+    pub monitor: Option<usize>,
+}
+
+impl Default for WindowSettings {
+    fn default() -> Self {
+        Self {
+            fullscreen: false,
+            width: 1920.0,
+            height: 1080.0,
+            monitor: None,
+        }
+    }
+}
 
 /// Window configuration for LIGHTWATCH
-pub fn configure_window() -> WindowPlugin {
+pub fn configure_window(settings: &WindowSettings) -> WindowPlugin {
+    let monitor = match settings.monitor {
+        Some(index) => MonitorSelection::Index(index),
+        None => MonitorSelection::Primary,
+    };
+
     WindowPlugin {
         primary_window: Some(Window {
             title: "LIGHTWATCH".into(),
-            resolution: WindowResolution::new(1920., 1080.),
+            resolution: WindowResolution::new(settings.width, settings.height),
             present_mode: PresentMode::Fifo,  // Strict VSync - caps to display refresh rate
-            mode: WindowMode::Windowed,
+            mode: if settings.fullscreen {
+                WindowMode::BorderlessFullscreen
+            } else {
+                WindowMode::Windowed
+            },
+            position: WindowPosition::Centered(monitor),
             resizable: true,
             decorations: true,
             transparent: false,
@@ -5,11 +5,16 @@
 use bevy::prelude::*;
 use rand::Rng;
 
-use crate::core::{ExperienceClock, Phase};
+use crate::core::{
+    spawn_warmup_copy, ExperienceClock, ExperienceRng, InterpolatedTransform, PerformanceConfig,
+    Phase, PipelineWarmupAppExt,
+};
 
 /// Individual dust particle
 #[derive(Component, Debug)]
 pub struct DustParticle {
+    /// Spawn order; motes past `PerformanceConfig::max_particles` stay hidden
+    pub index: usize,
     /// Depth layer (0 = close, 1 = far)
     pub depth: f32,
     /// Base opacity
@@ -65,8 +70,8 @@ pub fn spawn_dust_particles(
     config: Res<DustConfig>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    mut rng: ResMut<ExperienceRng>,
 ) {
-    let mut rng = rand::thread_rng();
 
     // Shared mesh (tiny quad)
     let dust_mesh = meshes.add(Rectangle::new(0.1, 0.1));
@@ -87,7 +92,7 @@ pub fn spawn_dust_particles(
     });
 
     // Spawn particles
-    for _ in 0..config.particle_count {
+    for index in 0..config.particle_count {
         // Spherical distribution
         let theta = rng.gen::<f32>() * std::f32::consts::TAU;
         let phi = rng.gen::<f32>() * std::f32::consts::PI;
@@ -118,15 +123,19 @@ pub fn spawn_dust_particles(
         let layer = ((depth - config.min_depth) / (config.max_depth - config.min_depth) * 4.0)
             .floor() as u8;
 
+        let transform = Transform::from_translation(position).with_scale(Vec3::splat(size));
+
         commands.spawn((
+            InterpolatedTransform::new(transform),
             PbrBundle {
                 mesh: dust_mesh.clone(),
                 material: dust_material.clone(),
-                transform: Transform::from_translation(position).with_scale(Vec3::splat(size)),
+                transform,
                 visibility: Visibility::Hidden, // Start hidden
                 ..default()
             },
             DustParticle {
+                index,
                 depth: depth / config.max_depth, // Normalized
                 opacity,
                 velocity,
@@ -144,11 +153,14 @@ pub fn spawn_dust_particles(
 pub fn animate_dust(
     time: Res<Time>,
     config: Res<DustConfig>,
-    mut particles: Query<(&DustParticle, &mut Transform), With<DustMarker>>,
+    mut particles: Query<
+        (&DustParticle, &mut Transform, &mut InterpolatedTransform),
+        With<DustMarker>,
+    >,
 ) {
     let t = time.elapsed_seconds();
 
-    for (dust, mut transform) in particles.iter_mut() {
+    for (dust, mut transform, mut interpolated) in particles.iter_mut() {
         // Base drift
         let drift = dust.velocity * t;
 
@@ -165,16 +177,18 @@ pub fn animate_dust(
         // Wrap around when too far
         if transform.translation.length() > config.spawn_radius * 1.5 {
             transform.translation = -transform.translation.normalize() * config.min_depth;
+            interpolated.snap(*transform);
         }
     }
 }
 
-/// Control dust visibility by phase
+/// Control dust visibility by phase, within the particle budget
 pub fn update_dust_visibility(
     clock: Res<ExperienceClock>,
     config: Res<DustConfig>,
+    performance: Res<PerformanceConfig>,
     dust_assets: Option<Res<DustAssets>>,
-    mut particles: Query<&mut Visibility, With<DustMarker>>,
+    mut particles: Query<(&DustParticle, &mut Visibility), With<DustMarker>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
     let phase = clock.phase();
@@ -201,9 +215,9 @@ pub fn update_dust_visibility(
         }
     }
 
-    // Update visibility for all particles
-    for mut visibility in particles.iter_mut() {
-        *visibility = if visible {
+    // Positions are random, so the first motes are an even sample
+    for (dust, mut visibility) in particles.iter_mut() {
+        *visibility = if visible && dust.index < performance.max_particles {
             Visibility::Visible
         } else {
             Visibility::Hidden
@@ -218,6 +232,8 @@ impl Plugin for DustPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<DustConfig>()
             .add_systems(Startup, spawn_dust_particles)
-            .add_systems(Update, (animate_dust, update_dust_visibility));
+            .add_systems(FixedUpdate, animate_dust)
+            .add_systems(Update, update_dust_visibility)
+            .add_pipeline_warmup("dust", spawn_warmup_copy::<DustMarker, StandardMaterial>);
     }
 }
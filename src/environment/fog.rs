@@ -4,7 +4,7 @@ use bevy::pbr::FogSettings;
 use bevy::prelude::*;
 
 use crate::camera::ExperienceCamera;
-use crate::core::{ExperienceClock, Phase};
+use crate::core::{reset_resource, ExperienceClock, ExperienceReset, Phase};
 
 /// Phase-specific fog colors
 #[derive(Clone)]
@@ -61,6 +61,8 @@ pub struct FogState {
     pub target_color: Color,
     pub current_density: f32,
     pub target_density: f32,
+    /// Pulse overriding the phase target: color, density, seconds left
+    pub pulse: Option<(Color, f32, f32)>,
 }
 
 impl Default for FogState {
@@ -70,6 +72,7 @@ impl Default for FogState {
             target_color: Color::BLACK,
             current_density: 0.0,
             target_density: 0.0,
+            pulse: None,
         }
     }
 }
@@ -79,7 +82,6 @@ impl Default for FogState {
 pub struct FogPulseEvent {
     pub color: Color,
     pub density: f32,
-    #[allow(dead_code)]
     pub duration: f32,
 }
 
@@ -171,12 +173,23 @@ pub fn interpolate_fog(
 }
 
 /// Handle fog pulse events (e.g., on explosion)
-pub fn handle_fog_pulses(mut events: EventReader<FogPulseEvent>, mut state: ResMut<FogState>) {
+pub fn handle_fog_pulses(
+    time: Res<Time>,
+    mut events: EventReader<FogPulseEvent>,
+    mut state: ResMut<FogState>,
+) {
     for event in events.read() {
-        // Override target temporarily
-        state.target_color = event.color;
-        state.target_density = event.density;
+        state.pulse = Some((event.color, event.density, event.duration));
     }
+
+    // Override the phase target until the pulse runs out
+    let Some((color, density, remaining)) = state.pulse else {
+        return;
+    };
+    state.target_color = color;
+    state.target_density = density;
+    let remaining = remaining - time.delta_seconds();
+    state.pulse = (remaining > 0.0).then_some((color, density, remaining));
 }
 
 /// Fog plugin
@@ -188,13 +201,10 @@ impl Plugin for FogPlugin {
             .init_resource::<FogState>()
             .add_event::<FogPulseEvent>()
             .add_systems(Startup, setup_fog)
+            .add_systems(ExperienceReset, reset_resource::<FogState>)
             .add_systems(
-                Update,
-                (
-                    update_fog_for_phase,
-                    interpolate_fog.after(update_fog_for_phase),
-                    handle_fog_pulses,
-                ),
+                FixedUpdate,
+                (update_fog_for_phase, handle_fog_pulses, interpolate_fog).chain(),
             );
     }
 }
@@ -1,7 +1,12 @@
 //! Environment systems: Starfield, Nebula, Dust, Fog, Reflection
+//!
+//! `EnvironmentChange` moments name a layer and an action; the fog takes
+//! `pulse` (a brief warm swell) and `clear` (thins out for a few seconds).
 
 use bevy::prelude::*;
 
+use crate::core::{EnvironmentEvent, MomentAction, MomentActionAppExt};
+
 mod dust;
 mod fog;
 mod nebula;
@@ -23,6 +28,63 @@ impl Plugin for EnvironmentPlugin {
             .add_plugins(NebulaPlugin)
             .add_plugins(DustPlugin)
             .add_plugins(FogPlugin)
-            .add_plugins(ReflectionPlugin);
+            .add_plugins(ReflectionPlugin)
+            .add_moment_actions(&["EnvironmentChange"], handle_environment_moment)
+            .add_systems(Update, apply_environment_changes);
+    }
+}
+
+/// Moment actions owned by the environment (`EnvironmentChange`)
+pub fn handle_environment_moment(world: &mut World, action: &MomentAction, elapsed: f32) {
+    if let MomentAction::EnvironmentChange { layer, action } = action {
+        world.send_event(EnvironmentEvent {
+            layer: layer.clone(),
+            action: action.clone(),
+            elapsed,
+        });
+    }
+}
+
+/// Carry out environment changes on the layer they name
+pub fn apply_environment_changes(
+    mut events: EventReader<EnvironmentEvent>,
+    fog: Res<FogConfig>,
+    mut fog_pulses: EventWriter<FogPulseEvent>,
+) {
+    for event in events.read() {
+        match (event.layer.as_str(), event.action.as_str()) {
+            ("fog", "pulse") => {
+                fog_pulses.send(FogPulseEvent {
+                    color: fog.phase_colors.bang,
+                    density: fog.density * 3.0,
+                    duration: 1.5,
+                });
+            }
+            ("fog", "clear") => {
+                fog_pulses.send(FogPulseEvent {
+                    color: fog.base_color,
+                    density: 0.0,
+                    duration: 4.0,
+                });
+            }
+            (layer, action) => {
+                warn!(
+                    target: "lightwatch::environment",
+                    "Unknown environment change {} on {} at {:.2}s",
+                    action,
+                    layer,
+                    event.elapsed
+                );
+                continue;
+            }
+        }
+
+        info!(
+            target: "lightwatch::environment",
+            "Environment change at {:.2}s: {} {}",
+            event.elapsed,
+            event.layer,
+            event.action
+        );
     }
 }
@@ -5,7 +5,10 @@
 use bevy::prelude::*;
 
 use crate::camera::ExperienceCamera;
-use crate::core::{ExperienceClock, Phase};
+use crate::core::{
+    reset_resource, spawn_warmup_copy, ExperienceClock, ExperienceReset, Phase,
+    PipelineWarmupAppExt,
+};
 
 /// Reflection plane configuration
 #[derive(Resource)]
@@ -107,14 +110,9 @@ pub fn update_reflection_for_phase(
                 config.max_opacity
             };
         }
-        Phase::Acceptance => {
-            // Fade out during first 30% of Acceptance
-            if progress < 0.3 {
-                state.target_opacity = config.max_opacity * (1.0 - progress / 0.3);
-            } else {
-                state.target_opacity = 0.0;
-                state.active = false;
-            }
+        // Fade out during first 30% of Acceptance
+        Phase::Acceptance if progress < 0.3 => {
+            state.target_opacity = config.max_opacity * (1.0 - progress / 0.3);
         }
         _ => {
             state.target_opacity = 0.0;
@@ -187,8 +185,13 @@ impl Plugin for ReflectionPlugin {
         app.init_resource::<ReflectionConfig>()
             .init_resource::<ReflectionState>()
             .add_systems(Startup, spawn_reflection_plane)
+            .add_systems(ExperienceReset, reset_resource::<ReflectionState>)
+            .add_pipeline_warmup(
+                "reflection plane",
+                spawn_warmup_copy::<ReflectionPlane, StandardMaterial>,
+            )
             .add_systems(
-                Update,
+                FixedUpdate,
                 (
                     update_reflection_for_phase,
                     interpolate_reflection.after(update_reflection_for_phase),
@@ -5,11 +5,16 @@
 use bevy::prelude::*;
 use rand::Rng;
 
-use crate::core::{ExperienceClock, Phase};
+use crate::core::{
+    spawn_warmup_copy, ExperienceClock, ExperienceReset, ExperienceRng, PerformanceConfig, Phase,
+    PipelineWarmupAppExt,
+};
 
 /// Individual star
 #[derive(Component, Debug)]
 pub struct Star {
+    /// Spawn order (position on the Fibonacci sphere)
+    pub index: usize,
     /// Base brightness (0-1)
     pub brightness: f32,
     /// Twinkle frequency
@@ -31,6 +36,7 @@ pub struct StarMarker;
 /// Starfield configuration
 #[derive(Resource)]
 pub struct StarfieldConfig {
+    /// Stars shown; set from `PerformanceConfig::star_count`, at most the number spawned
     pub star_count: usize,
     pub min_distance: f32,
     pub max_distance: f32,
@@ -57,6 +63,15 @@ impl Default for StarfieldConfig {
 pub struct StarfieldAssets {
     pub mesh: Handle<Mesh>,
     pub material: Handle<StandardMaterial>,
+    /// Stars spawned at startup (the most that can be shown)
+    pub spawned: usize,
+}
+
+impl StarfieldAssets {
+    /// Whether the star at `index` is among `shown` stars spread evenly over the sphere
+    pub fn is_shown(&self, index: usize, shown: usize) -> bool {
+        shown >= self.spawned || (index + 1) * shown / self.spawned > index * shown / self.spawned
+    }
 }
 
 /// Spawn the starfield
@@ -65,8 +80,8 @@ pub fn spawn_starfield(
     config: Res<StarfieldConfig>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    mut rng: ResMut<ExperienceRng>,
 ) {
-    let mut rng = rand::thread_rng();
 
     // Star mesh (tiny sphere)
     let star_mesh = meshes.add(Sphere::new(0.1).mesh().ico(0).unwrap());
@@ -84,6 +99,7 @@ pub fn spawn_starfield(
     commands.insert_resource(StarfieldAssets {
         mesh: star_mesh.clone(),
         material: star_material.clone(),
+        spawned: config.star_count,
     });
 
     for i in 0..config.star_count {
@@ -116,6 +132,7 @@ pub fn spawn_starfield(
                 ..default()
             },
             Star {
+                index: i,
                 brightness,
                 twinkle_freq,
                 phase_offset,
@@ -134,6 +151,7 @@ pub fn spawn_starfield(
 pub fn reveal_stars(
     clock: Res<ExperienceClock>,
     config: Res<StarfieldConfig>,
+    assets: Option<Res<StarfieldAssets>>,
     mut stars: Query<(&mut Star, &mut Visibility), With<StarMarker>>,
 ) {
     let elapsed = clock.elapsed();
@@ -158,12 +176,54 @@ pub fn reveal_stars(
 
         if reveal_progress >= distance_factor {
             star.revealed = true;
-            *visibility = Visibility::Visible;
+            let shown = assets
+                .as_ref()
+                .is_none_or(|assets| assets.is_shown(star.index, config.star_count));
+            *visibility = if shown {
+                Visibility::Visible
+            } else {
+                Visibility::Hidden
+            };
             star.opacity = 0.0;
         }
     }
 }
 
+/// Show the quality tier's share of the stars
+pub fn apply_star_quality(
+    performance: Res<PerformanceConfig>,
+    assets: Option<Res<StarfieldAssets>>,
+    mut config: ResMut<StarfieldConfig>,
+    mut stars: Query<(&Star, &mut Visibility), With<StarMarker>>,
+) {
+    let Some(assets) = assets else {
+        return;
+    };
+    let count = performance.star_count.min(assets.spawned);
+    if count == config.star_count {
+        return;
+    }
+    config.star_count = count;
+
+    for (star, mut visibility) in stars.iter_mut() {
+        if star.revealed {
+            *visibility = if assets.is_shown(star.index, count) {
+                Visibility::Visible
+            } else {
+                Visibility::Hidden
+            };
+        }
+    }
+}
+
+/// Hide every star again (on `ExperienceReset`)
+pub fn hide_stars(mut stars: Query<&mut Star>) {
+    for mut star in stars.iter_mut() {
+        star.revealed = false;
+        star.opacity = 0.0;
+    }
+}
+
 /// Fade in revealed stars
 pub fn fade_in_stars(time: Res<Time>, mut stars: Query<&mut Star, With<StarMarker>>) {
     for mut star in stars.iter_mut() {
@@ -240,13 +300,10 @@ impl Plugin for StarfieldPlugin {
             Startup,
             spawn_starfield,
         ).add_systems(
-            Update,
-            (
-                reveal_stars,
-                fade_in_stars,
-                update_stars,
-                fade_stars_during_acceptance,
-            ),
-        );
+            FixedUpdate,
+            (reveal_stars, fade_in_stars, fade_stars_during_acceptance),
+        ).add_systems(Update, (apply_star_quality, update_stars))
+        .add_systems(ExperienceReset, hide_stars)
+        .add_pipeline_warmup("stars", spawn_warmup_copy::<StarMarker, StandardMaterial>);
     }
 }
@@ -1,5 +1,7 @@
 //! LIGHTWATCH library - Re-exports for clean imports
 
+use bevy::prelude::*;
+
 pub mod audio;
 pub mod bang;
 pub mod camera;
@@ -16,3 +18,32 @@ pub mod prelude {
     pub use crate::core::*;
     pub use bevy::prelude::*;
 }
+
+/// Main plugin that coordinates all LIGHTWATCH systems
+pub struct LightwatchPlugin;
+
+impl Plugin for LightwatchPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Shader loading (must be first for material registration)
+            .add_plugins(shaders::ShadersPlugin)
+            // Core systems (window, renderer, exposure, clock, state, events)
+            .add_plugins(core::CorePlugin)
+            // Camera systems
+            .add_plugins(camera::CameraPlugin)
+            // Traveler systems
+            .add_plugins(travelers::TravelersPlugin)
+            // Environment systems
+            .add_plugins(environment::EnvironmentPlugin)
+            // Bang sequence
+            .add_plugins(bang::BangPlugin)
+            // Audio systems
+            .add_plugins(audio::AudioPlugin)
+            // Narrative/text systems
+            .add_plugins(narrative::NarrativePlugin)
+            // Post-processing
+            .add_plugins(post::PostPlugin)
+            // Final integration (performance, polish, verification)
+            .add_plugins(core::IntegrationPlugin);
+    }
+}
@@ -5,89 +5,143 @@
 use bevy::prelude::*;
 use bevy::render::settings::{Backends, RenderCreation, WgpuSettings};
 use bevy::render::RenderPlugin;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
-
-mod audio;
-mod bang;
-mod camera;
-mod core;
-mod environment;
-mod narrative;
-mod post;
-mod shaders;
-mod text;
-mod travelers;
+use clap::Parser;
+use lightwatch::{audio, core, LightwatchPlugin};
+
+mod cli;
 
 fn main() {
-    // Initialize tracing with structured output
-    tracing_subscriber::registry()
-        .with(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-                if cfg!(debug_assertions) {
-                    "lightwatch=debug,lightwatch::events=info,wgpu=warn,bevy=info".into()
-                } else {
-                    "lightwatch=info,wgpu=error,bevy=warn".into()
+    let cli = cli::Cli::parse();
+
+    // Offline timeline check: validate the schedule and exit without opening a window
+    if let Some(path) = &cli.check_timeline {
+        let ok = core::run_timeline_check(path, core::DEFAULT_PHASE_TABLE_PATH);
+        std::process::exit(if ok { 0 } else { 1 });
+    }
+
+    if cli.list_audio_devices {
+        for name in audio::output_device_names() {
+            println!("{}", name);
+        }
+        std::process::exit(0);
+    }
+
+    // Replay: drive the run from a recorded --events log
+    let replay = cli.replay.as_ref().map(|path| match core::ReplayLog::load(path) {
+        Ok(log) => log,
+        Err(err) => {
+            eprintln!("Cannot read replay log {}: {}", path.display(), err);
+            std::process::exit(1);
+        }
+    });
+
+    // Offline audio render: simulate the run and write the mix without a device
+    if let Some(mut config) = cli.render_audio_config() {
+        config.headless.replay = replay;
+        cli.init_tracing("warn", true);
+
+        match audio::run_audio_render(&config) {
+            Ok(report) => {
+                for file in &report.files {
+                    println!("{}", file.display());
                 }
-            }),
-        )
-        .with(
-            tracing_subscriber::fmt::layer()
-                .with_target(true)
-                .with_thread_ids(false)
-                .with_file(false)
-                .with_line_number(false),
-        )
-        .init();
+                println!(
+                    "rendered {:.2}s at {} Hz, peak {:.1} dBFS",
+                    report.duration(),
+                    report.sample_rate,
+                    20.0 * report.peak.max(1e-6).log10()
+                );
+                let ended = report.headless.summary.state == core::ExperienceState::Ended;
+                std::process::exit(if ended { 0 } else { 1 });
+            }
+            Err(err) => {
+                eprintln!("Cannot render audio to {}: {}", config.path.display(), err);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // Headless simulation: print the wide-event log and final state, then exit
+    if cli.headless {
+        let config = core::HeadlessConfig {
+            replay,
+            ..cli.headless_config()
+        };
+
+        // Diagnostics go to stderr so stdout is only the event log
+        cli.init_tracing("warn", true);
+
+        let report = core::run_headless(&config);
+        for event in &report.events {
+            println!("{}", event.to_line());
+        }
+        println!("{}", report.summary);
+
+        let ended = report.summary.state == core::ExperienceState::Ended;
+
+        // Replays exit non-zero when the run drifted from the recording
+        let mut matches = true;
+        if let Some(log) = &config.replay {
+            let differences = core::diff_events(
+                &log.events,
+                &report.events,
+                config.timestep * core::REPLAY_TOLERANCE_STEPS,
+            );
+            println!("replay differences: {}", differences.len());
+            for difference in &differences {
+                println!("  {}", difference);
+            }
+            matches = differences.is_empty();
+        }
+
+        std::process::exit(if ended && matches { 0 } else { 1 });
+    }
+
+    cli.init_tracing(
+        if cfg!(debug_assertions) {
+            "lightwatch=debug,lightwatch::events=info,wgpu=warn,bevy=info"
+        } else {
+            "lightwatch=info,wgpu=error,bevy=warn"
+        },
+        false,
+    );
 
     core::BuildInfo::log_info();
 
-    App::new()
-        .add_plugins(
-            DefaultPlugins
-                .set(core::configure_window())
-                .set(RenderPlugin {
-                    render_creation: RenderCreation::Automatic(WgpuSettings {
-                        backends: Some(Backends::PRIMARY),
-                        power_preference: bevy::render::settings::PowerPreference::HighPerformance,
-                        ..default()
-                    }),
+    // Saved user settings; a missing file gives the defaults
+    let settings = match cli.settings_path() {
+        Some(path) => core::load_user_settings(&path).unwrap_or_else(|err| {
+            warn!(target: "lightwatch::settings", "Ignoring settings file: {}", err);
+            core::UserSettings::default()
+        }),
+        None => core::UserSettings::default(),
+    };
+    let launch = cli.launch_config(settings);
+
+    let mut app = App::new();
+    if let Some(log) = replay {
+        // Must exist before LightwatchPlugin builds the schedule from it
+        app.insert_resource(log);
+    }
+
+    app.add_plugins(
+        DefaultPlugins
+            .set(launch.window_plugin())
+            .set(RenderPlugin {
+                render_creation: RenderCreation::Automatic(WgpuSettings {
+                    backends: Some(Backends::PRIMARY),
+                    power_preference: bevy::render::settings::PowerPreference::HighPerformance,
                     ..default()
                 }),
-        )
-        // Set background to pure black
-        .insert_resource(ClearColor(Color::BLACK))
-        // 4x MSAA for anti-aliasing
-        .insert_resource(core::configure_msaa())
-        // Initialize our systems
-        .add_plugins(LightwatchPlugin)
-        .run();
-}
+                ..default()
+            }),
+    )
+    // Set background to pure black
+    .insert_resource(ClearColor(Color::BLACK));
 
-/// Main plugin that coordinates all LIGHTWATCH systems
-pub struct LightwatchPlugin;
-
-impl Plugin for LightwatchPlugin {
-    fn build(&self, app: &mut App) {
-        app
-            // Shader loading (must be first for material registration)
-            .add_plugins(shaders::ShadersPlugin)
-            // Core systems (window, renderer, exposure, clock, state, events)
-            .add_plugins(core::CorePlugin)
-            // Camera systems
-            .add_plugins(camera::CameraPlugin)
-            // Traveler systems
-            .add_plugins(travelers::TravelersPlugin)
-            // Environment systems
-            .add_plugins(environment::EnvironmentPlugin)
-            // Bang sequence
-            .add_plugins(bang::BangPlugin)
-            // Audio systems
-            .add_plugins(audio::AudioPlugin)
-            // Narrative/text systems
-            .add_plugins(narrative::NarrativePlugin)
-            // Post-processing
-            .add_plugins(post::PostPlugin)
-            // Final integration (performance, polish, verification)
-            .add_plugins(core::IntegrationPlugin);
-    }
+    // Window, timing, audio, quality and sink settings from the command line
+    launch.insert_resources(&mut app);
+
+    // Initialize our systems
+    app.add_plugins(LightwatchPlugin).run();
 }
@@ -3,6 +3,8 @@
 //! Implements a render graph node for chromatic aberration post-processing.
 //! This samples the rendered scene and applies RGB channel separation.
 
+#![allow(dead_code)]
+
 use bevy::{
     core_pipeline::{
         core_3d::graph::{Core3d, Node3d},
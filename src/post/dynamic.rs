@@ -34,7 +34,7 @@ pub fn update_chromatic_aberration(
     let max = config.chromatic_aberration.max_intensity;
 
     // Increase CA during bang (3-6 seconds) with smooth easing
-    let ca_intensity = if elapsed >= 3.0 && elapsed < 6.0 {
+    let ca_intensity = if (3.0..6.0).contains(&elapsed) {
         if elapsed < 4.0 {
             // Building to peak with smooth easing
             let t = ease_in_out_cubic((elapsed - 3.0) / 1.0);
@@ -44,11 +44,11 @@ pub fn update_chromatic_aberration(
             let t = ease_in_out_cubic((elapsed - 4.0) / 2.0);
             max - t * (max - base)
         }
-    } else if elapsed >= 2.5 && elapsed < 3.0 {
+    } else if (2.5..3.0).contains(&elapsed) {
         // Smooth ramp-in before bang
         let t = smooth_step((elapsed - 2.5) / 0.5);
         base * (1.0 + t * 0.5)
-    } else if elapsed >= 6.0 && elapsed < 6.5 {
+    } else if (6.0..6.5).contains(&elapsed) {
         // Smooth ramp-out after bang
         let t = smooth_step((elapsed - 6.0) / 0.5);
         base * (1.5 - t * 0.5)
@@ -78,9 +78,9 @@ pub fn update_film_grain(
         // Start - more grain (coming into focus), smooth fade out
         let t = smooth_step(elapsed / 2.0);
         base * (1.5 - t * 0.5)
-    } else if elapsed > 133.0 {
-        // End - more grain (fading out), smooth fade in
-        let t = smooth_step((elapsed - 133.0) / 10.0);
+    } else if elapsed > clock.duration() - 10.0 {
+        // End - more grain over the last 10 seconds, smooth fade in
+        let t = smooth_step((elapsed - (clock.duration() - 10.0)) / 10.0);
         base * (1.0 + t * 0.5)
     } else {
         base
@@ -3,6 +3,8 @@
 //! Implements a render graph node for film grain post-processing.
 //! Adds animated noise for cinematic texture and organic feel.
 
+#![allow(dead_code)]
+
 use bevy::{
     core_pipeline::{
         core_3d::graph::{Core3d, Node3d},
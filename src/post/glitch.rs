@@ -0,0 +1,72 @@
+//! Glitch bursts - `Glitch` moments flash the chromatic aberration
+
+use bevy::prelude::*;
+
+use crate::core::{ExperienceClock, GlitchEvent, MomentAction};
+
+use super::{DynamicPostProcess, PostProcessConfig};
+
+/// Flickers per second while a glitch lasts
+const GLITCH_FLICKER_RATE: f32 = 30.0;
+
+/// The glitch in progress, if any
+#[derive(Resource, Debug, Default)]
+pub struct GlitchState {
+    /// Strength as a fraction of the maximum aberration
+    pub intensity: f32,
+    /// Clock time the glitch started
+    pub started: f32,
+    pub duration: f32,
+}
+
+impl GlitchState {
+    /// Aberration added at clock time `elapsed`: flickering, fading over the duration
+    pub fn aberration(&self, elapsed: f32, max_intensity: f32) -> f32 {
+        let since = elapsed - self.started;
+        if self.intensity <= 0.0 || since < 0.0 || since >= self.duration {
+            return 0.0;
+        }
+
+        let fade = 1.0 - since / self.duration;
+        let on = ((since * GLITCH_FLICKER_RATE) as u32).is_multiple_of(2);
+        let flicker = if on { 1.0 } else { 0.3 };
+        self.intensity * max_intensity * fade * flicker
+    }
+}
+
+/// Moment actions owned by post-processing (`Glitch`)
+pub fn handle_glitch_moment(world: &mut World, action: &MomentAction, elapsed: f32) {
+    if let MomentAction::Glitch { intensity, duration } = action {
+        world.send_event(GlitchEvent {
+            intensity: *intensity,
+            duration: *duration,
+            elapsed,
+        });
+    }
+}
+
+/// Start a glitch for each `GlitchEvent` (a new one replaces the last)
+pub fn start_glitches(mut events: EventReader<GlitchEvent>, mut glitch: ResMut<GlitchState>) {
+    for event in events.read() {
+        *glitch = GlitchState {
+            intensity: event.intensity.clamp(0.0, 1.0),
+            started: event.elapsed,
+            duration: event.duration.max(0.0),
+        };
+    }
+}
+
+/// Add the glitch to the aberration the phase calls for
+pub fn apply_glitch(
+    clock: Res<ExperienceClock>,
+    config: Res<PostProcessConfig>,
+    glitch: Res<GlitchState>,
+    mut dynamic: ResMut<DynamicPostProcess>,
+) {
+    if !config.chromatic_aberration.enabled {
+        return;
+    }
+
+    dynamic.chromatic_intensity +=
+        glitch.aberration(clock.elapsed(), config.chromatic_aberration.max_intensity);
+}
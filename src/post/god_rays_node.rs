@@ -3,6 +3,8 @@
 //! Implements a render graph node for radial light scattering.
 //! Creates volumetric light beams emanating from bang center.
 
+#![allow(dead_code)]
+
 use bevy::{
     core_pipeline::{
         core_3d::graph::Core3d,
@@ -6,6 +6,7 @@
 //! - Chromatic aberration: Edge color fringing during intense moments (render graph)
 //! - Vignette: Corner darkening, pulses at phase transitions (render graph)
 //! - Film grain: Subtle texture, stronger at start/end
+//! - Glitch: Flickering aberration burst from `Glitch` moments
 //!
 //! Bloom and tonemapping use Bevy's built-in systems.
 //! Chromatic aberration and vignette use custom render graph nodes.
@@ -15,6 +16,7 @@ mod chromatic_node;
 mod config;
 mod dynamic;
 mod film_grain_node;
+mod glitch;
 mod god_rays_node;
 mod materials;
 mod vignette_node;
@@ -23,13 +25,29 @@ pub use chromatic_node::{ChromaticAberrationPlugin, ChromaticAberrationSettings}
 pub use config::*;
 pub use dynamic::DynamicPostProcess;
 pub use film_grain_node::{FilmGrainPlugin, FilmGrainSettings};
+pub use glitch::GlitchState;
 pub use god_rays_node::{GodRaysRenderPlugin, GodRaysSettings};
 pub use materials::*;
 pub use vignette_node::{VignettePlugin, VignetteSettings};
 
 use bevy::prelude::*;
+use bevy::render::render_resource::{CachedPipelineState, CachedRenderPipelineId, PipelineCache};
+use bevy::render::{Render, RenderApp, RenderSet};
 use bevy::sprite::Material2dPlugin;
 
+use crate::core::{
+    reset_resource, ExperienceReset, LoadStatus, LoadingReports, LoadingTracker,
+    MomentActionAppExt,
+};
+
+/// Loading task names for the post-processing pipelines
+const POST_PIPELINES: [&str; 4] = [
+    "chromatic aberration pipeline",
+    "god rays pipeline",
+    "vignette pipeline",
+    "film grain pipeline",
+];
+
 /// Post-processing plugin for final visual polish
 pub struct PostPlugin;
 
@@ -37,6 +55,12 @@ impl Plugin for PostPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<PostProcessConfig>()
             .init_resource::<DynamicPostProcess>()
+            .init_resource::<GlitchState>()
+            .add_moment_actions(&["Glitch"], glitch::handle_glitch_moment)
+            .add_systems(
+                ExperienceReset,
+                (reset_resource::<DynamicPostProcess>, reset_resource::<GlitchState>),
+            )
             // Render graph post-processing nodes
             // Order: Tonemapping → CA → GodRays → Vignette → FilmGrain → End
             .add_plugins((
@@ -55,7 +79,12 @@ impl Plugin for PostPlugin {
                 Update,
                 (
                     bloom::update_bloom_for_bang,
-                    dynamic::update_chromatic_aberration,
+                    (
+                        glitch::start_glitches,
+                        dynamic::update_chromatic_aberration,
+                        glitch::apply_glitch,
+                    )
+                        .chain(),
                     dynamic::update_film_grain,
                     dynamic::update_vignette,
                     sync_chromatic_settings,
@@ -67,6 +96,65 @@ impl Plugin for PostPlugin {
 
         info!(target: "lightwatch::post", "Post-processing plugin initialized");
     }
+
+    fn finish(&self, app: &mut App) {
+        // No render world when headless: nothing to compile
+        if app.get_sub_app(RenderApp).is_none() {
+            return;
+        }
+        let Some(reports) = app.world().get_resource::<LoadingReports>().cloned() else {
+            return;
+        };
+        if let Some(mut tracker) = app.world_mut().get_resource_mut::<LoadingTracker>() {
+            for name in POST_PIPELINES {
+                tracker.add_task(name);
+            }
+        }
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .insert_resource(reports)
+                .add_systems(Render, report_post_pipelines.in_set(RenderSet::Cleanup));
+        }
+    }
+}
+
+/// Report when the post-processing pipelines have compiled (render world)
+fn report_post_pipelines(
+    pipeline_cache: Res<PipelineCache>,
+    reports: Res<LoadingReports>,
+    chromatic: Option<Res<chromatic_node::ChromaticAberrationPipeline>>,
+    god_rays: Option<Res<god_rays_node::GodRaysPipeline>>,
+    vignette: Option<Res<vignette_node::VignettePipeline>>,
+    film_grain: Option<Res<film_grain_node::FilmGrainPipeline>>,
+    mut reported: Local<bool>,
+) {
+    if *reported {
+        return;
+    }
+
+    let ids: [Option<CachedRenderPipelineId>; 4] = [
+        chromatic.map(|pipeline| pipeline.pipeline_id),
+        god_rays.map(|pipeline| pipeline.pipeline_id),
+        vignette.map(|pipeline| pipeline.pipeline_id),
+        film_grain.map(|pipeline| pipeline.pipeline_id),
+    ];
+
+    let mut finished = 0;
+    for (name, id) in POST_PIPELINES.into_iter().zip(ids) {
+        let Some(id) = id else {
+            continue;
+        };
+        let status = match pipeline_cache.get_render_pipeline_state(id) {
+            CachedPipelineState::Ok(_) => LoadStatus::Done,
+            CachedPipelineState::Err(err) => LoadStatus::Failed(err.to_string()),
+            _ => continue,
+        };
+        reports.report(name, status);
+        finished += 1;
+    }
+
+    *reported = finished == POST_PIPELINES.len();
 }
 
 /// Sync ChromaticAberrationSettings component with DynamicPostProcess state
@@ -102,10 +190,11 @@ fn sync_film_grain_settings(
     }
 }
 
-/// Sync GodRaysSettings component with bang GodRayState
+/// Sync GodRaysSettings component with bang GodRayState (samples capped by the quality tier)
 fn sync_god_rays_settings(
     god_ray_state: Res<crate::bang::GodRayState>,
     god_ray_config: Res<crate::bang::GodRayConfig>,
+    performance: Res<crate::core::PerformanceConfig>,
     mut cameras: Query<&mut GodRaysSettings>,
 ) {
     for mut settings in cameras.iter_mut() {
@@ -113,7 +202,7 @@ fn sync_god_rays_settings(
         settings.intensity = god_ray_state.current_intensity;
         settings.decay = god_ray_config.decay;
         settings.density = god_ray_config.density;
-        settings.samples = god_ray_config.samples;
+        settings.samples = god_ray_config.samples.min(performance.god_ray_samples);
         settings.exposure = god_ray_config.exposure;
     }
 }
@@ -3,6 +3,8 @@
 //! Implements a render graph node for vignette post-processing.
 //! Darkens screen edges for cinematic focus.
 
+#![allow(dead_code)]
+
 use bevy::{
     core_pipeline::{
         core_3d::graph::Core3d,
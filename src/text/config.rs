@@ -40,7 +40,7 @@ impl TextConfig {
 }
 
 /// Screen position for text
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum TextPosition {
     TopLeft,
     TopCenter,
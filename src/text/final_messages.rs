@@ -5,6 +5,9 @@ use bevy::prelude::*;
 use super::{TextPosition, Transmission, TransmissionCommands, TransmissionQueue};
 use crate::core::ExperienceClock;
 
+/// Seconds before the end of the cut at which the final messages start
+pub const FINAL_MESSAGES_LEAD: f32 = 13.0;
+
 /// State for final messages
 #[derive(Resource, Default)]
 pub struct FinalMessageState {
@@ -21,8 +24,8 @@ pub fn show_final_messages(
 ) {
     let elapsed = clock.elapsed();
 
-    // Start final messages at 130 seconds
-    if elapsed >= 130.0 && !state.messages_started {
+    // Start final messages 13 seconds before the end (130s in the standard cut)
+    if elapsed >= clock.duration() - FINAL_MESSAGES_LEAD && !state.messages_started {
         state.messages_started = true;
 
         queue.transmit_full(
@@ -6,7 +6,7 @@ use rand_chacha::ChaCha8Rng;
 
 use super::fragments::{get_traveler_fragments, traveler_display_name, TravelerFragment};
 use super::{TextPosition, Transmission, TransmissionCommands, TransmissionQueue};
-use crate::core::ExperienceClock;
+use crate::core::{ExperienceClock, Phase, PhaseTable};
 
 /// Fragment display state
 #[derive(Resource)]
@@ -46,6 +46,7 @@ impl Default for FragmentState {
 pub fn display_fragments(
     time: Res<Time>,
     clock: Res<ExperienceClock>,
+    table: Res<PhaseTable>,
     mut state: ResMut<FragmentState>,
     mut queue: ResMut<TransmissionQueue>,
 ) {
@@ -53,7 +54,7 @@ pub fn display_fragments(
     state.time_since_last += time.delta_seconds();
 
     // Only during active phases (after bang, before end)
-    if elapsed < 12.0 || elapsed > 135.0 {
+    if !(table.start(Phase::Awakening)..=table.rescale(135.0)).contains(&elapsed) {
         return;
     }
 
@@ -63,7 +64,7 @@ pub fn display_fragments(
     }
 
     // Get available fragments
-    let all_fragments = get_traveler_fragments();
+    let all_fragments = get_traveler_fragments(&table);
     let available: Vec<(usize, &TravelerFragment)> = all_fragments
         .iter()
         .enumerate()
@@ -1,6 +1,6 @@
 //! Traveler text fragments and display names
 
-use crate::core::TravelerId;
+use crate::core::{Phase, PhaseTable, TravelerId};
 
 /// Fragment attributed to a traveler
 pub struct TravelerFragment {
@@ -10,103 +10,108 @@ pub struct TravelerFragment {
     pub phase_end: f32,   // Cannot appear after this time
 }
 
-/// All traveler fragments
-pub fn get_traveler_fragments() -> Vec<TravelerFragment> {
+/// All traveler fragments, with windows placed on `table`'s phases
+pub fn get_traveler_fragments(table: &PhaseTable) -> Vec<TravelerFragment> {
+    let discovery = table.start(Phase::Discovery);
+    let connection = table.start(Phase::Connection);
+    let acceptance = table.start(Phase::Acceptance);
+    let end = table.duration();
+
     vec![
         // Archivist - memory keeper, deliberate
         TravelerFragment {
             traveler: TravelerId::Archivist,
             text: "We were here before the counting began",
-            phase_start: 27.0,
-            phase_end: 57.0,
+            phase_start: discovery,
+            phase_end: connection,
         },
         TravelerFragment {
             traveler: TravelerId::Archivist,
             text: "Memory persists where light cannot",
-            phase_start: 57.0,
-            phase_end: 87.0,
+            phase_start: connection,
+            phase_end: acceptance,
         },
         TravelerFragment {
             traveler: TravelerId::Archivist,
             text: "Each ending contains its beginning",
-            phase_start: 87.0,
-            phase_end: 143.0,
+            phase_start: acceptance,
+            phase_end: end,
         },
         // Wanderer - explorer, restless
         TravelerFragment {
             traveler: TravelerId::Wanderer,
             text: "Always further",
-            phase_start: 27.0,
-            phase_end: 57.0,
+            phase_start: discovery,
+            phase_end: connection,
         },
         TravelerFragment {
             traveler: TravelerId::Wanderer,
             text: "The edge calls louder than the center",
-            phase_start: 57.0,
-            phase_end: 87.0,
+            phase_start: connection,
+            phase_end: acceptance,
         },
         TravelerFragment {
             traveler: TravelerId::Wanderer,
             text: "There is no arrival, only approach",
-            phase_start: 87.0,
-            phase_end: 143.0,
+            phase_start: acceptance,
+            phase_end: end,
         },
         // Keeper - guardian, steady
         TravelerFragment {
             traveler: TravelerId::Keeper,
             text: "Hold what cannot be held",
-            phase_start: 27.0,
-            phase_end: 57.0,
+            phase_start: discovery,
+            phase_end: connection,
         },
         TravelerFragment {
             traveler: TravelerId::Keeper,
             text: "Stillness is not absence",
-            phase_start: 57.0,
-            phase_end: 87.0,
+            phase_start: connection,
+            phase_end: acceptance,
         },
         TravelerFragment {
             traveler: TravelerId::Keeper,
             text: "We remain because we must",
-            phase_start: 87.0,
-            phase_end: 143.0,
+            phase_start: acceptance,
+            phase_end: end,
         },
         // Child - first to fade, innocent
         TravelerFragment {
             traveler: TravelerId::Child,
             text: "Is this the first time or the last",
-            phase_start: 27.0,
-            phase_end: 57.0,
+            phase_start: discovery,
+            phase_end: connection,
         },
         TravelerFragment {
             traveler: TravelerId::Child,
             text: "The dark is warm here",
-            phase_start: 57.0,
-            phase_end: 87.0,
+            phase_start: connection,
+            phase_end: acceptance,
         },
         TravelerFragment {
             traveler: TravelerId::Child,
             text: "I forget which way we came",
-            phase_start: 87.0,
-            phase_end: 110.0, // Before fading
+            phase_start: acceptance,
+            phase_end: table.rescale(110.0), // Before fading
         },
         // Other - distant, unknowable
         TravelerFragment {
             traveler: TravelerId::Other,
             text: "...",
-            phase_start: 27.0,
-            phase_end: 57.0,
+            phase_start: discovery,
+            phase_end: connection,
         },
         TravelerFragment {
             traveler: TravelerId::Other,
             text: "The pattern recognizes itself",
-            phase_start: 57.0,
-            phase_end: 87.0,
+            phase_start: connection,
+            phase_end: acceptance,
         },
         TravelerFragment {
             traveler: TravelerId::Other,
             text: "We were never what you imagined",
-            phase_start: 120.0,
-            phase_end: 143.0,
+            phase_start: table.rescale(120.0),
+            phase_end: end,
         },
     ]
 }
@@ -6,6 +6,7 @@ mod final_messages;
 mod fragment_display;
 mod fragments;
 mod grief;
+mod narrative;
 mod queue;
 mod signal;
 mod spawn;
@@ -14,7 +15,7 @@ mod typewriter;
 
 pub use api::TransmissionCommands;
 pub use config::{TextConfig, TextPosition};
-pub use final_messages::FinalMessageState;
+pub use final_messages::{FinalMessageState, FINAL_MESSAGES_LEAD};
 pub use fragment_display::FragmentState;
 pub use grief::GriefTextState;
 pub use queue::TransmissionQueue;
@@ -23,9 +24,16 @@ pub use transmission::{Transmission, TransmissionState};
 
 use bevy::prelude::*;
 
+use crate::core::{despawn_all, reset_resource, ExperienceReset, LoadingTracker, MomentActionAppExt};
+
 /// Load font and create text config
-fn setup_text_config(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn setup_text_config(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut loading: ResMut<LoadingTracker>,
+) {
     let font = asset_server.load("fonts/JetBrainsMono-Regular.ttf");
+    loading.track_asset("fonts/JetBrainsMono-Regular.ttf", font.clone());
     commands.insert_resource(TextConfig::new(font));
     info!(target: "lightwatch::text", "Text font loaded");
 }
@@ -36,10 +44,20 @@ pub struct TransmissionPlugin;
 impl Plugin for TransmissionPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<TransmissionQueue>()
+            .add_moment_actions(&["ShowText", "HideText"], narrative::handle_text_moment)
             .add_systems(Startup, setup_text_config)
+            .add_systems(
+                ExperienceReset,
+                (
+                    reset_resource::<TransmissionQueue>,
+                    despawn_all::<Transmission>,
+                ),
+            )
             .add_systems(
                 Update,
                 (
+                    narrative::display_narrative_text,
+                    narrative::hide_transmissions,
                     spawn::update_transmission_queue,
                     spawn::spawn_queued_transmissions,
                     typewriter::update_typewriter,
@@ -60,11 +78,22 @@ impl Plugin for FragmentsPlugin {
             .init_resource::<FragmentState>()
             .init_resource::<GriefTextState>()
             .init_resource::<FinalMessageState>()
+            .add_moment_actions(&["SignalTypewriter"], signal::handle_signal_moment)
+            .add_systems(
+                ExperienceReset,
+                (
+                    reset_resource::<SignalState>,
+                    reset_resource::<FragmentState>,
+                    reset_resource::<GriefTextState>,
+                    reset_resource::<FinalMessageState>,
+                ),
+            )
             .add_systems(
                 Update,
                 (
                     signal::start_signal_detection,
                     signal::reveal_travelers,
+                    signal::show_signal_readout,
                     fragment_display::display_fragments,
                     grief::show_grief_text,
                     final_messages::show_final_messages,
@@ -0,0 +1,66 @@
+//! Scripted timeline text - `ShowText` and `HideText` moments
+
+use bevy::prelude::*;
+
+use super::fragments::traveler_display_name;
+use super::{TextPosition, Transmission, TransmissionCommands, TransmissionQueue, TransmissionState};
+use crate::core::{DisplayTextEvent, HideTextEvent, MomentAction, NarrativeTextStyle};
+
+/// Moment actions owned by the text (`ShowText`, `HideText`)
+pub fn handle_text_moment(world: &mut World, action: &MomentAction, elapsed: f32) {
+    match action {
+        MomentAction::ShowText { text, traveler } => {
+            world.send_event(DisplayTextEvent {
+                text: text.clone(),
+                traveler: *traveler,
+                style: NarrativeTextStyle::Normal,
+                elapsed,
+            });
+        }
+        MomentAction::HideText => {
+            world.send_event(HideTextEvent);
+        }
+        _ => {}
+    }
+}
+
+/// Queue scripted lines: a traveler's words with attribution, collective lines bare
+pub fn display_narrative_text(
+    mut events: EventReader<DisplayTextEvent>,
+    mut queue: ResMut<TransmissionQueue>,
+) {
+    for event in events.read() {
+        let text = match event.traveler {
+            Some(traveler) => format!("\"{}\" — {}", event.text, traveler_display_name(traveler)),
+            None => event.text.clone(),
+        };
+
+        queue.transmit_full(
+            Transmission::new(text)
+                .with_position(TextPosition::BottomCenter)
+                .with_speed(12.0)
+                .with_hold(3.0)
+                .with_priority(1), // Scripted lines go ahead of queued fragments
+            0.0,
+        );
+
+        info!(target: "lightwatch::text", "Narrative text at {:.2}s: {}", event.elapsed, event.text);
+    }
+}
+
+/// Fade out everything on screen
+pub fn hide_transmissions(
+    mut events: EventReader<HideTextEvent>,
+    mut transmissions: Query<&mut Transmission>,
+) {
+    if events.read().count() == 0 {
+        return;
+    }
+
+    for mut transmission in transmissions.iter_mut() {
+        if matches!(transmission.state, TransmissionState::Typing | TransmissionState::Holding) {
+            transmission.state = TransmissionState::Fading;
+            transmission.state_time = 0.0;
+        }
+    }
+}
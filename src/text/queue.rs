@@ -42,10 +42,11 @@ impl TransmissionQueue {
 
         // Sort by priority (higher first), then by order added
         self.queue
-            .sort_by(|a, b| b.transmission.priority.cmp(&a.transmission.priority));
+            .sort_by_key(|queued| std::cmp::Reverse(queued.transmission.priority));
     }
 
     /// Get next transmission if ready
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Option<Transmission> {
         if self.time_until_next > 0.0 {
             return None;
@@ -1,10 +1,13 @@
-//! Signal detection overlay
+//! Signal detection overlay, and the signal readout from `SignalTypewriter` moments
 
 use bevy::prelude::*;
 
 use super::fragments::traveler_display_name;
-use super::{TextPosition, Transmission, TransmissionCommands, TransmissionQueue};
-use crate::core::{ExperienceClock, TravelerId};
+use super::{TextPosition, Transmission, TransmissionCommands, TransmissionQueue, TransmissionState};
+use crate::core::{ExperienceClock, MomentAction, SignalAction, SignalOverlayEvent, TravelerId};
+
+/// Where the signal readout types its lines (the detection list is top left)
+const READOUT_POSITION: TextPosition = TextPosition::TopRight;
 
 /// Signal detection configuration
 #[derive(Resource)]
@@ -107,3 +110,55 @@ pub fn reveal_travelers(
         info!(target: "lightwatch::text", "Signal detection complete at {:.2}s", elapsed);
     }
 }
+
+/// Moment actions owned by the signal overlay (`SignalTypewriter`)
+pub fn handle_signal_moment(world: &mut World, action: &MomentAction, elapsed: f32) {
+    if let MomentAction::SignalTypewriter(text) = action {
+        world.send_event(SignalOverlayEvent {
+            action: SignalAction::Typewriter(text.clone()),
+            elapsed,
+        });
+    }
+}
+
+/// Type signal readout lines, each one replacing the last
+pub fn show_signal_readout(
+    mut events: EventReader<SignalOverlayEvent>,
+    mut queue: ResMut<TransmissionQueue>,
+    mut transmissions: Query<&mut Transmission>,
+) {
+    for event in events.read() {
+        // The readout has nothing to show until its first line arrives
+        if matches!(event.action, SignalAction::Show) {
+            continue;
+        }
+
+        for mut transmission in transmissions.iter_mut() {
+            let on_screen = matches!(
+                transmission.state,
+                TransmissionState::Typing | TransmissionState::Holding
+            );
+            if transmission.position == READOUT_POSITION && on_screen {
+                transmission.state = TransmissionState::Fading;
+                transmission.state_time = 0.0;
+            }
+        }
+
+        if let SignalAction::Typewriter(text) = &event.action {
+            queue.transmit_full(
+                Transmission::new(text.clone())
+                    .with_position(READOUT_POSITION)
+                    .with_speed(30.0)
+                    .with_hold(2.0),
+                0.0,
+            );
+
+            info!(
+                target: "lightwatch::text",
+                "Signal readout at {:.2}s: {}",
+                event.elapsed,
+                text
+            );
+        }
+    }
+}
@@ -20,8 +20,7 @@ pub struct TravelerDrift {
 }
 
 impl TravelerDrift {
-    pub fn for_traveler(id: TravelerId) -> Self {
-        let mut rng = rand::thread_rng();
+    pub fn for_traveler(id: TravelerId, rng: &mut impl Rng) -> Self {
         Self {
             velocity: Vec3::ZERO,
             max_speed: match id {
@@ -43,7 +42,8 @@ pub fn apply_traveler_drift(
     clock: Res<ExperienceClock>,
     mut travelers: Query<(&Traveler, &mut Transform, &mut TravelerDrift)>,
 ) {
-    let t = time.elapsed_seconds();
+    // Experience time, so a seek lands on the same noise as an uninterrupted run
+    let t = clock.elapsed();
     let phase = clock.phase();
 
     for (_traveler, mut transform, mut drift) in travelers.iter_mut() {
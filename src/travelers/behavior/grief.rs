@@ -2,34 +2,36 @@
 
 use bevy::prelude::*;
 
-use crate::core::{TravelerGriefEvent, TravelerId};
+use crate::core::{ExperienceClock, TravelerGriefEvent, TravelerId};
 use crate::travelers::{Traveler, TravelerGrief, TravelerPulse};
 
 use super::TravelerDrift;
 
 /// Grief-induced behavior changes
 pub fn apply_grief_behavior(
-    time: Res<Time>,
+    clock: Res<ExperienceClock>,
     mut travelers: Query<(&Traveler, &TravelerGrief, &mut TravelerPulse, &mut Transform)>,
 ) {
+    let t = clock.elapsed();
+
     for (_traveler, grief, mut pulse, mut transform) in travelers.iter_mut() {
         if !grief.active {
             continue;
         }
 
-        // Grief disrupts pulse
-        pulse.variance *= 1.0 + grief.intensity * 0.5;
+        // Grief disrupts pulse (scaled from the base so it does not compound per step)
+        pulse.variance = pulse.base_variance * (1.0 + grief.intensity * 0.5);
 
         // Grief causes slight tremor
         let tremor = Vec3::new(
-            (time.elapsed_seconds() * 15.0).sin() * 0.01 * grief.intensity,
-            (time.elapsed_seconds() * 17.0).cos() * 0.01 * grief.intensity,
+            (t * 15.0).sin() * 0.01 * grief.intensity,
+            (t * 17.0).cos() * 0.01 * grief.intensity,
             0.0,
         );
         transform.translation += tremor;
 
         // Grief slows pulse
-        pulse.frequency *= 1.0 - grief.intensity * 0.3;
+        pulse.frequency = pulse.base_frequency * (1.0 - grief.intensity * 0.3);
     }
 }
 
@@ -16,18 +16,20 @@ pub use orbit::*;
 pub use pulse::*;
 pub use sync::*;
 
+use crate::core::ExperienceRng;
 use crate::travelers::Traveler;
 
 /// Add behavior components when traveler spawns
 pub fn setup_traveler_behaviors(
     mut commands: Commands,
     travelers: Query<(Entity, &Traveler), Added<Traveler>>,
+    mut rng: ResMut<ExperienceRng>,
 ) {
     for (entity, traveler) in travelers.iter() {
         commands.entity(entity).insert((
-            TravelerDrift::for_traveler(traveler.id),
+            TravelerDrift::for_traveler(traveler.id, &mut *rng),
             TravelerAnchor::for_traveler(traveler.id),
-            TravelerOrbit::for_traveler(traveler.id),
+            TravelerOrbit::for_traveler(traveler.id, &mut *rng),
         ));
     }
 }
@@ -38,7 +40,7 @@ pub struct TravelerBehaviorPlugin;
 impl Plugin for TravelerBehaviorPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
-            Update,
+            FixedUpdate,
             (
                 setup_traveler_behaviors,
                 update_traveler_pulse,
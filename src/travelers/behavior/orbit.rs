@@ -23,8 +23,7 @@ pub struct TravelerOrbit {
 }
 
 impl TravelerOrbit {
-    pub fn for_traveler(id: TravelerId) -> Self {
-        let mut rng = rand::thread_rng();
+    pub fn for_traveler(id: TravelerId, rng: &mut impl Rng) -> Self {
         Self {
             center: Vec3::ZERO,
             angle: rng.gen::<f32>() * std::f32::consts::TAU,
@@ -2,12 +2,15 @@
 
 use bevy::prelude::*;
 
-use crate::core::TravelerId;
+use crate::core::{ExperienceClock, TravelerId};
 use crate::travelers::{Traveler, TravelerPulse};
 
 /// Update traveler pulse state
-pub fn update_traveler_pulse(time: Res<Time>, mut travelers: Query<(&Traveler, &mut TravelerPulse)>) {
-    let t = time.elapsed_seconds();
+pub fn update_traveler_pulse(
+    clock: Res<ExperienceClock>,
+    mut travelers: Query<(&Traveler, &mut TravelerPulse)>,
+) {
+    let t = clock.elapsed();
 
     for (traveler, mut pulse) in travelers.iter_mut() {
         // Natural frequency with variance
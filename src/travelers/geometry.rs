@@ -210,7 +210,7 @@ fn apply_noise(v: &mut Vec3, amount: f32) {
 }
 
 fn hash_f32(x: f32) -> f32 {
-    let x = (x * 12.9898).sin() * 43758.5453;
+    let x = (x * 12.9898).sin() * 43758.547;
     x.fract()
 }
 
@@ -294,13 +294,13 @@ fn generate_shell_mesh(geometry: TravelerGeometry, noise: f32) -> Mesh {
     let mut mesh = generate_mesh(geometry, noise);
 
     // Scale up positions slightly for shell effect
-    if let Some(positions) = mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION) {
-        if let bevy::render::mesh::VertexAttributeValues::Float32x3(ref mut pos) = positions {
-            for p in pos.iter_mut() {
-                p[0] *= 1.1;
-                p[1] *= 1.1;
-                p[2] *= 1.1;
-            }
+    if let Some(bevy::render::mesh::VertexAttributeValues::Float32x3(pos)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+    {
+        for p in pos.iter_mut() {
+            p[0] *= 1.1;
+            p[1] *= 1.1;
+            p[2] *= 1.1;
         }
     }
 
@@ -312,12 +312,11 @@ fn generate_edges_mesh(geometry: TravelerGeometry, noise: f32) -> Mesh {
     let base = generate_mesh(geometry, noise);
 
     // Extract positions
-    let positions: Vec<[f32; 3]> = if let Some(attr) = base.attribute(Mesh::ATTRIBUTE_POSITION) {
-        if let bevy::render::mesh::VertexAttributeValues::Float32x3(pos) = attr {
-            pos.clone()
-        } else {
-            vec![]
-        }
+    let positions: Vec<[f32; 3]> = if let Some(
+        bevy::render::mesh::VertexAttributeValues::Float32x3(pos),
+    ) = base.attribute(Mesh::ATTRIBUTE_POSITION)
+    {
+        pos.clone()
     } else {
         vec![]
     };
@@ -3,7 +3,8 @@
 use bevy::prelude::*;
 
 use super::{Traveler, TravelerGrief, TravelerState, TravelerVisibility};
-use crate::core::{ExperienceClock, TravelerFadedEvent, TravelerFadingEvent, TravelerGriefEvent};
+use crate::core::{ExperienceClock, MomentAction, TravelerFadedEvent, TravelerFadingEvent, TravelerGriefEvent};
+use crate::core::{TravelerSpawnedEvent, TravelersSyncedEvent, WideEventLog};
 use crate::wide_event;
 
 /// Update visibility based on state
@@ -43,6 +44,7 @@ pub fn handle_traveler_fading(
     mut events: EventReader<TravelerFadingEvent>,
     mut travelers: Query<(&Traveler, &mut TravelerState, &mut TravelerVisibility)>,
     clock: Res<ExperienceClock>,
+    log: Res<WideEventLog>,
 ) {
     for event in events.read() {
         for (traveler, mut state, mut vis) in travelers.iter_mut() {
@@ -52,7 +54,7 @@ pub fn handle_traveler_fading(
 
                 wide_event!("traveler_fading")
                     .with_str("id", event.id.name())
-                    .emit(clock.elapsed());
+                    .emit(&log, clock.elapsed());
             }
         }
     }
@@ -63,6 +65,7 @@ pub fn check_faded_travelers(
     mut travelers: Query<(&Traveler, &mut TravelerState, &TravelerVisibility)>,
     mut events: EventWriter<TravelerFadedEvent>,
     clock: Res<ExperienceClock>,
+    log: Res<WideEventLog>,
 ) {
     for (traveler, mut state, vis) in travelers.iter_mut() {
         if *state == TravelerState::Fading && vis.opacity < 0.01 {
@@ -74,7 +77,7 @@ pub fn check_faded_travelers(
 
             wide_event!("traveler_faded")
                 .with_str("id", traveler.id.name())
-                .emit(clock.elapsed());
+                .emit(&log, clock.elapsed());
         }
     }
 }
@@ -84,6 +87,7 @@ pub fn handle_grief_events(
     mut events: EventReader<TravelerGriefEvent>,
     mut travelers: Query<(&Traveler, &mut TravelerState, &mut TravelerGrief)>,
     clock: Res<ExperienceClock>,
+    log: Res<WideEventLog>,
 ) {
     for event in events.read() {
         for (traveler, mut state, mut grief) in travelers.iter_mut() {
@@ -96,7 +100,7 @@ pub fn handle_grief_events(
                 wide_event!("traveler_grieving")
                     .with_str("mourner", event.mourner.name())
                     .with_str("deceased", event.deceased.name())
-                    .emit(clock.elapsed());
+                    .emit(&log, clock.elapsed());
             }
         }
     }
@@ -117,3 +121,33 @@ pub fn decay_grief(time: Res<Time>, mut travelers: Query<(&mut TravelerState, &m
         }
     }
 }
+
+/// Moment actions owned by the travelers (spawn, fade, grief, sync)
+pub fn handle_traveler_moment(world: &mut World, action: &MomentAction, elapsed: f32) {
+    match action {
+        MomentAction::SpawnTraveler(id) => {
+            world.send_event(TravelerSpawnedEvent {
+                id: *id,
+                position: Vec3::ZERO, // Position determined by spawn system
+                elapsed,
+            });
+        }
+        MomentAction::FadeTraveler(id) => {
+            world.send_event(TravelerFadingEvent { id: *id, elapsed });
+        }
+        MomentAction::TriggerGrief { mourner, deceased } => {
+            world.send_event(TravelerGriefEvent {
+                mourner: *mourner,
+                deceased: *deceased,
+                elapsed,
+            });
+        }
+        MomentAction::SyncTravelers(travelers) => {
+            world.send_event(TravelersSyncedEvent {
+                participants: travelers.clone(),
+                elapsed,
+            });
+        }
+        _ => {}
+    }
+}
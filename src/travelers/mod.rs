@@ -2,6 +2,11 @@
 
 use bevy::prelude::*;
 
+use crate::core::{
+    despawn_all, reset_resource, ExperienceReset, MomentActionAppExt, PipelineWarmupAppExt,
+    TravelerId,
+};
+
 mod behavior;
 mod geometry;
 mod identity;
@@ -33,17 +38,34 @@ impl Plugin for TravelersPlugin {
             .init_resource::<TravelerRegistry>()
             .init_resource::<TravelerMeshCache>()
             .init_resource::<TravelerMaterialCache>()
+            .add_moment_actions(
+                &["SpawnTraveler", "FadeTraveler", "TriggerGrief", "SyncTravelers"],
+                handle_traveler_moment,
+            )
             .add_systems(
-                Update,
+                ExperienceReset,
+                (
+                    reset_resource::<TravelerRegistry>,
+                    despawn_all::<Traveler>,
+                    despawn_all::<TrailParticle>,
+                ),
+            )
+            .add_systems(
+                FixedUpdate,
                 (
                     handle_traveler_spawns,
-                    spawn_traveler_visuals.after(handle_traveler_spawns),
                     update_traveler_visibility,
                     finalize_spawn,
                     handle_traveler_fading,
                     check_faded_travelers,
                     handle_grief_events,
                     decay_grief,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    spawn_traveler_visuals,
                     update_traveler_registry,
                     // Material systems
                     update_pulsing_materials,
@@ -51,5 +73,12 @@ impl Plugin for TravelersPlugin {
                     apply_grief_to_materials,
                 ),
             );
+
+        // Each traveler has its own geometry
+        for &id in TravelerId::all() {
+            app.add_pipeline_warmup(&format!("traveler {:?}", id), move |world, transform| {
+                Some(spawn_traveler_warmup(world, transform, id))
+            });
+        }
     }
 }
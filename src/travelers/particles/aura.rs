@@ -5,7 +5,8 @@
 use bevy::prelude::*;
 use rand::Rng;
 
-use crate::core::TravelerId;
+use crate::camera::ExperienceCamera;
+use crate::core::{ExperienceRng, InterpolatedTransform, PerformanceConfig, TravelerId};
 use crate::travelers::{Traveler, TravelerPulse, TravelerState, TravelerVisibility};
 
 /// Aura particle system configuration
@@ -106,29 +107,41 @@ fn calculate_particle_position(angle: f32, radius: f32, height: f32, tilt: Quat)
     tilt * base_pos
 }
 
+/// Particle mesh (small sphere)
+pub fn aura_particle_mesh(aura: &TravelerAura) -> Mesh {
+    Sphere::new(aura.size).mesh().ico(1).unwrap()
+}
+
+/// Unlit, blended particle material in the aura's color
+pub fn aura_particle_material(aura: &TravelerAura) -> StandardMaterial {
+    let base_srgba = aura.color.to_srgba();
+    StandardMaterial {
+        base_color: aura.color,
+        emissive: LinearRgba::new(base_srgba.red, base_srgba.green, base_srgba.blue, 1.0),
+        unlit: true,
+        alpha_mode: AlphaMode::Blend,
+        ..default()
+    }
+}
+
+/// First `ExperienceRng` stream of the aura particles (one per traveler);
+/// they spawn in `Update`, outside the fixed step
+const AURA_STREAM: u64 = 0x6175_7261;
+
 /// Spawn aura particles when traveler is created
 pub fn spawn_aura_particles(
     mut commands: Commands,
     travelers: Query<(Entity, &Traveler), Added<Traveler>>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    rng: Res<ExperienceRng>,
 ) {
     for (entity, traveler) in travelers.iter() {
         let aura = TravelerAura::for_traveler(traveler.id);
-        let mut rng = rand::thread_rng();
-
-        // Create particle mesh (small sphere)
-        let particle_mesh = meshes.add(Sphere::new(aura.size).mesh().ico(1).unwrap());
-
-        // Create particle material
-        let base_srgba = aura.color.to_srgba();
-        let particle_material = materials.add(StandardMaterial {
-            base_color: aura.color,
-            emissive: LinearRgba::new(base_srgba.red, base_srgba.green, base_srgba.blue, 1.0),
-            unlit: true,
-            alpha_mode: AlphaMode::Blend,
-            ..default()
-        });
+        let mut rng = rng.stream(AURA_STREAM + traveler.id as u64);
+
+        let particle_mesh = meshes.add(aura_particle_mesh(&aura));
+        let particle_material = materials.add(aura_particle_material(&aura));
 
         // Spawn particles as children
         commands.entity(entity).with_children(|parent| {
@@ -148,14 +161,16 @@ pub fn spawn_aura_particles(
                 );
 
                 let position = calculate_particle_position(angle, radius, height, tilt);
+                let transform = Transform::from_translation(position);
 
                 parent.spawn((
                     PbrBundle {
                         mesh: particle_mesh.clone(),
                         material: particle_material.clone(),
-                        transform: Transform::from_translation(position),
+                        transform,
                         ..default()
                     },
+                    InterpolatedTransform::new(transform),
                     AuraParticle {
                         angle,
                         radius,
@@ -210,18 +225,27 @@ pub fn animate_aura_particles(
     }
 }
 
-/// Control particle visibility based on density
+/// Control particle visibility based on density, quality tier and camera distance
 pub fn control_particle_density(
-    travelers: Query<(&TravelerAura, &TravelerPulse)>,
+    performance: Res<PerformanceConfig>,
+    camera: Query<&GlobalTransform, With<ExperienceCamera>>,
+    travelers: Query<(&TravelerAura, &TravelerPulse, &GlobalTransform)>,
     mut particles: Query<(&Parent, &mut Visibility, &AuraParticle), With<AuraParticleMarker>>,
 ) {
+    let camera = camera.get_single().ok().map(|transform| transform.translation());
+
     for (parent, mut visibility, particle) in particles.iter_mut() {
-        let Ok((aura, pulse)) = travelers.get(parent.get()) else {
+        let Ok((aura, pulse, transform)) = travelers.get(parent.get()) else {
             continue;
         };
 
+        let lod = camera.map_or(1.0, |camera| {
+            performance.lod_detail(camera.distance(transform.translation()))
+        });
+
         // Density increases with pulse
-        let effective_density = aura.density * (0.5 + pulse.intensity * 0.5);
+        let effective_density =
+            aura.density * (0.5 + pulse.intensity * 0.5) * performance.particle_density * lod;
 
         // Use phase to determine visibility threshold
         let threshold = particle.phase / std::f32::consts::TAU;
@@ -2,6 +2,8 @@
 
 use bevy::prelude::*;
 
+use crate::core::{spawn_warmup_mesh, PipelineWarmupAppExt, TravelerId};
+
 pub mod aura;
 pub mod trails;
 
@@ -14,12 +16,12 @@ pub struct TravelerParticlesPlugin;
 impl Plugin for TravelerParticlesPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Startup, init_trail_mesh_cache)
+            .add_systems(FixedUpdate, animate_aura_particles)
             .add_systems(
                 Update,
                 (
                     // Aura systems
                     spawn_aura_particles,
-                    animate_aura_particles,
                     control_particle_density,
                     fade_aura_with_traveler,
                     // Trail systems
@@ -29,6 +31,18 @@ impl Plugin for TravelerParticlesPlugin {
                     update_trail_particles,
                     control_trail_activation,
                 ),
-            );
+            )
+            // Particle materials differ only in color, so one of each covers all travelers
+            .add_pipeline_warmup("aura particle", |world, transform| {
+                let aura = TravelerAura::for_traveler(TravelerId::Archivist);
+                let (mesh, material) = (aura_particle_mesh(&aura), aura_particle_material(&aura));
+                Some(spawn_warmup_mesh(world, transform, mesh, material))
+            })
+            .add_pipeline_warmup("trail particle", |world, transform| {
+                let trail = TravelerTrail::for_traveler(TravelerId::Archivist);
+                let mesh = Sphere::new(1.0).mesh().ico(0).unwrap();
+                let material = trail_material(trail.color);
+                Some(spawn_warmup_mesh(world, transform, mesh, material))
+            });
     }
 }
@@ -3,7 +3,8 @@
 use bevy::prelude::*;
 use std::collections::VecDeque;
 
-use crate::core::{ExperienceClock, Phase, TravelerId};
+use crate::camera::ExperienceCamera;
+use crate::core::{ExperienceClock, PerformanceConfig, Phase, TravelerId};
 use crate::travelers::Traveler;
 
 /// Configuration for traveler trails
@@ -156,10 +157,25 @@ pub fn update_position_history(
     }
 }
 
-/// Spawn trail particles at history positions
+/// Unlit, blended trail particle material
+pub fn trail_material(color: Color) -> StandardMaterial {
+    let srgba = color.to_srgba();
+    StandardMaterial {
+        base_color: color,
+        emissive: LinearRgba::new(srgba.red * 0.5, srgba.green * 0.5, srgba.blue * 0.5, 1.0),
+        unlit: true,
+        alpha_mode: AlphaMode::Blend,
+        ..default()
+    }
+}
+
+/// Spawn trail particles at history positions (none past the middle LOD distance)
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_trail_particles(
     mut commands: Commands,
     time: Res<Time>,
+    performance: Res<PerformanceConfig>,
+    camera: Query<&GlobalTransform, With<ExperienceCamera>>,
     travelers: Query<(&TravelerTrail, &PositionHistory), Changed<PositionHistory>>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
@@ -185,15 +201,14 @@ pub fn spawn_trail_particles(
                 continue;
             }
 
-            let srgba = trail.color.to_srgba();
-
-            let trail_material = materials.add(StandardMaterial {
-                base_color: trail.color,
-                emissive: LinearRgba::new(srgba.red * 0.5, srgba.green * 0.5, srgba.blue * 0.5, 1.0),
-                unlit: true,
-                alpha_mode: AlphaMode::Blend,
-                ..default()
+            let lod = camera.get_single().map_or(1.0, |camera| {
+                performance.lod_detail(camera.translation().distance(position))
             });
+            if lod < 0.5 {
+                continue;
+            }
+
+            let trail_material = materials.add(trail_material(trail.color));
 
             commands.spawn((
                 PbrBundle {
@@ -205,7 +220,8 @@ pub fn spawn_trail_particles(
                 },
                 TrailParticle {
                     spawn_time: timestamp,
-                    fade_duration: trail.fade_duration,
+                    // Shorter lives on lower tiers, so fewer particles at once
+                    fade_duration: trail.fade_duration * performance.particle_density,
                     color: trail.color,
                     size: trail.size,
                 },
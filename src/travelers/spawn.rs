@@ -9,7 +9,7 @@ use super::{
     TravelerShellMaterial, TravelerState, TravelerVisibility,
 };
 use crate::audio::SpatialAudioSource;
-use crate::core::{TravelerId, TravelerSpawnedEvent};
+use crate::core::{InterpolatedTransform, TravelerId, TravelerSpawnedEvent, WideEventLog};
 use crate::wide_event;
 
 /// Bundle for spawning a traveler entity
@@ -22,11 +22,13 @@ pub struct TravelerBundle {
     pub grief: TravelerGrief,
     pub spatial_audio: SpatialAudioSource,
     pub spatial: SpatialBundle,
+    pub interpolation: InterpolatedTransform,
 }
 
 impl TravelerBundle {
     pub fn new(id: TravelerId, spawn_time: f32) -> Self {
         let def = TravelerDef::get(id);
+        let transform = Transform::from_translation(def.spawn_position);
 
         Self {
             traveler: Traveler::new(id, spawn_time),
@@ -37,13 +39,10 @@ impl TravelerBundle {
             spatial_audio: SpatialAudioSource {
                 volume: 1.0,
                 previous_position: def.spawn_position,
-                computed_gain: 1.0,
-                computed_pan: 0.0,
-                computed_pitch: 1.0,
+                ..default()
             },
-            spatial: SpatialBundle::from_transform(Transform::from_translation(
-                def.spawn_position,
-            )),
+            spatial: SpatialBundle::from_transform(transform),
+            interpolation: InterpolatedTransform::new(transform),
         }
     }
 }
@@ -54,6 +53,7 @@ pub fn handle_traveler_spawns(
     mut events: EventReader<TravelerSpawnedEvent>,
     existing: Query<&Traveler>,
     clock: Res<crate::core::ExperienceClock>,
+    log: Res<WideEventLog>,
 ) {
     for event in events.read() {
         // Check if already spawned
@@ -70,7 +70,7 @@ pub fn handle_traveler_spawns(
         wide_event!("traveler_spawned")
             .with_str("id", event.id.name())
             .with_str("name", event.id.display_name())
-            .emit(clock.elapsed());
+            .emit(&log, clock.elapsed());
     }
 }
 
@@ -93,36 +93,8 @@ pub fn spawn_traveler_visuals(
         // Get or create meshes for this geometry type
         let (core_mesh, shell_mesh, _edge_mesh) = mesh_cache.get_or_create(def.geometry, &mut meshes);
 
-        // Create glow material with traveler's color
-        let base_srgba = def.color.base.to_srgba();
-        let glow_material = glow_materials.add(TravelerGlowMaterial {
-            base_color: LinearRgba::new(base_srgba.red, base_srgba.green, base_srgba.blue, 1.0),
-            emissive: LinearRgba::new(
-                base_srgba.red * 2.0,
-                base_srgba.green * 2.0,
-                base_srgba.blue * 2.0,
-                1.0,
-            ),
-            pulse_intensity: 0.3,
-            pulse_phase: traveler.id as u8 as f32 * 0.7, // Different phase per traveler
-            time: 0.0,
-            fresnel_power: 3.0,
-            inner_glow_strength: 0.5,
-            rim_color: LinearRgba::new(1.0, 0.95, 0.9, 1.0),
-            grief_amount: 0.0,
-            _padding: Vec3::ZERO,
-        });
-
-        // Create shell material for translucent outer layer
-        let shell_material = shell_materials.add(TravelerShellMaterial {
-            base_color: LinearRgba::new(base_srgba.red, base_srgba.green, base_srgba.blue, 0.2),
-            refraction_strength: 0.1,
-            thickness: 0.5,
-            ior: 1.5,
-            pulse_intensity: 0.15,
-            time: 0.0,
-            _padding: Vec3::ZERO,
-        });
+        let glow_material = glow_materials.add(glow_material(traveler.id));
+        let shell_material = shell_materials.add(shell_material(traveler.id));
 
         // Spawn visual meshes as children
         commands.entity(entity).with_children(|parent| {
@@ -154,6 +126,74 @@ pub fn spawn_traveler_visuals(
     }
 }
 
+/// Glow material with the traveler's color
+pub fn glow_material(id: TravelerId) -> TravelerGlowMaterial {
+    let base_srgba = TravelerDef::get(id).color.base.to_srgba();
+    TravelerGlowMaterial {
+        base_color: LinearRgba::new(base_srgba.red, base_srgba.green, base_srgba.blue, 1.0),
+        emissive: LinearRgba::new(
+            base_srgba.red * 2.0,
+            base_srgba.green * 2.0,
+            base_srgba.blue * 2.0,
+            1.0,
+        ),
+        pulse_intensity: 0.3,
+        pulse_phase: id as u8 as f32 * 0.7, // Different phase per traveler
+        time: 0.0,
+        fresnel_power: 3.0,
+        inner_glow_strength: 0.5,
+        rim_color: LinearRgba::new(1.0, 0.95, 0.9, 1.0),
+        grief_amount: 0.0,
+        _padding: Vec3::ZERO,
+    }
+}
+
+/// Shell material for the translucent outer layer
+pub fn shell_material(id: TravelerId) -> TravelerShellMaterial {
+    let base_srgba = TravelerDef::get(id).color.base.to_srgba();
+    TravelerShellMaterial {
+        base_color: LinearRgba::new(base_srgba.red, base_srgba.green, base_srgba.blue, 0.2),
+        refraction_strength: 0.1,
+        thickness: 0.5,
+        ior: 1.5,
+        pulse_intensity: 0.15,
+        time: 0.0,
+        _padding: Vec3::ZERO,
+    }
+}
+
+/// Warm-up copy of a traveler's core and shell (meshes go into the cache)
+pub fn spawn_traveler_warmup(world: &mut World, transform: Transform, id: TravelerId) -> Entity {
+    let geometry = TravelerDef::get(id).geometry;
+    let (core_mesh, shell_mesh, _) =
+        world.resource_scope(|world, mut cache: Mut<TravelerMeshCache>| {
+            cache.get_or_create(geometry, &mut world.resource_mut::<Assets<Mesh>>())
+        });
+    let glow = world
+        .resource_mut::<Assets<TravelerGlowMaterial>>()
+        .add(glow_material(id));
+    let shell = world
+        .resource_mut::<Assets<TravelerShellMaterial>>()
+        .add(shell_material(id));
+
+    world
+        .spawn(MaterialMeshBundle {
+            mesh: core_mesh,
+            material: glow,
+            transform,
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(MaterialMeshBundle {
+                mesh: shell_mesh,
+                material: shell,
+                transform: Transform::from_scale(Vec3::splat(1.1)),
+                ..default()
+            });
+        })
+        .id()
+}
+
 /// Update traveler registry
 #[derive(Resource, Default)]
 pub struct TravelerRegistry {
@@ -168,11 +208,14 @@ pub fn update_traveler_registry(
 ) {
     registry.spawned.clear();
     registry.active.clear();
+    registry.faded.clear();
 
     for (traveler, state) in travelers.iter() {
         registry.spawned.push(traveler.id);
-        if *state == TravelerState::Active {
-            registry.active.push(traveler.id);
+        match state {
+            TravelerState::Active => registry.active.push(traveler.id),
+            TravelerState::Gone => registry.faded.push(traveler.id),
+            _ => {}
         }
     }
 }
@@ -1,13 +1,15 @@
 //! Traveler state - lifecycle, visibility, pulse, grief
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::core::TravelerId;
 
 /// Traveler lifecycle state
-#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum TravelerState {
     /// Spawning in (fade up)
+    #[default]
     Spawning,
     /// Fully alive and active
     Active,
@@ -19,12 +21,6 @@ pub enum TravelerState {
     Gone,
 }
 
-impl Default for TravelerState {
-    fn default() -> Self {
-        TravelerState::Spawning
-    }
-}
-
 /// Traveler visibility/opacity
 #[derive(Component, Debug)]
 pub struct TravelerVisibility {
@@ -57,6 +53,10 @@ pub struct TravelerPulse {
     pub frequency: f32,
     /// Frequency variance
     pub variance: f32,
+    /// Unmodified frequency, grief scales from this
+    pub base_frequency: f32,
+    /// Unmodified variance, grief scales from this
+    pub base_variance: f32,
     /// Is synchronized with others?
     pub synced: bool,
     /// Sync phase offset
@@ -70,6 +70,8 @@ impl TravelerPulse {
             intensity: 0.0,
             frequency,
             variance,
+            base_frequency: frequency,
+            base_variance: variance,
             synced: false,
             sync_offset: 0.0,
         }